@@ -0,0 +1,508 @@
+//! AuRa block authoring for local Gnosis-style devnets (`--gnosis.aura-signer`).
+//!
+//! Gnosis and Chiado never produce AuRa blocks again; this mode exists so
+//! private AuRa networks can be spun up to reproduce historical behavior. The
+//! node keeps its own step schedule (the chain's [`StepDuration`], counted
+//! across duration transitions) and,
+//! whenever [`ValidatorSet::expected_proposer`] names our signer for the
+//! current step, has the node's engine build and import a block, as a
+//! consensus client would post-merge (see [`EngineAuthoringBackend`]). The
+//! [`crate::build::GnosisBlockAssembler`] stamps `aura_step`, the AuRa
+//! difficulty and the seal (see [`AuraAuthor::seal_header`]).
+
+use std::{
+    future::Future,
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use alloy_primitives::{Address, FixedBytes, B256, U256};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use futures_util::StreamExt;
+use gnosis_primitives::header::GnosisHeader;
+use reth::rpc::types::engine::{ForkchoiceState, PayloadStatusEnum};
+use reth_chainspec::EthereumHardforks;
+use reth_engine_primitives::{ConsensusEngineEvent, ConsensusEngineHandle};
+use reth_ethereum_engine_primitives::EthPayloadAttributes;
+use reth_node_api::{BuiltPayload, EngineApiMessageVersion, PayloadKind};
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_primitives_traits::{SealedBlock, SealedHeader};
+use reth_provider::{BlockNumReader, HeaderProvider};
+use reth_tokio_util::EventSender;
+
+use crate::aura::{
+    config::StepDuration,
+    finality_cache::FinalityCache,
+    seal::{calculate_aura_difficulty, compute_seal_hash},
+    validators::ValidatorSet,
+};
+use crate::{
+    engine::GnosisEngineTypes,
+    primitives::{block::GnosisBlock, GnosisNodePrimitives},
+    spec::gnosis_spec::GnosisChainSpec,
+};
+
+/// Secret key used to seal authored AuRa blocks.
+#[derive(Debug, Clone)]
+pub struct AuraSigner {
+    signer: PrivateKeySigner,
+}
+
+impl AuraSigner {
+    /// Load a hex-encoded secp256k1 secret key (optionally `0x`-prefixed,
+    /// surrounding whitespace ignored) — the same format as reth's
+    /// `--p2p-secret-key` file.
+    pub fn from_keyfile(path: &Path) -> eyre::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| eyre::eyre!("failed to read AuRa signer key {}: {e}", path.display()))?;
+        let key = contents.trim();
+        let key = key.strip_prefix("0x").unwrap_or(key);
+        let signer = key
+            .parse::<PrivateKeySigner>()
+            .map_err(|e| eyre::eyre!("invalid AuRa signer key in {}: {e}", path.display()))?;
+        Ok(Self { signer })
+    }
+
+    /// Address blocks are authored (and rewarded) as.
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    /// Sign `seal_hash` into the 65-byte `r || s || v` AuRa seal layout with
+    /// `v` as the raw recovery id (0/1), as OpenEthereum writes it.
+    pub fn sign_seal(&self, seal_hash: &B256) -> eyre::Result<FixedBytes<65>> {
+        let sig = self.signer.sign_hash_sync(seal_hash)?;
+        let mut seal = [0u8; 65];
+        seal[..32].copy_from_slice(&sig.r().to_be_bytes::<32>());
+        seal[32..64].copy_from_slice(&sig.s().to_be_bytes::<32>());
+        seal[64] = sig.v() as u8;
+        Ok(FixedBytes(seal))
+    }
+}
+
+/// Block-authoring parameters for one AuRa signer.
+#[derive(Debug, Clone)]
+pub struct AuraAuthor {
+    signer: AuraSigner,
+    step_duration: StepDuration,
+}
+
+impl AuraAuthor {
+    pub fn new(signer: AuraSigner, step_duration: StepDuration) -> Self {
+        Self {
+            signer,
+            step_duration,
+        }
+    }
+
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    /// AuRa step containing `timestamp`.
+    pub fn step_at(&self, timestamp: u64) -> u64 {
+        self.step_duration.step_at(timestamp)
+    }
+
+    /// Timestamp at which `step` begins.
+    pub fn step_start(&self, step: u64) -> u64 {
+        self.step_duration.step_start(step)
+    }
+
+    /// Turn an assembled pre-merge header into a sealed AuRa header: drop the
+    /// PoW/beacon `mix_hash`/`nonce`, set `aura_step` from the timestamp,
+    /// the AuRa difficulty relative to `parent`, and sign the bare hash.
+    ///
+    /// The header's `beneficiary` must already be our signer — it is part of
+    /// the signed hash and is what consensus checks the seal against.
+    pub fn seal_header(
+        &self,
+        header: &mut GnosisHeader,
+        parent: &GnosisHeader,
+    ) -> eyre::Result<()> {
+        eyre::ensure!(
+            header.beneficiary == self.address(),
+            "AuRa block beneficiary {} is not the configured signer {}",
+            header.beneficiary,
+            self.address()
+        );
+        let step = self.step_at(header.timestamp);
+        let parent_step = parent_step(parent, &self.step_duration);
+        eyre::ensure!(
+            step > parent_step,
+            "AuRa step {step} does not advance past parent step {parent_step}"
+        );
+
+        header.mix_hash = None;
+        header.nonce = None;
+        header.difficulty = calculate_aura_difficulty(parent_step, step);
+        header.aura_step = Some(U256::from(step));
+        header.aura_seal = Some(self.signer.sign_seal(&compute_seal_hash(header))?);
+        Ok(())
+    }
+}
+
+/// Step of `parent`. Genesis (and any header without a seal) falls back to
+/// the step containing its timestamp.
+pub fn parent_step(parent: &GnosisHeader, step_duration: &StepDuration) -> u64 {
+    parent
+        .aura_step
+        .map(|step| step.saturating_to())
+        .unwrap_or_else(|| step_duration.step_at(parent.timestamp))
+}
+
+/// Validators eligible to propose the child of `parent_hash` at height
+/// `block_number`: the static list, or — for contract-based sets — the
+/// `getValidators()` result recorded in the parent's rolling-finality state.
+pub fn proposer_set(
+    validators: &ValidatorSet,
+    block_number: u64,
+    parent_hash: &B256,
+    finality_cache: &FinalityCache,
+) -> Option<Vec<Address>> {
+    if let Some(list) = validators.try_get_list_validators(block_number) {
+        return Some(list.to_vec());
+    }
+    finality_cache
+        .get(parent_hash)
+        .and_then(|rf| rf.sealed_validators().map(<[Address]>::to_vec))
+}
+
+/// Seconds since the Unix epoch.
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Chain access and block production hooks for [`run_aura_authoring`].
+pub trait AuraAuthoringBackend: Send + Sync {
+    /// Current canonical head.
+    fn head(&self) -> eyre::Result<SealedHeader<GnosisHeader>>;
+    /// Build a block on top of `parent` at `timestamp`, with our signer as
+    /// beneficiary.
+    fn build(
+        &self,
+        parent: SealedHeader<GnosisHeader>,
+        timestamp: u64,
+        beneficiary: Address,
+    ) -> impl Future<Output = eyre::Result<SealedBlock<GnosisBlock>>> + Send;
+    /// Make an authored block canonical.
+    fn import(
+        &self,
+        block: SealedBlock<GnosisBlock>,
+    ) -> impl Future<Output = eyre::Result<()>> + Send;
+}
+
+/// Author blocks until the merge block is reached. At every step boundary the
+/// loop checks whether our signer is the expected proposer for the child of
+/// the current head and, if so, builds, seals and imports it. Failures are
+/// logged and the step skipped; the next step starts from the head again.
+pub async fn run_aura_authoring<B: AuraAuthoringBackend>(
+    author: Arc<AuraAuthor>,
+    chain_spec: Arc<GnosisChainSpec>,
+    finality_cache: Arc<FinalityCache>,
+    backend: B,
+) -> eyre::Result<()> {
+    let Some(aura_config) = chain_spec.aura_config.as_ref() else {
+        eyre::bail!("--gnosis.aura-signer requires a chain spec with an `aura` section");
+    };
+    tracing::info!(
+        target: "reth::gnosis",
+        signer = %author.address(),
+        "AuRa authoring enabled"
+    );
+
+    loop {
+        let now = now_secs();
+        let next_step_at = author.step_start(author.step_at(now) + 1);
+        tokio::time::sleep(Duration::from_secs(next_step_at.saturating_sub(now))).await;
+
+        let head = match backend.head() {
+            Ok(head) => head,
+            Err(err) => {
+                tracing::warn!(target: "reth::gnosis", %err, "AuRa authoring: failed to read canonical head");
+                continue;
+            }
+        };
+        let number = head.number + 1;
+        if chain_spec.is_paris_active_at_block(number) {
+            tracing::info!(
+                target: "reth::gnosis",
+                block = number,
+                "AuRa authoring: merge block reached, stopping"
+            );
+            return Ok(());
+        }
+
+        let step = author.step_at(now_secs());
+        if step <= parent_step(head.header(), &author.step_duration) {
+            continue;
+        }
+        let Some(validators) = proposer_set(
+            &aura_config.validators,
+            number,
+            &head.hash(),
+            &finality_cache,
+        ) else {
+            tracing::warn!(
+                target: "reth::gnosis",
+                block = number,
+                "AuRa authoring: validator set unknown for parent, skipping step"
+            );
+            continue;
+        };
+        if ValidatorSet::expected_proposer(step, &validators) != Some(author.address()) {
+            continue;
+        }
+
+        let timestamp = author.step_start(step);
+        let block = match backend.build(head, timestamp, author.address()).await {
+            Ok(block) => block,
+            Err(err) => {
+                tracing::warn!(target: "reth::gnosis", block = number, step, %err, "AuRa authoring: failed to build block");
+                continue;
+            }
+        };
+        let hash = block.hash();
+        let tx_count = block.body().transactions.len();
+        if let Err(err) = backend.import(block).await {
+            tracing::warn!(target: "reth::gnosis", block = number, step, %hash, %err, "AuRa authoring: failed to import block");
+            continue;
+        }
+        tracing::info!(
+            target: "reth::gnosis",
+            block = number,
+            step,
+            %hash,
+            txs = tx_count,
+            "AuRa authoring: sealed block"
+        );
+    }
+}
+
+/// How long [`EngineAuthoringBackend::build`] waits for the engine to insert
+/// a built block into its tree.
+const INSERT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// [`AuraAuthoringBackend`] driving the node's own engine, the way a
+/// consensus client would: a forkchoice update with payload attributes
+/// starts a job on the node's payload builder, whose result (sealed by
+/// [`crate::build::GnosisBlockAssembler`]) the node's engine launcher inserts
+/// into the tree; a second forkchoice update makes it canonical. Persistence,
+/// gas limit and payload ids are the engine's and payload builder's.
+///
+/// The block is not submitted through `new_payload`: execution payloads have
+/// no field for the AuRa step and seal, so the engine would rebuild a header
+/// with another hash and reject it.
+#[derive(Debug)]
+pub struct EngineAuthoringBackend<Provider> {
+    provider: Provider,
+    chain_spec: Arc<GnosisChainSpec>,
+    engine: ConsensusEngineHandle<GnosisEngineTypes>,
+    engine_events: EventSender<ConsensusEngineEvent<GnosisNodePrimitives>>,
+    payload_builder: PayloadBuilderHandle<GnosisEngineTypes>,
+}
+
+impl<Provider> EngineAuthoringBackend<Provider> {
+    /// The node's evm config must carry the author (see
+    /// [`crate::GnosisArgs::aura_signer`]) so the payload builder seals
+    /// pre-merge blocks.
+    pub fn new(
+        provider: Provider,
+        chain_spec: Arc<GnosisChainSpec>,
+        engine: ConsensusEngineHandle<GnosisEngineTypes>,
+        engine_events: EventSender<ConsensusEngineEvent<GnosisNodePrimitives>>,
+        payload_builder: PayloadBuilderHandle<GnosisEngineTypes>,
+    ) -> Self {
+        Self {
+            provider,
+            chain_spec,
+            engine,
+            engine_events,
+            payload_builder,
+        }
+    }
+}
+
+impl<Provider> AuraAuthoringBackend for EngineAuthoringBackend<Provider>
+where
+    Provider: BlockNumReader + HeaderProvider<Header = GnosisHeader> + Send + Sync,
+{
+    fn head(&self) -> eyre::Result<SealedHeader<GnosisHeader>> {
+        let number = self.provider.best_block_number()?;
+        self.provider
+            .sealed_header(number)?
+            .ok_or_else(|| eyre::eyre!("missing header for canonical head {number}"))
+    }
+
+    /// Returns once the engine has inserted the built block, so that
+    /// [`Self::import`] never finds it missing.
+    async fn build(
+        &self,
+        parent: SealedHeader<GnosisHeader>,
+        timestamp: u64,
+        beneficiary: Address,
+    ) -> eyre::Result<SealedBlock<GnosisBlock>> {
+        let spec = &self.chain_spec;
+        let attributes = EthPayloadAttributes {
+            timestamp,
+            // AuRa headers carry no mix hash (`AuraAuthor::seal_header` drops
+            // it) and pre-merge `DIFFICULTY` reads the header difficulty, so
+            // no block ever observes this value.
+            prev_randao: B256::ZERO,
+            suggested_fee_recipient: beneficiary,
+            withdrawals: spec
+                .is_shanghai_active_at_timestamp(timestamp)
+                .then(Vec::new),
+            parent_beacon_block_root: spec
+                .is_cancun_active_at_timestamp(timestamp)
+                .then_some(B256::ZERO),
+            ..Default::default()
+        };
+        // Subscribed before the job starts, so the insertion cannot be missed.
+        let mut events = self.engine_events.new_listener();
+        let updated = self
+            .engine
+            .fork_choice_updated(
+                forkchoice(parent.hash()),
+                Some(attributes),
+                forkchoice_version(spec, timestamp),
+            )
+            .await?;
+        let payload_id = updated.payload_id.ok_or_else(|| {
+            eyre::eyre!(
+                "engine started no payload job on {}: {:?}",
+                parent.hash(),
+                updated.payload_status.status
+            )
+        })?;
+        let payload = self
+            .payload_builder
+            .resolve_kind(payload_id, PayloadKind::WaitForPending)
+            .await
+            .ok_or_else(|| eyre::eyre!("payload job {payload_id} not found"))??;
+        let block = payload.block().clone();
+
+        let hash = block.hash();
+        let inserted = async {
+            while let Some(event) = events.next().await {
+                match event {
+                    ConsensusEngineEvent::CanonicalBlockAdded(executed, _)
+                    | ConsensusEngineEvent::ForkBlockAdded(executed, _)
+                        if executed.recovered_block().hash() == hash =>
+                    {
+                        return Ok(())
+                    }
+                    ConsensusEngineEvent::InvalidBlock(invalid) if invalid.hash() == hash => {
+                        eyre::bail!("engine rejected built block {hash}")
+                    }
+                    _ => {}
+                }
+            }
+            eyre::bail!("engine event stream closed")
+        };
+        tokio::time::timeout(INSERT_TIMEOUT, inserted)
+            .await
+            .map_err(|_| eyre::eyre!("engine did not insert built block {hash}"))??;
+        Ok(block)
+    }
+
+    async fn import(&self, block: SealedBlock<GnosisBlock>) -> eyre::Result<()> {
+        let updated = self
+            .engine
+            .fork_choice_updated(
+                forkchoice(block.hash()),
+                None,
+                forkchoice_version(&self.chain_spec, block.header().timestamp),
+            )
+            .await?;
+        match updated.payload_status.status {
+            PayloadStatusEnum::Valid => Ok(()),
+            status => eyre::bail!("engine refused authored block {}: {status:?}", block.hash()),
+        }
+    }
+}
+
+/// `engine_forkchoiceUpdated` version whose payload attributes match the
+/// forks active at `timestamp`.
+fn forkchoice_version(spec: &GnosisChainSpec, timestamp: u64) -> EngineApiMessageVersion {
+    if spec.is_cancun_active_at_timestamp(timestamp) {
+        EngineApiMessageVersion::V3
+    } else if spec.is_shanghai_active_at_timestamp(timestamp) {
+        EngineApiMessageVersion::V2
+    } else {
+        EngineApiMessageVersion::V1
+    }
+}
+
+/// Forkchoice state with `head` as head and AuRa finality left to the node.
+fn forkchoice(head: B256) -> ForkchoiceState {
+    ForkchoiceState {
+        head_block_hash: head,
+        safe_block_hash: B256::ZERO,
+        finalized_block_hash: B256::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aura::seal::recover_seal_author;
+
+    fn author() -> AuraAuthor {
+        let signer = AuraSigner {
+            signer: PrivateKeySigner::from_bytes(&B256::repeat_byte(0x42)).unwrap(),
+        };
+        AuraAuthor::new(signer, StepDuration::fixed(5))
+    }
+
+    #[test]
+    fn sealed_header_recovers_to_signer() {
+        let author = author();
+        let parent = GnosisHeader {
+            timestamp: 100,
+            aura_step: Some(U256::from(20u64)),
+            ..Default::default()
+        };
+        let mut header = GnosisHeader {
+            number: 1,
+            timestamp: 110,
+            beneficiary: author.address(),
+            mix_hash: Some(B256::ZERO),
+            nonce: Some(Default::default()),
+            ..Default::default()
+        };
+        author.seal_header(&mut header, &parent).unwrap();
+
+        assert_eq!(header.aura_step, Some(U256::from(22u64)));
+        assert_eq!(header.difficulty, calculate_aura_difficulty(20, 22));
+        assert!(header.mix_hash.is_none() && header.nonce.is_none());
+        assert_eq!(recover_seal_author(&header).unwrap(), author.address());
+    }
+
+    #[test]
+    fn seal_rejects_foreign_beneficiary_and_stale_step() {
+        let author = author();
+        let parent = GnosisHeader {
+            timestamp: 100,
+            ..Default::default()
+        };
+        let mut foreign = GnosisHeader {
+            timestamp: 110,
+            beneficiary: Address::repeat_byte(1),
+            ..Default::default()
+        };
+        assert!(author.seal_header(&mut foreign, &parent).is_err());
+
+        let mut stale = GnosisHeader {
+            timestamp: 104,
+            beneficiary: author.address(),
+            ..Default::default()
+        };
+        assert!(author.seal_header(&mut stale, &parent).is_err());
+    }
+}
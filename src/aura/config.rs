@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, Bytes};
+use alloy_primitives::{Address, Bytes, U256};
 use serde::Deserialize;
 use std::collections::BTreeMap;
 
@@ -7,21 +7,62 @@ use super::validators::{ValidatorSet, ValidatorSetKind};
 /// AuRa consensus configuration parsed from the chain spec genesis JSON.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AuraConfig {
-    /// Duration of each step in seconds.
-    pub step_duration: u64,
+    /// Duration of each step in seconds, per timestamp-keyed transition.
+    pub step_duration: StepDuration,
     /// Validator set configuration.
     pub validators: ValidatorSet,
     /// Block reward contract transitions: block_number -> contract_address.
     pub block_reward_contract_transitions: BTreeMap<u64, Address>,
+    /// Fixed block reward transitions: block_number -> wei credited to the
+    /// block author. Only paid while no reward contract is active (see
+    /// [`Self::fixed_block_reward_at`]). Empty on Gnosis and Chiado.
+    pub block_reward: BTreeMap<u64, u128>,
+    /// Block gas limit contract transitions: block_number -> contract_address.
+    /// From each transition on, `blockGasLimit()` on the contract dictates
+    /// the block gas limit (see [`Self::block_gas_limit_contract_at`]).
+    pub block_gas_limit_contract_transitions: BTreeMap<u64, Address>,
+    /// First block whose gas limit must stay within the 1/1024 ramp of its
+    /// parent's outside `blockGasLimitContractTransitions` ranges
+    /// (`gasLimitRampTransition`). `u64::MAX` when absent, as on Gnosis and
+    /// Chiado, whose pre-merge history is only bounded by the contract.
+    pub gas_limit_ramp_transition: u64,
     /// POSDAO activation block. Required in genesis for AuRa chains —
     /// both we ship (Gnosis, Chiado) supply this, so an absent
     /// `posdaoTransition` is treated as a chain-spec misconfig and the
     /// parser errors out.
     pub posdao_transition: u64,
+    /// First block from which the step must match the header timestamp and
+    /// a step lower than the parent's is rejected (`validateStepTransition`).
+    /// `None` when absent (Gnosis, Chiado): the step is never matched against
+    /// the timestamp, and any step not above the parent's is rejected.
+    pub validate_step_transition: Option<u64>,
+    /// First block whose difficulty is checked against the AuRa score
+    /// formula (`validateScoreTransition`, default 0).
+    pub validate_score_transition: u64,
+    /// First block finalized under a strict two-thirds majority instead of
+    /// the geth 1/2 rule, as OpenEthereum's `RollingFinality` and
+    /// Nethermind's `AuRaBlockFinalizationManager` apply it. `u64::MAX` when
+    /// `twoThirdsMajorityTransition` is absent (Gnosis). Chiado sets it to 0,
+    /// but its validator sets are all lists, so finality never gates a
+    /// `finalizeChange` there and only the reported finality changes.
+    pub two_thirds_majority_transition: u64,
     /// Pre-merge bytecode rewrites: block_number -> { contract_address -> new_bytecode }.
     /// Used by AuRa chains to upgrade contract bytecode at hardfork blocks
     /// (e.g., Gnosis token contract rewrite at block 21,735,000).
     pub rewrite_bytecode: BTreeMap<u64, BTreeMap<Address, Bytes>>,
+    /// Timestamp-keyed bytecode rewrites: timestamp -> { contract_address ->
+    /// new_bytecode }. Applied at the first block whose timestamp reaches the
+    /// key, pre- and post-merge (see [`Self::timestamp_bytecode_rewrites`]).
+    pub rewrite_bytecode_timestamp: BTreeMap<u64, BTreeMap<Address, Bytes>>,
+    /// Name registrar (`registrar`) resolving the `service_transaction_checker`
+    /// Certifier that whitelists zero-gas-price senders.
+    pub registrar: Option<Address>,
+    /// Transaction permission contract (`transactionPermissionContract`)
+    /// deciding which senders may send basic, call or create transactions.
+    pub transaction_permission_contract: Option<Address>,
+    /// First block checked against [`Self::transaction_permission_contract`]
+    /// (`transactionPermissionContractTransition`, default 0).
+    pub transaction_permission_contract_transition: u64,
 }
 
 /// Raw JSON structure for the "aura" section in chain spec.
@@ -33,21 +74,10 @@ pub struct AuraConfig {
 /// AuRa again, so historical-sync is the only thing reth_gnosis cares about
 /// and these fields are not load-bearing for replaying historical blocks:
 ///
-/// - `blockReward` — fixed-amount reward transitions. Gnosis uses contract-
-///   based rewards (`blockRewardContractTransitions`), not flat amounts.
 /// - `maximumUncleCount` / `maximumUncleCountTransition` — AuRa disallows
 ///   uncles, so the count is always 0.
-/// - `validateScoreTransition` / `validateStepTransition` — gating block
-///   numbers below which difficulty / step monotonicity isn't checked. We
-///   always validate; the reference chains don't gate.
 /// - `randomnessContractAddress` — POSDAO randomness for block production.
 ///   Sync-only clients don't produce blocks.
-/// - `twoThirdsMajorityTransition` — switches finality threshold from 1/2
-///   to 2/3 at this block. Not active on Gnosis or Chiado in the synced
-///   range; would matter only on a chain that activated it.
-/// - `rewriteBytecodeTimestamp` — bytecode rewrite indexed by timestamp
-///   instead of block number. Only `rewriteBytecode` (block-number-keyed)
-///   is in use on Gnosis (for AuRa).
 /// - `withdrawalContractAddress` — post-Shanghai withdrawal contract. We
 ///   resolve the equivalent address through the chain spec elsewhere.
 ///
@@ -58,7 +88,7 @@ pub struct AuraConfig {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct RawAuraConfig {
-    step_duration: u64,
+    step_duration: RawStepDuration,
     validators: RawValidators,
     #[serde(default)]
     block_reward_contract_address: Option<Address>,
@@ -67,15 +97,82 @@ struct RawAuraConfig {
     #[serde(default)]
     block_reward_contract_transitions: Option<BTreeMap<StringNum, Address>>,
     #[serde(default)]
+    block_reward: Option<RawBlockReward>,
+    #[serde(default)]
+    block_gas_limit_contract_transitions: Option<BTreeMap<StringNum, Address>>,
+    #[serde(default)]
+    gas_limit_ramp_transition: Option<u64>,
+    #[serde(default)]
     posdao_transition: Option<u64>,
     #[serde(default)]
+    two_thirds_majority_transition: Option<u64>,
+    #[serde(default)]
+    validate_step_transition: Option<u64>,
+    #[serde(default)]
+    validate_score_transition: Option<u64>,
+    #[serde(default)]
     rewrite_bytecode: Option<BTreeMap<StringNum, BTreeMap<Address, Bytes>>>,
+    #[serde(default)]
+    rewrite_bytecode_timestamp: Option<BTreeMap<StringNum, BTreeMap<Address, Bytes>>>,
+    #[serde(default)]
+    registrar: Option<Address>,
+    #[serde(default)]
+    transaction_permission_contract: Option<Address>,
+    #[serde(default)]
+    transaction_permission_contract_transition: Option<Quantity>,
+}
+
+/// `stepDuration` is either a single number of seconds or a
+/// `{ "timestamp": seconds }` transition map.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawStepDuration {
+    Fixed(u64),
+    Transitions(BTreeMap<StringNum, u64>),
+}
+
+/// `blockReward` is either a single amount or a `{ "block": amount }`
+/// transition map. Amounts are hex or decimal quantities, in wei.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawBlockReward {
+    Fixed(U256),
+    Transitions(BTreeMap<StringNum, U256>),
 }
 
 /// Wrapper for block numbers that may appear as strings in JSON.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 struct StringNum(u64);
 
+/// Block number written as a JSON number or a decimal or `0x` hex string, as
+/// Nethermind chain specs do.
+#[derive(Debug, Clone, Copy)]
+struct Quantity(u64);
+
+impl<'de> Deserialize<'de> for Quantity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(u64),
+            String(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(number) => Ok(Quantity(number)),
+            Raw::String(s) => match s.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16),
+                None => s.parse(),
+            }
+            .map(Quantity)
+            .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for StringNum {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -112,6 +209,14 @@ impl AuraConfig {
     /// Parse AuRa config from the genesis JSON "aura" field.
     pub fn from_json_value(value: &serde_json::Value) -> Result<Self, serde_json::Error> {
         let raw: RawAuraConfig = serde_json::from_value(value.clone())?;
+        let step_duration = StepDuration::new(match raw.step_duration {
+            RawStepDuration::Fixed(seconds) => BTreeMap::from([(0, seconds)]),
+            RawStepDuration::Transitions(transitions) => transitions
+                .into_iter()
+                .map(|(timestamp, seconds)| (timestamp.0, seconds))
+                .collect(),
+        })
+        .map_err(serde::de::Error::custom)?;
 
         // Parse validator sets
         let mut sets = BTreeMap::new();
@@ -143,6 +248,26 @@ impl AuraConfig {
             }
         }
 
+        let block_reward = match raw.block_reward {
+            None => BTreeMap::new(),
+            Some(RawBlockReward::Fixed(amount)) => BTreeMap::from([(StringNum(0), amount)]),
+            Some(RawBlockReward::Transitions(transitions)) => transitions,
+        }
+        .into_iter()
+        .map(|(block_num, amount)| {
+            u128::try_from(amount)
+                .map(|amount| (block_num.0, amount))
+                .map_err(|_| serde::de::Error::custom(format!("blockReward too large: {amount}")))
+        })
+        .collect::<Result<_, serde_json::Error>>()?;
+
+        let block_gas_limit_contract_transitions = raw
+            .block_gas_limit_contract_transitions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(block_num, addr)| (block_num.0, addr))
+            .collect();
+
         // Parse rewrite_bytecode
         let mut rewrite_bytecode = BTreeMap::new();
         if let Some(rewrites) = raw.rewrite_bytecode {
@@ -161,13 +286,225 @@ impl AuraConfig {
         })?;
 
         Ok(AuraConfig {
-            step_duration: raw.step_duration,
+            step_duration,
             validators,
             block_reward_contract_transitions,
+            block_reward,
+            block_gas_limit_contract_transitions,
+            gas_limit_ramp_transition: raw.gas_limit_ramp_transition.unwrap_or(u64::MAX),
             posdao_transition,
+            validate_step_transition: raw.validate_step_transition,
+            validate_score_transition: raw.validate_score_transition.unwrap_or_default(),
+            two_thirds_majority_transition: raw.two_thirds_majority_transition.unwrap_or(u64::MAX),
             rewrite_bytecode,
+            rewrite_bytecode_timestamp: raw
+                .rewrite_bytecode_timestamp
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(timestamp, contracts)| (timestamp.0, contracts))
+                .collect(),
+            registrar: raw.registrar,
+            transaction_permission_contract: raw.transaction_permission_contract,
+            transaction_permission_contract_transition: raw
+                .transaction_permission_contract_transition
+                .map_or(0, |transition| transition.0),
         })
     }
+
+    /// Transaction permission contract checked for the transactions of
+    /// `block_number`, if any.
+    pub fn transaction_permission_contract_at(&self, block_number: u64) -> Option<Address> {
+        self.transaction_permission_contract
+            .filter(|_| block_number >= self.transaction_permission_contract_transition)
+    }
+
+    /// AuRa step containing `timestamp`.
+    pub fn step_at(&self, timestamp: u64) -> u64 {
+        self.step_duration.step_at(timestamp)
+    }
+
+    /// Block reward contract active at `block_number`, if any.
+    pub fn block_reward_contract_at(&self, block_number: u64) -> Option<Address> {
+        self.block_reward_contract_transitions
+            .range(..=block_number)
+            .next_back()
+            .map(|(_, addr)| *addr)
+    }
+
+    /// Fixed reward (wei) for the author of `block_number`: the latest
+    /// `blockReward` transition at or below it, unless a reward contract is
+    /// active there, which then decides the rewards instead (OpenEthereum /
+    /// Nethermind semantics). `None` if no transition applies or the amount
+    /// is zero.
+    pub fn fixed_block_reward_at(&self, block_number: u64) -> Option<u128> {
+        if self.block_reward_contract_at(block_number).is_some() {
+            return None;
+        }
+        self.block_reward
+            .range(..=block_number)
+            .next_back()
+            .map(|(_, amount)| *amount)
+            .filter(|amount| *amount != 0)
+    }
+
+    /// Timestamp-keyed rewrites crossed between a parent at
+    /// `parent_timestamp` and its child at `timestamp`, i.e. keys with
+    /// `parent_timestamp < key <= timestamp`, in key order. Same edge
+    /// detection as timestamp contract rewrite forks, so each rewrite fires exactly once
+    /// on a chain regardless of which block number reaches it.
+    pub fn timestamp_bytecode_rewrites(
+        &self,
+        parent_timestamp: u64,
+        timestamp: u64,
+    ) -> impl Iterator<Item = &BTreeMap<Address, Bytes>> {
+        let range = (parent_timestamp < timestamp).then_some((
+            std::ops::Bound::Excluded(parent_timestamp),
+            std::ops::Bound::Included(timestamp),
+        ));
+        range
+            .into_iter()
+            .flat_map(|range| self.rewrite_bytecode_timestamp.range(range))
+            .map(|(_, rewrites)| rewrites)
+    }
+
+    /// Block gas limit contract governing `block_number`, if it falls in a
+    /// `blockGasLimitContractTransitions` range.
+    pub fn block_gas_limit_contract_at(&self, block_number: u64) -> Option<Address> {
+        self.block_gas_limit_contract_transitions
+            .range(..=block_number)
+            .next_back()
+            .map(|(_, addr)| *addr)
+    }
+
+    /// Whether the header of `block_number` is checked against the gas limit
+    /// ramp rule: from `gasLimitRampTransition` on, outside contract ranges.
+    pub fn gas_limit_ramp_at(&self, block_number: u64) -> bool {
+        block_number >= self.gas_limit_ramp_transition
+            && self.block_gas_limit_contract_at(block_number).is_none()
+    }
+}
+
+/// Step-duration schedule (`stepDuration`), keyed by timestamp as in
+/// OpenEthereum and Nethermind.
+///
+/// There is deliberately no block-keyed `step_duration_at(block)`: both
+/// clients read the `stepDuration` map keys as timestamps, so a chainspec
+/// written for them would switch at the wrong time if read as block numbers.
+/// Look durations up by the block's timestamp ([`Self::duration_at`]) and
+/// steps through [`Self::step_at`].
+///
+/// Steps are counted across transitions rather than recomputed as
+/// `timestamp / duration`, which would jump backwards when the duration
+/// grows. A transition takes effect at the first step boundary of the
+/// previous duration at or after its timestamp, and numbering continues from
+/// the step that starts there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepDuration {
+    /// Ascending by start; the first period starts at timestamp 0, step 0.
+    periods: Vec<StepPeriod>,
+}
+
+/// Stretch of the schedule with one step duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StepPeriod {
+    start_timestamp: u64,
+    start_step: u64,
+    duration: u64,
+}
+
+impl StepPeriod {
+    fn step_at(&self, timestamp: u64) -> u64 {
+        self.start_step + (timestamp - self.start_timestamp) / self.duration
+    }
+}
+
+impl StepDuration {
+    /// Build from `timestamp -> seconds` transitions. Requires a transition
+    /// at timestamp 0 and non-zero durations.
+    pub fn new(transitions: BTreeMap<u64, u64>) -> Result<Self, String> {
+        if transitions
+            .first_key_value()
+            .map(|(timestamp, _)| *timestamp)
+            != Some(0)
+        {
+            return Err("AuRa `stepDuration` must start at timestamp 0".to_string());
+        }
+        if let Some(timestamp) = transitions
+            .iter()
+            .find_map(|(timestamp, seconds)| (*seconds == 0).then_some(timestamp))
+        {
+            return Err(format!(
+                "AuRa `stepDuration` must be non-zero (transition at timestamp {timestamp})"
+            ));
+        }
+
+        let mut periods: Vec<StepPeriod> = Vec::with_capacity(transitions.len());
+        for (timestamp, duration) in transitions {
+            let period = match periods.last() {
+                None => StepPeriod {
+                    start_timestamp: 0,
+                    start_step: 0,
+                    duration,
+                },
+                Some(prev) => {
+                    // Round up to the previous duration's next step boundary.
+                    let steps = (timestamp - prev.start_timestamp).div_ceil(prev.duration);
+                    StepPeriod {
+                        start_timestamp: steps
+                            .checked_mul(prev.duration)
+                            .and_then(|elapsed| elapsed.checked_add(prev.start_timestamp))
+                            .ok_or_else(|| {
+                                format!("AuRa `stepDuration` transition at {timestamp} overflows")
+                            })?,
+                        start_step: prev.start_step + steps,
+                        duration,
+                    }
+                }
+            };
+            periods.push(period);
+        }
+        Ok(Self { periods })
+    }
+
+    /// The same duration at every timestamp.
+    pub fn fixed(seconds: u64) -> Self {
+        Self {
+            periods: vec![StepPeriod {
+                start_timestamp: 0,
+                start_step: 0,
+                duration: seconds.max(1),
+            }],
+        }
+    }
+
+    /// Period containing `timestamp`.
+    fn period_at(&self, timestamp: u64) -> &StepPeriod {
+        let index = self
+            .periods
+            .partition_point(|period| period.start_timestamp <= timestamp);
+        &self.periods[index.saturating_sub(1)]
+    }
+
+    /// Duration (seconds) of the step containing `timestamp`.
+    pub fn duration_at(&self, timestamp: u64) -> u64 {
+        self.period_at(timestamp).duration
+    }
+
+    /// Step containing `timestamp`.
+    pub fn step_at(&self, timestamp: u64) -> u64 {
+        self.period_at(timestamp).step_at(timestamp)
+    }
+
+    /// Timestamp at which `step` begins.
+    pub fn step_start(&self, step: u64) -> u64 {
+        let index = self
+            .periods
+            .partition_point(|period| period.start_step <= step);
+        let period = &self.periods[index.saturating_sub(1)];
+        (step - period.start_step)
+            .saturating_mul(period.duration)
+            .saturating_add(period.start_timestamp)
+    }
 }
 
 #[cfg(test)]
@@ -257,6 +594,246 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_two_thirds_majority_transition() {
+        let mut v = json!({
+            "stepDuration": 5,
+            "posdaoTransition": 0,
+            "validators": { "multi": { "0": { "list": ["0x0000000000000000000000000000000000000001"] } } }
+        });
+        let cfg = AuraConfig::from_json_value(&v).unwrap();
+        assert_eq!(cfg.two_thirds_majority_transition, u64::MAX);
+
+        v["twoThirdsMajorityTransition"] = json!(1000);
+        let cfg = AuraConfig::from_json_value(&v).unwrap();
+        assert_eq!(cfg.two_thirds_majority_transition, 1000);
+    }
+
+    #[test]
+    fn two_thirds_majority_transition_of_bundled_specs() {
+        use crate::spec::{
+            chains::{CHIADO_GENESIS, GNOSIS_GENESIS},
+            gnosis_spec::GnosisChainSpec,
+        };
+
+        let chiado = GnosisChainSpec::from(CHIADO_GENESIS.clone())
+            .aura_config
+            .unwrap();
+        assert_eq!(chiado.two_thirds_majority_transition, 0);
+        // The threshold only decides when a pending `InitiateChange` is
+        // finalized; Chiado never runs a contract-based set to emit one.
+        for block in [0, 67_334, u64::MAX] {
+            assert_eq!(chiado.validators.contract_address_at(block), None);
+        }
+
+        let gnosis = GnosisChainSpec::from(GNOSIS_GENESIS.clone())
+            .aura_config
+            .unwrap();
+        assert_eq!(gnosis.two_thirds_majority_transition, u64::MAX);
+    }
+
+    #[test]
+    fn parse_step_duration_scalar_and_transitions() {
+        let mut v = json!({
+            "stepDuration": 5,
+            "posdaoTransition": 0,
+            "validators": { "multi": { "0": { "list": ["0x0000000000000000000000000000000000000001"] } } }
+        });
+        let cfg = AuraConfig::from_json_value(&v).unwrap();
+        assert_eq!(cfg.step_at(0), 0);
+        assert_eq!(cfg.step_at(1004), 200);
+        assert_eq!(cfg.step_duration.duration_at(u64::MAX), 5);
+
+        v["stepDuration"] = json!({ "0": 5, "1000": 3 });
+        let cfg = AuraConfig::from_json_value(&v).unwrap();
+        assert_eq!(cfg.step_duration.duration_at(999), 5);
+        assert_eq!(cfg.step_duration.duration_at(1000), 3);
+        assert_eq!(cfg.step_at(1003), 201);
+
+        v["stepDuration"] = json!({ "0": 5, "1000": 0 });
+        assert!(AuraConfig::from_json_value(&v).is_err());
+        v["stepDuration"] = json!({ "1000": 5 });
+        assert!(AuraConfig::from_json_value(&v).is_err());
+        v["stepDuration"] = json!(0);
+        assert!(AuraConfig::from_json_value(&v).is_err());
+    }
+
+    #[test]
+    fn steps_continue_across_increasing_transition() {
+        // Aligned: 5s steps until timestamp 1000 (step 200), 10s after.
+        let schedule = StepDuration::new(BTreeMap::from([(0, 5), (1000, 10)])).unwrap();
+        assert_eq!(schedule.step_at(999), 199);
+        assert_eq!(schedule.step_at(1000), 200);
+        assert_eq!(schedule.step_at(1009), 200);
+        assert_eq!(schedule.step_at(1010), 201);
+        assert_eq!(schedule.step_start(201), 1010);
+        assert_eq!(schedule.step_start(199), 995);
+
+        // Unaligned: the transition waits for the 5s boundary at 1005.
+        let schedule = StepDuration::new(BTreeMap::from([(0, 5), (1003, 10)])).unwrap();
+        assert_eq!(schedule.step_at(1004), 200);
+        assert_eq!(schedule.step_at(1005), 201);
+        assert_eq!(schedule.duration_at(1004), 5);
+        assert_eq!(schedule.step_at(1015), 202);
+
+        let steps: Vec<_> = (900..1200).map(|t| schedule.step_at(t)).collect();
+        assert!(
+            steps.windows(2).all(|w| w[0] <= w[1]),
+            "step went backwards"
+        );
+        for step in 180..240 {
+            assert_eq!(schedule.step_at(schedule.step_start(step)), step);
+        }
+    }
+
+    #[test]
+    fn parse_fixed_block_reward_transitions() {
+        let mut v = json!({
+            "stepDuration": 5,
+            "posdaoTransition": 0,
+            "validators": { "multi": { "0": { "list": ["0x0000000000000000000000000000000000000001"] } } },
+            "blockReward": "0xde0b6b3a7640000"
+        });
+        let cfg = AuraConfig::from_json_value(&v).unwrap();
+        assert_eq!(
+            cfg.fixed_block_reward_at(0),
+            Some(1_000_000_000_000_000_000)
+        );
+
+        v["blockReward"] =
+            json!({ "0": "0xde0b6b3a7640000", "100": "0x0", "200": "0x1bc16d674ec80000" });
+        v["blockRewardContractAddress"] = json!("0x000000000000000000000000000000000000beef");
+        v["blockRewardContractTransition"] = json!(300);
+        let cfg = AuraConfig::from_json_value(&v).unwrap();
+        assert_eq!(
+            cfg.fixed_block_reward_at(99),
+            Some(1_000_000_000_000_000_000)
+        );
+        assert_eq!(cfg.fixed_block_reward_at(150), None);
+        assert_eq!(
+            cfg.fixed_block_reward_at(299),
+            Some(2_000_000_000_000_000_000)
+        );
+        // The reward contract takes over from its transition on.
+        assert_eq!(cfg.fixed_block_reward_at(300), None);
+    }
+
+    #[test]
+    fn timestamp_bytecode_rewrites_fire_once_when_crossed() {
+        let v = json!({
+            "stepDuration": 5,
+            "posdaoTransition": 0,
+            "validators": { "multi": { "0": { "list": ["0x0000000000000000000000000000000000000001"] } } },
+            "rewriteBytecodeTimestamp": {
+                "1000": { "0x000000000000000000000000000000000000beef": "0x6001" },
+                "2000": { "0x000000000000000000000000000000000000beef": "0x6002" }
+            }
+        });
+        let cfg = AuraConfig::from_json_value(&v).unwrap();
+        let crossed = |parent, ts| cfg.timestamp_bytecode_rewrites(parent, ts).count();
+
+        assert_eq!(crossed(990, 995), 0);
+        assert_eq!(crossed(995, 1000), 1);
+        assert_eq!(crossed(1000, 1005), 0);
+        // A gap spanning both keys applies both, in order.
+        let codes: Vec<_> = cfg
+            .timestamp_bytecode_rewrites(995, 2005)
+            .flat_map(|r| r.values().cloned())
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                Bytes::from_static(&[0x60, 0x01]),
+                Bytes::from_static(&[0x60, 0x02])
+            ]
+        );
+        assert_eq!(crossed(2005, 2000), 0);
+    }
+
+    #[test]
+    fn parse_block_gas_limit_contract_transitions() {
+        let v = json!({
+            "stepDuration": 5,
+            "posdaoTransition": 0,
+            "validators": { "multi": { "0": { "list": ["0x0000000000000000000000000000000000000001"] } } },
+            "blockGasLimitContractTransitions": {
+                "100": "0x4000000000000000000000000000000000000001",
+                "200": "0x4000000000000000000000000000000000000002"
+            }
+        });
+        let cfg = AuraConfig::from_json_value(&v).unwrap();
+        assert_eq!(cfg.block_gas_limit_contract_at(99), None);
+        assert_eq!(
+            cfg.block_gas_limit_contract_at(150),
+            Some(alloy_primitives::address!(
+                "0x4000000000000000000000000000000000000001"
+            ))
+        );
+        assert_eq!(
+            cfg.block_gas_limit_contract_at(200),
+            Some(alloy_primitives::address!(
+                "0x4000000000000000000000000000000000000002"
+            ))
+        );
+        // Without `gasLimitRampTransition` no block is ramp-checked.
+        assert!(!cfg.gas_limit_ramp_at(50));
+    }
+
+    #[test]
+    fn parse_gas_limit_ramp_transition() {
+        let v = json!({
+            "stepDuration": 5,
+            "posdaoTransition": 0,
+            "validators": { "multi": { "0": { "list": ["0x0000000000000000000000000000000000000001"] } } },
+            "gasLimitRampTransition": 10,
+            "blockGasLimitContractTransitions": {
+                "100": "0x4000000000000000000000000000000000000001"
+            }
+        });
+        let cfg = AuraConfig::from_json_value(&v).unwrap();
+        assert!(!cfg.gas_limit_ramp_at(9));
+        assert!(cfg.gas_limit_ramp_at(10));
+        assert!(cfg.gas_limit_ramp_at(99));
+        // The contract decides from its transition on.
+        assert!(!cfg.gas_limit_ramp_at(100));
+    }
+
+    #[test]
+    fn parse_registrar() {
+        let mut v = json!({
+            "stepDuration": 5,
+            "posdaoTransition": 0,
+            "validators": { "multi": { "0": { "list": ["0x0000000000000000000000000000000000000001"] } } }
+        });
+        assert_eq!(AuraConfig::from_json_value(&v).unwrap().registrar, None);
+        v["registrar"] = json!("0x6B53721D4f2Fb9514B85f5C49b197D857e36Cf03");
+        assert_eq!(
+            AuraConfig::from_json_value(&v).unwrap().registrar,
+            Some(alloy_primitives::address!(
+                "0x6B53721D4f2Fb9514B85f5C49b197D857e36Cf03"
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_transaction_permission_contract() {
+        let v = json!({
+            "stepDuration": 5,
+            "posdaoTransition": 0,
+            "validators": { "multi": { "0": { "list": ["0x0000000000000000000000000000000000000001"] } } },
+            "transactionPermissionContract": "0x4000000000000000000000000000000000000005",
+            "transactionPermissionContractTransition": "0x64"
+        });
+        let cfg = AuraConfig::from_json_value(&v).unwrap();
+        assert_eq!(cfg.transaction_permission_contract_at(99), None);
+        assert_eq!(
+            cfg.transaction_permission_contract_at(100),
+            Some(alloy_primitives::address!(
+                "0x4000000000000000000000000000000000000005"
+            ))
+        );
+    }
+
     #[test]
     fn parse_missing_posdao_transition_errors() {
         // Both AuRa chains we ship include `posdaoTransition`; absence is a
@@ -0,0 +1,283 @@
+//! Detection of AuRa equivocations: a validator sealing two different headers
+//! for the same step.
+//!
+//! `GnosisConsensus` reports every pre-merge header that passed validation
+//! against its parent, together with the signer recovered from its seal. The
+//! detector keeps a bounded index `(step, signer) -> header` of recent
+//! headers; a second header with a different hash for an indexed pair is an
+//! equivocation. Each detection is
+//!
+//! - logged as a `warn` event on target `reth::gnosis::equivocation`,
+//! - counted in the `aura_equivocations` Prometheus counter,
+//! - written as an [`EquivocationEvidence`] JSON record to
+//!   `<datadir>/aura-equivocations/` when an evidence directory is set.
+//!
+//! Detection never rejects a header: both blocks are individually valid, and
+//! fork choice decides which one becomes canonical.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use alloy_primitives::{Address, Bytes, B256};
+use gnosis_primitives::header::GnosisHeader;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives_traits::SealedHeader;
+use serde::{Deserialize, Serialize};
+
+/// Number of `(step, signer)` pairs remembered. At one block per 5s step this
+/// covers several hours of headers, far more than any honest reorg depth.
+pub const EQUIVOCATION_INDEX_CAPACITY: usize = 4096;
+
+/// Name of the evidence directory inside the datadir.
+pub const EQUIVOCATION_EVIDENCE_DIR: &str = "aura-equivocations";
+
+/// Equivocation detector metrics.
+#[derive(Metrics)]
+#[metrics(scope = "aura")]
+struct EquivocationMetrics {
+    /// Number of detected AuRa equivocations (two headers sealed by the same
+    /// validator for the same step).
+    equivocations: Counter,
+}
+
+/// One of the two conflicting headers of an [`EquivocationEvidence`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquivocationHeader {
+    pub number: u64,
+    pub hash: B256,
+    pub parent_hash: B256,
+    pub timestamp: u64,
+    /// Full RLP-encoded header, including the AuRa step and seal, so the
+    /// signature can be re-verified independently.
+    pub rlp: Bytes,
+}
+
+impl EquivocationHeader {
+    fn new(header: &SealedHeader<GnosisHeader>) -> Self {
+        Self {
+            number: header.number,
+            hash: header.hash(),
+            parent_hash: header.parent_hash,
+            timestamp: header.timestamp,
+            rlp: alloy_rlp::encode(header.header()).into(),
+        }
+    }
+}
+
+/// Proof that `signer` sealed two different headers for `step`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquivocationEvidence {
+    pub step: u64,
+    /// Signer recovered from both seals.
+    pub signer: Address,
+    /// The header seen first.
+    pub first: EquivocationHeader,
+    /// The conflicting header that triggered the detection.
+    pub second: EquivocationHeader,
+}
+
+impl EquivocationEvidence {
+    /// File name of this record: step, signer and the conflicting hash, so
+    /// repeated detections for the same pair do not overwrite each other.
+    fn file_name(&self) -> String {
+        format!(
+            "{:012}-{}-{}.json",
+            self.step, self.signer, self.second.hash
+        )
+    }
+}
+
+/// Bounded index of recently validated AuRa headers by `(step, signer)`.
+#[derive(Debug)]
+pub struct EquivocationDetector {
+    capacity: usize,
+    inner: Mutex<IndexInner>,
+    evidence_dir: Option<PathBuf>,
+    metrics: EquivocationMetrics,
+}
+
+#[derive(Debug, Default)]
+struct IndexInner {
+    headers: HashMap<(u64, Address), SealedHeader<GnosisHeader>>,
+    /// Insertion order, oldest first.
+    order: VecDeque<(u64, Address)>,
+}
+
+impl Default for EquivocationDetector {
+    fn default() -> Self {
+        Self::new(EQUIVOCATION_INDEX_CAPACITY)
+    }
+}
+
+impl EquivocationDetector {
+    /// Detector remembering at most `capacity` `(step, signer)` pairs, without
+    /// an evidence directory.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            inner: Mutex::new(IndexInner::default()),
+            evidence_dir: None,
+            metrics: EquivocationMetrics::default(),
+        }
+    }
+
+    /// Also write every detection to `<data_dir>/aura-equivocations`. The
+    /// directory is created lazily on the first detection.
+    pub fn with_evidence_dir(mut self, data_dir: &Path) -> Self {
+        self.evidence_dir = Some(data_dir.join(EQUIVOCATION_EVIDENCE_DIR));
+        self
+    }
+
+    /// Record that `signer` sealed `header` for `step`. Returns the evidence
+    /// if an indexed header with a different hash holds the same pair.
+    ///
+    /// The first header seen for a pair stays indexed, so every later
+    /// conflicting header is reported against it.
+    pub fn observe(
+        &self,
+        header: &SealedHeader<GnosisHeader>,
+        step: u64,
+        signer: Address,
+    ) -> Option<EquivocationEvidence> {
+        let evidence = {
+            let Ok(mut inner) = self.inner.lock() else {
+                return None;
+            };
+            match inner.headers.get(&(step, signer)) {
+                Some(first) if first.hash() == header.hash() => return None,
+                Some(first) => EquivocationEvidence {
+                    step,
+                    signer,
+                    first: EquivocationHeader::new(first),
+                    second: EquivocationHeader::new(header),
+                },
+                None => {
+                    inner.headers.insert((step, signer), header.clone());
+                    inner.order.push_back((step, signer));
+                    while inner.order.len() > self.capacity {
+                        if let Some(evicted) = inner.order.pop_front() {
+                            inner.headers.remove(&evicted);
+                        }
+                    }
+                    return None;
+                }
+            }
+        };
+
+        self.metrics.equivocations.increment(1);
+        tracing::warn!(
+            target: "reth::gnosis::equivocation",
+            step,
+            %signer,
+            first_number = evidence.first.number,
+            first_hash = %evidence.first.hash,
+            second_number = evidence.second.number,
+            second_hash = %evidence.second.hash,
+            "AuRa equivocation: validator sealed two headers for the same step"
+        );
+        if let Some(dir) = &self.evidence_dir {
+            if let Err(err) = write_evidence(dir, &evidence) {
+                tracing::warn!(
+                    target: "reth::gnosis::equivocation",
+                    %err,
+                    dir = %dir.display(),
+                    "Failed to write AuRa equivocation evidence"
+                );
+            }
+        }
+        Some(evidence)
+    }
+
+    /// Number of indexed `(step, signer)` pairs.
+    pub fn len(&self) -> usize {
+        self.inner.lock().map(|i| i.headers.len()).unwrap_or(0)
+    }
+
+    /// True if no pair is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Write `evidence` atomically (temp file + rename) into `dir`.
+fn write_evidence(dir: &Path, evidence: &EquivocationEvidence) -> eyre::Result<()> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(evidence.file_name());
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(evidence)?)?;
+    fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+
+    fn addr(b: u8) -> Address {
+        Address::from([b; 20])
+    }
+
+    fn header(number: u64, step: u64, timestamp: u64) -> SealedHeader<GnosisHeader> {
+        SealedHeader::seal_slow(GnosisHeader {
+            number,
+            timestamp,
+            aura_step: Some(U256::from(step)),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn flags_two_headers_for_same_step_and_signer() {
+        let dir =
+            std::env::temp_dir().join(format!("reth-gnosis-equivocation-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let detector = EquivocationDetector::default().with_evidence_dir(&dir);
+        let a = header(10, 100, 500);
+        let b = header(10, 100, 501);
+
+        assert!(detector.observe(&a, 100, addr(1)).is_none());
+        // Re-validating the same header is not an equivocation.
+        assert!(detector.observe(&a, 100, addr(1)).is_none());
+        // Same step, other signer: a different (invalid elsewhere) proposer,
+        // not a double seal.
+        assert!(detector.observe(&b, 100, addr(2)).is_none());
+
+        let evidence = detector.observe(&b, 100, addr(1)).unwrap();
+        assert_eq!(evidence.step, 100);
+        assert_eq!(evidence.signer, addr(1));
+        assert_eq!(evidence.first.hash, a.hash());
+        assert_eq!(evidence.second.hash, b.hash());
+        assert_eq!(
+            evidence.second.rlp,
+            Bytes::from(alloy_rlp::encode(b.header()))
+        );
+
+        let written = dir
+            .join(EQUIVOCATION_EVIDENCE_DIR)
+            .join(evidence.file_name());
+        let on_disk: EquivocationEvidence =
+            serde_json::from_slice(&fs::read(written).unwrap()).unwrap();
+        assert_eq!(on_disk, evidence);
+    }
+
+    #[test]
+    fn forgets_oldest_pairs_beyond_capacity() {
+        let detector = EquivocationDetector::new(2);
+        detector.observe(&header(1, 1, 5), 1, addr(1));
+        detector.observe(&header(2, 2, 10), 2, addr(1));
+        detector.observe(&header(3, 3, 15), 3, addr(1));
+        assert_eq!(detector.len(), 2);
+
+        // Step 1 was evicted, so a conflicting header for it goes unnoticed
+        // and takes its place in the index.
+        assert!(detector.observe(&header(1, 1, 6), 1, addr(1)).is_none());
+        assert!(detector.observe(&header(3, 3, 16), 3, addr(1)).is_some());
+    }
+}
@@ -1,6 +1,8 @@
 use alloy_primitives::Address;
 use std::collections::{BTreeMap, VecDeque};
 
+use crate::aura::persistence::FinalityState;
+
 /// Cadence (in blocks) for trace-logging unfinalized pending transitions.
 /// Logging on every block would be too noisy during sync; once every N blocks
 /// gives enough visibility to debug stuck transitions without flooding logs.
@@ -11,18 +13,23 @@ const PENDING_TRANSITIONS_LOG_INTERVAL: u64 = 5;
 /// Tracks unique block signers to determine when a block becomes finalized.
 /// A block is finalized when more than half of the current validator set has
 /// signed blocks after it (geth rule: `sign_count * 2 > validator_count`).
+/// From the chain's `twoThirdsMajorityTransition` onward the threshold is a
+/// strict two-thirds majority (`sign_count * 3 > validator_count * 2`), as in
+/// OpenEthereum / Nethermind.
 ///
 /// When a finalized block has a pending InitiateChange event, the
 /// finalizeChange() system call should be triggered at the next block.
 ///
 /// **Append-only invariant**: blocks must be pushed in monotonically increasing
-/// order. There is no reorg-rollback path — orphaned blocks linger in `headers`
-/// / `sign_count` and would shift the finality threshold. This is safe for the
-/// AuRa pre-merge replay use case (frozen historical chain, no reorgs) but
-/// would be unsafe under any reorg-capable execution path.
+/// order. There is no rollback path on the tracker itself; instead the
+/// executor never mutates a shared tracker. Each executed block starts from a
+/// clone of its parent's state in [`crate::aura::finality_cache::FinalityCache`]
+/// and records its own result under its hash, so unwinds and reorgs never see
+/// orphaned signers.
 ///
-/// **No persistence**: state lives entirely in memory and is rebuilt from
-/// scratch on every node restart by re-running execution.
+/// **Persistence**: the tracker of every committed block is snapshotted by
+/// [`crate::aura::persistence::persist_committed_finality`]; see
+/// [`Self::to_state`] / [`Self::from_state`].
 #[derive(Debug, Clone)]
 pub struct RollingFinality {
     /// Current validator set (addresses authorized to sign blocks).
@@ -41,6 +48,14 @@ pub struct RollingFinality {
     /// The block number at which finalization was most recently determined,
     /// meaning finalizeChange should be called at finalized_at + 1.
     finalize_change_at: Option<(u64, Address)>,
+    /// First block pushed with the 2/3 threshold (`u64::MAX` = never).
+    /// Chain-spec derived, so not part of the persisted [`FinalityState`];
+    /// set via [`Self::set_two_thirds_majority_transition`].
+    two_thirds_majority_transition: u64,
+    /// Sealed validator set at the start of the last executed block, i.e. in
+    /// its parent state: the set that block's proposer was chosen from.
+    /// Recorded by the executor for contract-based sets; not persisted.
+    proposer_set: Option<Vec<Address>>,
 }
 
 impl RollingFinality {
@@ -53,6 +68,8 @@ impl RollingFinality {
             sign_count: BTreeMap::new(),
             pending_transitions: BTreeMap::new(),
             finalize_change_at: None,
+            two_thirds_majority_transition: u64::MAX,
+            proposer_set: None,
         }
     }
 
@@ -69,13 +86,56 @@ impl RollingFinality {
             sign_count: BTreeMap::new(),
             pending_transitions,
             finalize_change_at,
+            two_thirds_majority_transition: u64::MAX,
+            proposer_set: None,
+        }
+    }
+
+    /// Restore a tracker from a persisted [`FinalityState`]. `sign_count` is
+    /// rebuilt from the signer window.
+    pub fn from_state(state: FinalityState) -> Self {
+        let mut sign_count = BTreeMap::new();
+        for (_, signer) in &state.headers {
+            *sign_count.entry(*signer).or_insert(0) += 1;
+        }
+        Self {
+            validators: state.validators,
+            validators_sealed: state.validators_sealed,
+            headers: state.headers.into_iter().collect(),
+            sign_count,
+            pending_transitions: state.pending_transitions,
+            finalize_change_at: state.finalize_change_at,
+            two_thirds_majority_transition: u64::MAX,
+            proposer_set: None,
         }
     }
 
-    /// Returns true if any block in the queue is finalized
-    /// (more than half of validators have signed).
-    fn is_finalized(&self) -> bool {
-        self.sign_count.len() * 2 > self.validators.len()
+    /// Use the 2/3 finality threshold for blocks pushed at or after
+    /// `block_number` (the chain's `twoThirdsMajorityTransition`).
+    pub fn set_two_thirds_majority_transition(&mut self, block_number: u64) {
+        self.two_thirds_majority_transition = block_number;
+    }
+
+    /// Persistable copy of the full tracker state.
+    pub fn to_state(&self) -> FinalityState {
+        FinalityState {
+            validators: self.validators.clone(),
+            validators_sealed: self.validators_sealed,
+            headers: self.headers.iter().copied().collect(),
+            pending_transitions: self.pending_transitions.clone(),
+            finalize_change_at: self.finalize_change_at,
+        }
+    }
+
+    /// Returns true if any block in the queue is finalized: more than half of
+    /// validators have signed, or more than two thirds once `block_number`
+    /// (the block being pushed) reaches `twoThirdsMajorityTransition`.
+    fn is_finalized(&self, block_number: u64) -> bool {
+        if block_number >= self.two_thirds_majority_transition {
+            self.sign_count.len() * 3 > self.validators.len() * 2
+        } else {
+            self.sign_count.len() * 2 > self.validators.len()
+        }
     }
 
     /// Push a new block with its signer. Returns any blocks that became finalized.
@@ -118,7 +178,7 @@ impl RollingFinality {
 
         // Pop finalized blocks
         let mut finalized = Vec::new();
-        while self.is_finalized() {
+        while self.is_finalized(block_number) {
             if let Some((num, addr)) = self.headers.pop_front() {
                 // Decrease signer count
                 if let Some(count) = self.sign_count.get_mut(&addr) {
@@ -221,6 +281,24 @@ impl RollingFinality {
         self.validators.len()
     }
 
+    /// The validator set last fetched via `getValidators()`, or `None` while
+    /// the set is still being auto-discovered from block signers.
+    pub fn sealed_validators(&self) -> Option<&[Address]> {
+        self.validators_sealed.then_some(self.validators.as_slice())
+    }
+
+    /// Record the current sealed set as the proposer set of the block being
+    /// executed, before its `finalizeChange` can replace it.
+    pub fn record_proposer_set(&mut self) {
+        self.proposer_set = self.sealed_validators().map(<[Address]>::to_vec);
+    }
+
+    /// Validator set the last executed block was proposed under, see
+    /// [`Self::record_proposer_set`].
+    pub fn proposer_set(&self) -> Option<&[Address]> {
+        self.proposer_set.as_deref()
+    }
+
     pub fn pending_transitions(&self) -> &BTreeMap<u64, Address> {
         &self.pending_transitions
     }
@@ -263,6 +341,27 @@ mod tests {
         assert_eq!(finalized[0], (1, addr(1)));
     }
 
+    #[test]
+    fn two_thirds_majority_from_transition_block() {
+        // 6 validators: 4 unique signers are a simple majority but not > 2/3.
+        let validators: Vec<_> = (1..=6).map(addr).collect();
+
+        let mut rf = sealed_tracker(validators.clone());
+        rf.set_two_thirds_majority_transition(3);
+        for n in 1..=4 {
+            assert!(rf.push(n, addr(n as u8)).is_empty(), "block {n}");
+        }
+        assert_eq!(rf.push(5, addr(5)), vec![(1, addr(1))]);
+
+        // Before the transition the geth 1/2 rule still applies.
+        let mut rf = sealed_tracker(validators);
+        rf.set_two_thirds_majority_transition(100);
+        for n in 1..=3 {
+            assert!(rf.push(n, addr(n as u8)).is_empty(), "block {n}");
+        }
+        assert_eq!(rf.push(4, addr(4)), vec![(1, addr(1))]);
+    }
+
     #[test]
     fn push_does_not_double_count_same_signer() {
         let mut rf = sealed_tracker(vec![addr(1), addr(2), addr(3), addr(4)]);
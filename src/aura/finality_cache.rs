@@ -0,0 +1,181 @@
+//! Per-block [`RollingFinality`] states keyed by block hash.
+//!
+//! Every executed pre-merge block records the tracker state *after* it under
+//! its own hash. Executing block N clones the state recorded under N's
+//! `parent_hash`, so sibling blocks, unwinds and re-executions all start from
+//! the state of their actual parent instead of whatever block ran last.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use alloy_primitives::B256;
+
+use crate::aura::finality::RollingFinality;
+
+/// Number of post-block states kept in memory. Deeper reorgs and unwinds fall
+/// back to persisted snapshots / receipt replay (see
+/// [`crate::aura::recovery::recover_finality_at`]).
+pub const FINALITY_CACHE_CAPACITY: usize = 256;
+
+/// Bounded LRU map `block_hash -> RollingFinality` (state after that block).
+///
+/// States are immutable once inserted: [`Self::get`] hands out clones, and the
+/// executor mutates its own copy before inserting the result under the new
+/// block's hash.
+#[derive(Debug)]
+pub struct FinalityCache {
+    capacity: usize,
+    inner: Mutex<CacheInner>,
+}
+
+#[derive(Debug, Default)]
+struct CacheInner {
+    /// State and last-use tick per block hash.
+    states: HashMap<B256, (RollingFinality, u64)>,
+    /// Block hashes by last-use tick, least recently used first.
+    order: BTreeMap<u64, B256>,
+    /// Last tick handed out.
+    tick: u64,
+}
+
+impl CacheInner {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Mark cached `hash` as used now.
+    fn touch(&mut self, hash: B256) {
+        let tick = self.next_tick();
+        if let Some((_, used)) = self.states.get_mut(&hash) {
+            self.order.remove(used);
+            *used = tick;
+            self.order.insert(tick, hash);
+        }
+    }
+}
+
+impl Default for FinalityCache {
+    fn default() -> Self {
+        Self::new(FINALITY_CACHE_CAPACITY)
+    }
+}
+
+impl FinalityCache {
+    /// Empty cache holding at most `capacity` states.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            inner: Mutex::new(CacheInner::default()),
+        }
+    }
+
+    /// Clone of the state after block `hash`, if cached.
+    pub fn get(&self, hash: &B256) -> Option<RollingFinality> {
+        let mut inner = self.inner.lock().ok()?;
+        let state = inner.states.get(hash)?.0.clone();
+        inner.touch(*hash);
+        Some(state)
+    }
+
+    /// Record `state` as the state after block `hash`, evicting the least
+    /// recently used entry when full. Re-inserting a hash (re-execution of the
+    /// same block) replaces the previous state.
+    pub fn insert(&self, hash: B256, state: RollingFinality) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        let tick = inner.next_tick();
+        if let Some((_, used)) = inner.states.insert(hash, (state, tick)) {
+            inner.order.remove(&used);
+        }
+        inner.order.insert(tick, hash);
+        while inner.order.len() > self.capacity {
+            if let Some((_, evicted)) = inner.order.pop_first() {
+                inner.states.remove(&evicted);
+            }
+        }
+    }
+
+    /// Number of cached states.
+    pub fn len(&self) -> usize {
+        self.inner.lock().map(|i| i.states.len()).unwrap_or(0)
+    }
+
+    /// True if no state is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+
+    fn addr(b: u8) -> Address {
+        Address::from([b; 20])
+    }
+
+    fn hash(b: u8) -> B256 {
+        B256::from([b; 32])
+    }
+
+    fn sealed(validators: Vec<Address>) -> RollingFinality {
+        let mut rf = RollingFinality::new(validators.clone());
+        rf.set_validators(validators);
+        rf
+    }
+
+    #[test]
+    fn sibling_blocks_start_from_shared_parent() {
+        let cache = FinalityCache::default();
+        let mut parent = sealed(vec![addr(1), addr(2), addr(3), addr(4)]);
+        parent.push(10, addr(1));
+        parent.add_pending_transition(10, addr(0xaa));
+        cache.insert(hash(10), parent);
+
+        // Block 11a pushes a second signer and is recorded.
+        let mut a = cache.get(&hash(10)).unwrap();
+        a.push(11, addr(2));
+        cache.insert(hash(0xa1), a);
+
+        // Sibling 11b (after a reorg) must not see 11a's signer: 11b + 12b
+        // signed by the same validator stay below majority, whereas 11a's
+        // branch finalizes block 10 on the next distinct signer.
+        let mut b = cache.get(&hash(10)).unwrap();
+        assert!(b.push(11, addr(3)).is_empty());
+        assert!(b.push(12, addr(3)).is_empty());
+        assert_eq!(b.pending_transitions().len(), 1);
+
+        let mut a = cache.get(&hash(0xa1)).unwrap();
+        assert_eq!(a.push(12, addr(3)), vec![(10, addr(1))]);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = FinalityCache::new(2);
+        cache.insert(hash(1), RollingFinality::new(Vec::new()));
+        cache.insert(hash(2), RollingFinality::new(Vec::new()));
+        // Touch 1 so 2 becomes the eviction candidate.
+        assert!(cache.get(&hash(1)).is_some());
+        cache.insert(hash(3), RollingFinality::new(Vec::new()));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&hash(1)).is_some());
+        assert!(cache.get(&hash(2)).is_none());
+        assert!(cache.get(&hash(3)).is_some());
+    }
+
+    #[test]
+    fn reinsert_refreshes_without_growing() {
+        let cache = FinalityCache::new(2);
+        cache.insert(hash(1), RollingFinality::new(Vec::new()));
+        cache.insert(hash(2), RollingFinality::new(Vec::new()));
+        // Re-executing block 1 replaces its state and makes it most recent.
+        cache.insert(hash(1), RollingFinality::new(vec![Address::ZERO]));
+        cache.insert(hash(3), RollingFinality::new(Vec::new()));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&hash(1)).unwrap().validator_count(), 1);
+        assert!(cache.get(&hash(2)).is_none());
+    }
+}
@@ -1,12 +1,20 @@
+pub mod author;
 pub mod config;
+pub mod equivocation;
 pub mod finality;
+pub mod finality_cache;
+pub mod persistence;
 pub mod recovery;
+pub mod rpc;
 pub mod seal;
+pub mod seal_cache;
+pub mod tx_permission;
 pub mod validators;
 
 use std::sync::Arc;
 
 use alloy_consensus::{constants::EMPTY_OMMER_ROOT_HASH, BlockHeader};
+use alloy_primitives::Address;
 use gnosis_primitives::header::GnosisHeader;
 use reth_chainspec::EthereumHardforks;
 use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator, ReceiptRootBloom};
@@ -17,13 +25,17 @@ use reth_consensus_common::validation::{
 };
 use reth_ethereum_consensus::{validate_block_post_execution, EthBeaconConsensus};
 use reth_execution_types::BlockExecutionResult;
+use reth_primitives_traits::constants::{GAS_LIMIT_BOUND_DIVISOR, MINIMUM_GAS_LIMIT};
 use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
 
+use crate::aura::equivocation::EquivocationDetector;
+use crate::aura::finality_cache::FinalityCache;
 use crate::primitives::GnosisNodePrimitives;
 use crate::spec::gnosis_spec::GnosisChainSpec;
 
 use self::config::AuraConfig;
-use self::seal::{calculate_aura_difficulty, recover_seal_author};
+use self::seal::calculate_aura_difficulty;
+use self::seal_cache::SealAuthorCache;
 use self::validators::ValidatorSet;
 
 /// Gnosis consensus implementation that handles both AuRa (pre-merge) and
@@ -36,6 +48,16 @@ pub struct GnosisConsensus {
     aura_config: Option<AuraConfig>,
     /// Chain spec reference.
     chain_spec: Arc<GnosisChainSpec>,
+    /// Post-block rolling-finality states shared with the executor. Holds the
+    /// `getValidators()` set each executed POSDAO block was run with, used
+    /// for the contract-based proposer check after execution.
+    finality_cache: Option<Arc<FinalityCache>>,
+    /// Index of recently validated AuRa headers by `(step, signer)`, flagging
+    /// validators that seal two headers for the same step.
+    equivocation: Arc<EquivocationDetector>,
+    /// Seal authors by block hash, recovered once by header validation and
+    /// reused by the post-execution proposer check.
+    seal_authors: Arc<SealAuthorCache>,
 }
 
 impl GnosisConsensus {
@@ -46,9 +68,87 @@ impl GnosisConsensus {
             inner: EthBeaconConsensus::new(chain_spec.clone()),
             aura_config,
             chain_spec,
+            finality_cache: None,
+            equivocation: Arc::new(EquivocationDetector::default()),
+            seal_authors: Arc::new(SealAuthorCache::default()),
         }
     }
 
+    /// Check contract-based proposers after execution against the validator
+    /// sets the executor records in `finality_cache`. Must be the cache of
+    /// the `GnosisEvmConfig` executing the blocks.
+    pub fn with_finality_cache(mut self, finality_cache: Arc<FinalityCache>) -> Self {
+        self.finality_cache = Some(finality_cache);
+        self
+    }
+
+    /// Write equivocation evidence to `<data_dir>/aura-equivocations`.
+    pub fn with_equivocation_evidence_dir(mut self, data_dir: &std::path::Path) -> Self {
+        self.equivocation = Arc::new(EquivocationDetector::default().with_evidence_dir(data_dir));
+        self
+    }
+
+    /// Proposer check for blocks whose validator set lives in a contract,
+    /// which header validation cannot resolve without EVM state.
+    ///
+    /// Like the list-based check, this uses the validator set of the parent
+    /// state. The executor records it in `apply_pre_execution_changes`,
+    /// before the block's own `finalizeChange` can replace it (see
+    /// [`crate::aura::finality::RollingFinality::proposer_set`]), and the
+    /// post-block state recorded under the block hash carries it.
+    fn validate_contract_proposer(
+        &self,
+        header: &SealedHeader<GnosisHeader>,
+    ) -> Result<(), ConsensusError> {
+        let (Some(aura_config), Some(finality_cache)) =
+            (self.aura_config.as_ref(), self.finality_cache.as_ref())
+        else {
+            return Ok(());
+        };
+        let block_number = header.number;
+        if block_number < aura_config.posdao_transition
+            || aura_config
+                .validators
+                .try_get_list_validators(block_number.saturating_sub(1))
+                .is_some()
+        {
+            // Pre-POSDAO, or a list-based set already checked on the header.
+            return Ok(());
+        }
+
+        let Some(validators) = finality_cache
+            .get(&header.hash())
+            .and_then(|rf| rf.proposer_set().map(<[_]>::to_vec))
+        else {
+            // Every executed POSDAO block records its proposer set (fetched
+            // via `getValidators()` when the parent state had none), so a miss
+            // means the block was not executed against this cache. Fail closed
+            // rather than accept an unverified seal.
+            return Err(ConsensusError::msg(format!(
+                "AuRa validator set unknown after executing block {block_number}"
+            )));
+        };
+
+        let step = header_step(header.header())?;
+        let signer = self
+            .seal_authors
+            .author(header)
+            .map_err(|e| ConsensusError::msg(format!("AuRa seal verification failed: {}", e)))?;
+        check_proposer(signer, step, &validators, block_number)
+    }
+
+    /// AuRa config for validating a pre-merge block. A chain with a pre-merge
+    /// phase but no `aura` section in genesis is a misconfiguration; refuse
+    /// rather than silently skipping seal + proposer verification.
+    fn require_aura_config(&self) -> Result<&AuraConfig, ConsensusError> {
+        self.aura_config.as_ref().ok_or_else(|| {
+            ConsensusError::msg(
+                "AuRa config missing for chain with pre-merge phase: \
+                 genesis must contain an `aura` section",
+            )
+        })
+    }
+
     /// Returns `true` if `header` belongs to the pre-merge AuRa phase, `false`
     /// if it belongs to the post-merge Beacon phase. The chain spec is the
     /// authoritative source of phase.
@@ -74,7 +174,11 @@ impl GnosisConsensus {
 impl HeaderValidator<GnosisHeader> for GnosisConsensus {
     fn validate_header(&self, header: &SealedHeader<GnosisHeader>) -> Result<(), ConsensusError> {
         if self.is_aura_block(header.header())? {
-            validate_aura_header(header.header(), &self.chain_spec)
+            validate_aura_header(
+                header.header(),
+                &self.chain_spec,
+                self.require_aura_config()?,
+            )
         } else {
             self.inner.validate_header(header)
         }
@@ -86,17 +190,16 @@ impl HeaderValidator<GnosisHeader> for GnosisConsensus {
         parent: &SealedHeader<GnosisHeader>,
     ) -> Result<(), ConsensusError> {
         if self.is_aura_block(header.header())? {
-            // We're validating an AuRa block, so aura_config MUST be present in
-            // the chain spec. A chain with pre-merge phase but no `aura` section
-            // in genesis is a misconfiguration; refuse rather than silently
-            // skipping seal + proposer verification.
-            let aura_config = self.aura_config.as_ref().ok_or_else(|| {
-                ConsensusError::msg(
-                    "AuRa config missing for chain with pre-merge phase: \
-                     genesis must contain an `aura` section",
-                )
-            })?;
-            validate_aura_header_against_parent(header, parent, &self.chain_spec, aura_config)
+            let aura_config = self.require_aura_config()?;
+            let (step, signer) = validate_aura_header_against_parent(
+                header,
+                parent,
+                &self.chain_spec,
+                aura_config,
+                &self.seal_authors,
+            )?;
+            self.equivocation.observe(header, step, signer);
+            Ok(())
         } else {
             self.inner.validate_header_against_parent(header, parent)
         }
@@ -126,7 +229,12 @@ where
                 return Err(ConsensusError::TheMergeOmmerRootIsNotEmpty);
             }
 
-            Ok(())
+            validate_step_not_in_future(
+                header_step(block.header())?,
+                block.header().number(),
+                self.require_aura_config()?,
+                unix_now(),
+            )
         } else {
             validate_block_pre_execution(block, &*self.chain_spec)
         }
@@ -140,7 +248,11 @@ impl FullConsensus<GnosisNodePrimitives> for GnosisConsensus {
         result: &BlockExecutionResult<<GnosisNodePrimitives as NodePrimitives>::Receipt>,
         receipt_root_bloom: Option<ReceiptRootBloom>,
     ) -> Result<(), ConsensusError> {
-        validate_block_post_execution(block, &*self.chain_spec, result, receipt_root_bloom)
+        validate_block_post_execution(block, &*self.chain_spec, result, receipt_root_bloom)?;
+        if self.is_aura_block(block.header())? {
+            self.validate_contract_proposer(block.sealed_header())?;
+        }
+        Ok(())
     }
 }
 
@@ -153,6 +265,7 @@ impl FullConsensus<GnosisNodePrimitives> for GnosisConsensus {
 fn validate_aura_header(
     header: &GnosisHeader,
     chain_spec: &GnosisChainSpec,
+    aura_config: &AuraConfig,
 ) -> Result<(), ConsensusError> {
     // Ommers must be empty in AuRa
     if header.ommers_hash != EMPTY_OMMER_ROOT_HASH {
@@ -182,16 +295,90 @@ fn validate_aura_header(
         return Err(ConsensusError::BlobGasUsedUnexpected);
     }
 
+    validate_step_timestamp(
+        header_step(header)?,
+        header.timestamp,
+        header.number,
+        aura_config,
+    )
+}
+
+/// Steps a header may run ahead of the local clock before it is rejected as
+/// a block from the future. Tolerates clock skew between validators.
+const MAX_FUTURE_STEPS: u64 = 4;
+
+/// From `validateStepTransition` onward, check `step` against the header
+/// timestamp: block producers take the timestamp before reading the step, so
+/// a block sealed right at a step boundary may carry a timestamp from the
+/// previous step, but never from a later one.
+fn validate_step_timestamp(
+    step: u64,
+    timestamp: u64,
+    block_number: u64,
+    aura_config: &AuraConfig,
+) -> Result<(), ConsensusError> {
+    if aura_config
+        .validate_step_transition
+        .is_some_and(|transition| block_number >= transition)
+    {
+        let timestamp_step = aura_config.step_at(timestamp);
+        if timestamp_step != step && timestamp_step.saturating_add(1) != step {
+            return Err(ConsensusError::msg(format!(
+                "AuRa step does not match timestamp: step={step}, timestamp={timestamp}, \
+                 timestamp step={timestamp_step} (block={block_number})"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Reject a `step` more than [`MAX_FUTURE_STEPS`] ahead of the local step at
+/// `now` (unix seconds). Depends on the local clock, so it only runs when a
+/// block is imported (`validate_block_pre_execution`), never in header
+/// validation.
+fn validate_step_not_in_future(
+    step: u64,
+    block_number: u64,
+    aura_config: &AuraConfig,
+    now: u64,
+) -> Result<(), ConsensusError> {
+    let local_step = aura_config.step_at(now);
+    if step > local_step.saturating_add(MAX_FUTURE_STEPS) {
+        return Err(ConsensusError::msg(format!(
+            "AuRa block from the future: step={step}, local step={local_step} (block={block_number})"
+        )));
+    }
     Ok(())
 }
 
-/// Validate a pre-merge AuRa header against its parent.
+/// The header's AuRa step. A peer-supplied U256 step that exceeds u64 must
+/// NOT panic the node; reject the header instead.
+fn header_step(header: &GnosisHeader) -> Result<u64, ConsensusError> {
+    let step = header
+        .aura_step
+        .ok_or_else(|| ConsensusError::msg("missing AuRa step"))?;
+    step.try_into()
+        .map_err(|_| ConsensusError::msg(format!("AuRa step exceeds u64: {step}")))
+}
+
+/// Current unix time in seconds (0 if the clock is before the epoch).
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Validate a pre-merge AuRa header against its parent. Returns the header's
+/// step and the signer recovered from its seal, cached in `seal_authors`.
 fn validate_aura_header_against_parent(
     header: &SealedHeader<GnosisHeader>,
     parent: &SealedHeader<GnosisHeader>,
     chain_spec: &GnosisChainSpec,
     aura_config: &AuraConfig,
-) -> Result<(), ConsensusError> {
+    seal_authors: &SealAuthorCache,
+) -> Result<(u64, Address), ConsensusError> {
     // Standard parent validations
     validate_against_parent_hash_number(header.header(), parent)?;
     validate_against_parent_timestamp(header.header(), parent.header())?;
@@ -199,17 +386,23 @@ fn validate_aura_header_against_parent(
     // Base fee validation (if London is active)
     validate_against_parent_eip1559_base_fee(header.header(), parent.header(), chain_spec)?;
 
-    // Skip gas limit ramp check — AuRa networks may use gas limit contracts
+    // Gas limit: inside a `blockGasLimitContractTransitions` range the
+    // contract's `blockGasLimit()` dictates the limit, which needs EVM state
+    // and is checked during execution. Elsewhere the ramp rule applies only
+    // from `gasLimitRampTransition` on.
+    if aura_config.gas_limit_ramp_at(header.header().number) {
+        validate_gas_limit_ramp(
+            chain_spec,
+            header.header().number,
+            header.header().timestamp,
+            header.header().gas_limit,
+            parent.header().gas_limit,
+        )?;
+    }
 
-    // AuRa step must be present and fit in u64. A peer-supplied U256 step that
-    // exceeds u64 must NOT panic the node; reject the header instead.
-    let current_step = header
-        .header()
-        .aura_step
-        .ok_or_else(|| ConsensusError::msg("missing AuRa step"))?;
-    let current_step: u64 = current_step
-        .try_into()
-        .map_err(|_| ConsensusError::msg(format!("AuRa step exceeds u64: {current_step}")))?;
+    // AuRa step must be present and fit in u64.
+    let current_step = header_step(header.header())?;
+    let block_number = header.header().number;
 
     // The caller (`GnosisConsensus::validate_header_against_parent`) only
     // dispatches here when `is_aura_block(child)` returns `true`, i.e. when
@@ -227,28 +420,39 @@ fn validate_aura_header_against_parent(
         .try_into()
         .map_err(|_| ConsensusError::msg(format!("AuRa parent step exceeds u64: {parent_step}")))?;
 
-    if current_step <= parent_step {
+    // Two blocks for the same step are always rejected (double vote); a step
+    // going backwards only from `validateStepTransition` onward (always when
+    // the chain does not set it), so historical blocks of chains that gated
+    // it are still accepted.
+    if current_step == parent_step
+        || (current_step < parent_step
+            && aura_config
+                .validate_step_transition
+                .is_none_or(|transition| block_number >= transition))
+    {
         return Err(ConsensusError::msg(format!(
             "AuRa step must be monotonically increasing: current={current_step}, parent={parent_step}",
         )));
     }
 
-    // Verify AuRa difficulty.
-    let expected_difficulty = calculate_aura_difficulty(parent_step, current_step);
-    if header.header().difficulty != expected_difficulty {
-        return Err(ConsensusError::msg(format!(
-            "AuRa difficulty mismatch: expected={}, got={}",
-            expected_difficulty,
-            header.header().difficulty
-        )));
+    // Verify AuRa difficulty from `validateScoreTransition` onward.
+    if block_number >= aura_config.validate_score_transition {
+        let expected_difficulty = calculate_aura_difficulty(parent_step, current_step);
+        if header.header().difficulty != expected_difficulty {
+            return Err(ConsensusError::msg(format!(
+                "AuRa difficulty mismatch: expected={}, got={}",
+                expected_difficulty,
+                header.header().difficulty
+            )));
+        }
     }
 
     // Seal signature + proposer check. Always runs in pre-merge AuRa phase —
     // the caller guarantees a valid `aura_config` is present.
-    let block_number = header.header().number;
 
-    // Recover signer from seal
-    let signer = recover_seal_author(header.header())
+    // Recover signer from seal.
+    let signer = seal_authors
+        .author(header)
         .map_err(|e| ConsensusError::msg(format!("AuRa seal verification failed: {}", e)))?;
 
     // Get expected validators. The proposer for block N is determined by
@@ -260,29 +464,207 @@ fn validate_aura_header_against_parent(
         .try_get_list_validators(proposer_lookup_block)
     {
         // current_step is already validated to fit in u64 above.
-        let step = current_step;
-        let expected_proposer =
-            ValidatorSet::expected_proposer(step, validators).ok_or_else(|| {
-                ConsensusError::msg("AuRa validator list is empty for proposer lookup")
-            })?;
-
-        if signer != expected_proposer {
-            return Err(ConsensusError::msg(format!(
-                "AuRa proposer mismatch: expected={}, got={} (step={}, block={})",
-                expected_proposer, signer, step, block_number
-            )));
-        }
+        check_proposer(signer, current_step, validators, block_number)?;
     } else {
-        // Contract-based validator set: proposer check is deferred to execution
-        // (resolving the active validator list requires EVM state access, which
-        // isn't available at consensus-validation time). Log so a chainspec
-        // misconfig that flips a List into a Contract here is at least visible.
+        // Contract-based validator set: resolving the active validator list
+        // requires EVM state, so the proposer is checked after execution in
+        // `GnosisConsensus::validate_contract_proposer`.
         tracing::debug!(
             target: "reth::gnosis",
             block = block_number,
-            "AuRa proposer check deferred to execution: contract-based validator set"
+            "AuRa proposer check deferred to post-execution: contract-based validator set"
         );
     }
 
+    Ok((current_step, signer))
+}
+
+/// Standard gas limit ramp: the limit may move by less than 1/1024 of the
+/// parent's per block and never drops below the 5000 minimum. At the London
+/// block the parent limit is first scaled by the EIP-1559 elasticity
+/// multiplier, as in reth / Nethermind.
+fn validate_gas_limit_ramp(
+    chain_spec: &GnosisChainSpec,
+    block_number: u64,
+    timestamp: u64,
+    gas_limit: u64,
+    parent_gas_limit: u64,
+) -> Result<(), ConsensusError> {
+    use reth_chainspec::EthChainSpec;
+
+    let parent_gas_limit = if chain_spec.is_london_active_at_block(block_number)
+        && !chain_spec.is_london_active_at_block(block_number.saturating_sub(1))
+    {
+        parent_gas_limit.saturating_mul(
+            chain_spec
+                .base_fee_params_at_timestamp(timestamp)
+                .elasticity_multiplier as u64,
+        )
+    } else {
+        parent_gas_limit
+    };
+
+    let max_delta = parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR;
+    if gas_limit > parent_gas_limit && gas_limit - parent_gas_limit >= max_delta {
+        return Err(ConsensusError::GasLimitInvalidIncrease {
+            parent_gas_limit,
+            child_gas_limit: gas_limit,
+        });
+    }
+    if gas_limit < parent_gas_limit && parent_gas_limit - gas_limit >= max_delta {
+        return Err(ConsensusError::GasLimitInvalidDecrease {
+            parent_gas_limit,
+            child_gas_limit: gas_limit,
+        });
+    }
+    if gas_limit < MINIMUM_GAS_LIMIT {
+        return Err(ConsensusError::GasLimitInvalidMinimum {
+            child_gas_limit: gas_limit,
+        });
+    }
     Ok(())
 }
+
+/// Require `signer` to be the round-robin proposer for `step` among
+/// `validators`.
+fn check_proposer(
+    signer: Address,
+    step: u64,
+    validators: &[Address],
+    block_number: u64,
+) -> Result<(), ConsensusError> {
+    let expected_proposer = ValidatorSet::expected_proposer(step, validators)
+        .ok_or_else(|| ConsensusError::msg("AuRa validator list is empty for proposer lookup"))?;
+    if signer != expected_proposer {
+        return Err(ConsensusError::msg(format!(
+            "AuRa proposer mismatch: expected={}, got={} (step={}, block={})",
+            expected_proposer, signer, step, block_number
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aura::{finality::RollingFinality, seal::compute_seal_hash};
+    use crate::spec::chains::CHIADO_GENESIS;
+    use alloy_primitives::{FixedBytes, B256, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    fn addr(b: u8) -> Address {
+        Address::from([b; 20])
+    }
+
+    #[test]
+    fn check_proposer_enforces_round_robin() {
+        let validators = [addr(1), addr(2), addr(3)];
+        assert!(check_proposer(addr(2), 7, &validators, 100).is_ok());
+        let err = check_proposer(addr(1), 7, &validators, 100).unwrap_err();
+        assert!(err.to_string().contains("proposer mismatch"), "{err}");
+        assert!(check_proposer(addr(1), 7, &[], 100).is_err());
+    }
+
+    fn aura_config(validate_step_transition: Option<u64>) -> AuraConfig {
+        let mut value = serde_json::json!({
+            "stepDuration": 5,
+            "posdaoTransition": 0,
+            "validators": { "multi": { "0": { "list": ["0x0000000000000000000000000000000000000001"] } } }
+        });
+        if let Some(transition) = validate_step_transition {
+            value["validateStepTransition"] = transition.into();
+        }
+        AuraConfig::from_json_value(&value).unwrap()
+    }
+
+    #[test]
+    fn step_must_match_timestamp_from_transition() {
+        let cfg = aura_config(Some(100));
+        // Step 200 covers timestamps 1000..=1004; a block sealed at the step
+        // boundary may carry a timestamp from step 199.
+        assert!(validate_step_timestamp(200, 1000, 100, &cfg).is_ok());
+        assert!(validate_step_timestamp(200, 999, 100, &cfg).is_ok());
+        let err = validate_step_timestamp(200, 1005, 100, &cfg).unwrap_err();
+        assert!(
+            err.to_string().contains("does not match timestamp"),
+            "{err}"
+        );
+        assert!(validate_step_timestamp(150, 1000, 100, &cfg).is_err());
+        // Below `validateStepTransition` the timestamp is not checked.
+        assert!(validate_step_timestamp(150, 1000, 99, &cfg).is_ok());
+        // Nor anywhere on chains that do not set it.
+        assert!(validate_step_timestamp(150, 1000, 100, &aura_config(None)).is_ok());
+    }
+
+    #[test]
+    fn rejects_steps_too_far_ahead_of_local_clock() {
+        let cfg = aura_config(None);
+        let now = 1000; // local step 200
+        assert!(validate_step_not_in_future(200 + MAX_FUTURE_STEPS, 1, &cfg, now).is_ok());
+        let err = validate_step_not_in_future(201 + MAX_FUTURE_STEPS, 1, &cfg, now).unwrap_err();
+        assert!(err.to_string().contains("from the future"), "{err}");
+    }
+
+    /// Post-block finality state whose proposer set is `parent_set` and
+    /// whose current set is `post_set`, as after a `finalizeChange`.
+    fn finalized_change(parent_set: Vec<Address>, post_set: Vec<Address>) -> RollingFinality {
+        let mut state = RollingFinality::new(Vec::new());
+        state.set_validators(parent_set);
+        state.record_proposer_set();
+        state.set_validators(post_set);
+        state
+    }
+
+    #[test]
+    fn contract_proposer_uses_parent_state_set() {
+        let key = PrivateKeySigner::from_bytes(&B256::repeat_byte(0x42)).unwrap();
+        let signer = key.address();
+        let cache = Arc::new(FinalityCache::default());
+        let consensus = GnosisConsensus {
+            aura_config: Some(
+                AuraConfig::from_json_value(&serde_json::json!({
+                    "stepDuration": 5,
+                    "posdaoTransition": 0,
+                    "validators": { "multi": { "0": { "contract": "0x00000000000000000000000000000000000000aa" } } }
+                }))
+                .unwrap(),
+            ),
+            ..GnosisConsensus::new(Arc::new(GnosisChainSpec::from(CHIADO_GENESIS.clone())))
+        }
+        .with_finality_cache(cache.clone());
+
+        // Step 7 of a two-validator set: the proposer is the second one.
+        let mut header = GnosisHeader {
+            number: 10,
+            beneficiary: signer,
+            aura_step: Some(U256::from(7u64)),
+            ..Default::default()
+        };
+        let sig = key.sign_hash_sync(&compute_seal_hash(&header)).unwrap();
+        let mut seal = [0u8; 65];
+        seal[..32].copy_from_slice(&sig.r().to_be_bytes::<32>());
+        seal[32..64].copy_from_slice(&sig.s().to_be_bytes::<32>());
+        seal[64] = sig.v() as u8;
+        header.aura_seal = Some(FixedBytes(seal));
+        let header = SealedHeader::seal_slow(header);
+
+        // Not executed against the cache: fail closed.
+        assert!(consensus.validate_contract_proposer(&header).is_err());
+
+        // The block's own `finalizeChange` reordered the set; only the parent
+        // state's order decides its proposer.
+        cache.insert(
+            header.hash(),
+            finalized_change(vec![addr(1), signer], vec![signer, addr(1)]),
+        );
+        assert!(consensus.validate_contract_proposer(&header).is_ok());
+
+        cache.insert(
+            header.hash(),
+            finalized_change(vec![signer, addr(1)], vec![addr(1), signer]),
+        );
+        let err = consensus.validate_contract_proposer(&header).unwrap_err();
+        assert!(err.to_string().contains("proposer mismatch"), "{err}");
+    }
+}
@@ -0,0 +1,545 @@
+//! On-disk persistence of [`RollingFinality`] state in the datadir.
+//!
+//! [`persist_committed_finality`] follows the Execution stage checkpoint and
+//! snapshots the tracker state of every block committed to the database into
+//! `<datadir>/aura-finality/`. Each snapshot is the tracker state *after* the
+//! block, i.e. what `context_for_block(N + 1)` must start from, taken from the
+//! executor's [`crate::aura::finality_cache::FinalityCache`]. Blocks that are
+//! only executed (payload building, RPC replays, blocks the engine never makes
+//! canonical) are never written. On
+//! startup, `GnosisExecutorBuilder::build_evm` loads the newest snapshot at or
+//! below the canonical head whose block hash is canonical and replays any
+//! remaining gap from receipts (see
+//! [`crate::aura::recovery::recover_finality_at`]). Snapshots carry the block
+//! hash, and files are named by number and hash, because an unwind can
+//! replace a persisted block with a sibling of the same height.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use alloy_primitives::{Address, B256};
+use reth_chainspec::EthereumHardforks;
+use reth_stages_types::StageId;
+use reth_storage_api::{BlockHashReader, StageCheckpointReader};
+use serde::{Deserialize, Serialize};
+
+use crate::aura::finality::RollingFinality;
+use crate::evm_config::GnosisEvmConfig;
+
+/// Format version written into every snapshot. Bump on any incompatible
+/// change to [`FinalitySnapshot`]; snapshots with another version are ignored
+/// and recovery falls back to receipt replay.
+pub const FINALITY_SNAPSHOT_VERSION: u32 = 2;
+
+/// Name of the snapshot directory inside the datadir.
+pub const FINALITY_SNAPSHOT_DIR: &str = "aura-finality";
+
+/// Number of most recent snapshots to keep. Older ones only matter after an
+/// unwind deeper than all of them, which falls back to receipt replay.
+const SNAPSHOT_RETENTION: usize = 64;
+
+/// How often [`persist_committed_finality`] checks the Execution stage
+/// checkpoint.
+pub const FINALITY_PERSIST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Serializable [`RollingFinality`] state. `sign_count` is not stored — it is
+/// derived from `headers` on load.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalityState {
+    pub validators: Vec<Address>,
+    pub validators_sealed: bool,
+    pub headers: Vec<(u64, Address)>,
+    pub pending_transitions: BTreeMap<u64, Address>,
+    pub finalize_change_at: Option<(u64, Address)>,
+}
+
+/// A versioned [`FinalityState`] taken after block `block_hash` (at height
+/// `block_number`) was executed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalitySnapshot {
+    pub version: u32,
+    pub block_number: u64,
+    pub block_hash: B256,
+    pub state: FinalityState,
+}
+
+impl FinalitySnapshot {
+    /// Snapshot `finality` as the state after block `block_hash`.
+    pub fn new(block_number: u64, block_hash: B256, finality: &RollingFinality) -> Self {
+        Self {
+            version: FINALITY_SNAPSHOT_VERSION,
+            block_number,
+            block_hash,
+            state: finality.to_state(),
+        }
+    }
+}
+
+/// Directory-backed store of [`FinalitySnapshot`]s, one JSON file per block
+/// hash.
+#[derive(Debug, Clone)]
+pub struct FinalityStore {
+    dir: PathBuf,
+}
+
+impl FinalityStore {
+    /// Store rooted at `<data_dir>/aura-finality`. The directory is created
+    /// lazily on the first write.
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join(FINALITY_SNAPSHOT_DIR),
+        }
+    }
+
+    fn path_for(&self, block_number: u64, block_hash: B256) -> PathBuf {
+        self.dir
+            .join(format!("{block_number:012}-{block_hash:x}.json"))
+    }
+
+    /// Block numbers and hashes of all snapshot files present, ascending by
+    /// number.
+    fn stored_blocks(&self) -> eyre::Result<Vec<(u64, B256)>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut blocks = Vec::new();
+        for entry in entries {
+            let name = entry?.file_name();
+            let Some((number, hash)) = name
+                .to_str()
+                .and_then(|n| n.strip_suffix(".json"))
+                .and_then(|stem| stem.split_once('-'))
+            else {
+                continue;
+            };
+            if let (Ok(number), Ok(hash)) = (number.parse::<u64>(), hash.parse::<B256>()) {
+                blocks.push((number, hash));
+            }
+        }
+        blocks.sort_unstable();
+        Ok(blocks)
+    }
+
+    /// Write `snapshot` atomically (temp file + rename), then prune snapshots
+    /// that fall outside the retention policy.
+    pub fn save(&self, snapshot: &FinalitySnapshot) -> eyre::Result<()> {
+        self.save_all(std::slice::from_ref(snapshot))
+    }
+
+    /// [`Self::save`] for several snapshots, pruning once after the last
+    /// write.
+    pub fn save_all(&self, snapshots: &[FinalitySnapshot]) -> eyre::Result<()> {
+        let Some(latest) = snapshots.iter().map(|s| s.block_number).max() else {
+            return Ok(());
+        };
+        fs::create_dir_all(&self.dir)?;
+        for snapshot in snapshots {
+            let path = self.path_for(snapshot.block_number, snapshot.block_hash);
+            let tmp = path.with_extension("json.tmp");
+            fs::write(&tmp, serde_json::to_vec(snapshot)?)?;
+            fs::rename(&tmp, &path)?;
+        }
+        self.prune(latest)
+    }
+
+    /// Keep the [`SNAPSHOT_RETENTION`] newest snapshots at or below `latest`.
+    /// Snapshots above `latest` were written before an unwind; they are left
+    /// alone, never loaded for a lower head, and overwritten once the chain
+    /// grows past them again.
+    fn prune(&self, latest: u64) -> eyre::Result<()> {
+        let below: Vec<_> = self
+            .stored_blocks()?
+            .into_iter()
+            .filter(|(n, _)| *n <= latest)
+            .collect();
+        let excess = below.len().saturating_sub(SNAPSHOT_RETENTION);
+        for (n, hash) in &below[..excess] {
+            let _ = fs::remove_file(self.path_for(*n, *hash));
+        }
+        Ok(())
+    }
+
+    /// Read and validate the snapshot file for `block_hash` at
+    /// `block_number`. Files that fail to parse or carry an unknown version
+    /// are skipped with a warning, so a corrupt file degrades to an older
+    /// snapshot instead of blocking startup.
+    fn read(&self, block_number: u64, block_hash: B256) -> Option<FinalitySnapshot> {
+        let path = self.path_for(block_number, block_hash);
+        let snapshot = fs::read(&path)
+            .map_err(eyre::Report::from)
+            .and_then(|bytes| Ok(serde_json::from_slice::<FinalitySnapshot>(&bytes)?));
+        match snapshot {
+            Ok(s)
+                if s.version == FINALITY_SNAPSHOT_VERSION
+                    && s.block_number == block_number
+                    && s.block_hash == block_hash =>
+            {
+                Some(s)
+            }
+            Ok(s) => {
+                tracing::warn!(
+                    target: "reth::gnosis",
+                    path = %path.display(),
+                    version = s.version,
+                    "AuRa finality snapshot has unexpected version or block, skipping"
+                );
+                None
+            }
+            Err(e) => {
+                if !matches!(
+                    e.downcast_ref::<std::io::Error>(),
+                    Some(io) if io.kind() == std::io::ErrorKind::NotFound
+                ) {
+                    tracing::warn!(
+                        target: "reth::gnosis",
+                        path = %path.display(),
+                        %e,
+                        "AuRa finality snapshot unreadable, skipping"
+                    );
+                }
+                None
+            }
+        }
+    }
+
+    /// Snapshot taken after exactly block `block_hash` at `block_number`.
+    pub fn load(&self, block_number: u64, block_hash: B256) -> Option<FinalitySnapshot> {
+        self.read(block_number, block_hash)
+    }
+
+    /// Newest readable snapshot with `block_number <= head` for which
+    /// `is_canonical(block_number, block_hash)` holds. Snapshots of blocks
+    /// that were executed but later reorged out are skipped.
+    pub fn load_at_or_below(
+        &self,
+        head: u64,
+        is_canonical: impl Fn(u64, B256) -> bool,
+    ) -> eyre::Result<Option<FinalitySnapshot>> {
+        for (n, hash) in self
+            .stored_blocks()?
+            .into_iter()
+            .rev()
+            .filter(|(n, _)| *n <= head)
+        {
+            if !is_canonical(n, hash) {
+                tracing::debug!(
+                    target: "reth::gnosis",
+                    block = n,
+                    %hash,
+                    "AuRa finality snapshot is not on the canonical chain, skipping"
+                );
+                continue;
+            }
+            if let Some(snapshot) = self.read(n, hash) {
+                return Ok(Some(snapshot));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Snapshot the finality state of every block committed by the Execution
+/// stage, until the executed head is past the merge.
+///
+/// Follows the Execution stage checkpoint rather than the best block number,
+/// which during pipeline sync is the headers-stage tip and has not been
+/// executed yet, and rather than canonical state notifications, which
+/// pipeline sync never emits. The engine advances the same checkpoint when it
+/// persists blocks. See [`persist_executed_blocks`] for what each tick
+/// writes. A failed tick only costs a longer receipt replay on the next
+/// restart, so it is logged and retried on the next one.
+pub async fn persist_committed_finality<P>(
+    provider: P,
+    evm_config: GnosisEvmConfig,
+    store: FinalityStore,
+) where
+    P: StageCheckpointReader + BlockHashReader + Clone + Send + Sync + 'static,
+{
+    let evm_config = evm_config.with_read_only_finality();
+    let mut interval = tokio::time::interval(FINALITY_PERSIST_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last_saved: Option<u64> = None;
+    loop {
+        interval.tick().await;
+        let executed = match provider.get_stage_checkpoint(StageId::Execution) {
+            Ok(checkpoint) => checkpoint.map_or(0, |c| c.block_number),
+            Err(err) => {
+                tracing::warn!(
+                    target: "reth::gnosis",
+                    %err,
+                    "AuRa finality persistence: failed to read Execution stage checkpoint"
+                );
+                continue;
+            }
+        };
+        if evm_config.chain_spec().is_paris_active_at_block(executed) {
+            return;
+        }
+        // After an unwind the blocks above the new head are re-executed and
+        // may differ, so they are snapshotted again.
+        last_saved = last_saved.map(|n| n.min(executed));
+        if executed == 0 || last_saved == Some(executed) {
+            continue;
+        }
+
+        let (provider, evm_config, store) = (provider.clone(), evm_config.clone(), store.clone());
+        let saved = tokio::task::spawn_blocking(move || {
+            persist_executed_blocks(&evm_config, &store, executed, last_saved, |n| {
+                provider.block_hash(n).ok().flatten()
+            })
+        })
+        .await
+        .unwrap_or_else(|err| Err(err.into()));
+        match saved {
+            Ok(()) => last_saved = Some(executed),
+            Err(err) => tracing::warn!(
+                target: "reth::gnosis",
+                block = executed,
+                %err,
+                "Failed to persist AuRa rolling-finality snapshots"
+            ),
+        }
+    }
+}
+
+/// Snapshot the committed blocks after `last_saved` up to and including
+/// `executed`, the Execution stage checkpoint.
+///
+/// Only the newest [`SNAPSHOT_RETENTION`] blocks are considered, since older
+/// snapshots would be pruned right away. Their states come from the finality
+/// cache; a pipeline batch can execute past the cache's capacity before its
+/// checkpoint is read, so the state of `executed` itself falls back to
+/// [`GnosisEvmConfig::finality_after`] (persisted snapshot, then receipt
+/// replay) and fails the tick if that has nothing either. `canonical_hash`
+/// returns the committed hash at a height.
+pub fn persist_executed_blocks(
+    evm_config: &GnosisEvmConfig,
+    store: &FinalityStore,
+    executed: u64,
+    last_saved: Option<u64>,
+    canonical_hash: impl Fn(u64) -> Option<B256>,
+) -> eyre::Result<()> {
+    let first = last_saved
+        .map_or(1, |n| n + 1)
+        .max(executed.saturating_sub(SNAPSHOT_RETENTION as u64 - 1));
+    let mut snapshots = Vec::new();
+    for n in first..=executed {
+        let hash = canonical_hash(n)
+            .ok_or_else(|| eyre::eyre!("missing canonical hash for executed block {n}"))?;
+        let state = if n == executed {
+            evm_config.finality_after(n, hash)
+        } else {
+            evm_config.finality_cache.get(&hash)
+        };
+        match state {
+            Some(state) => snapshots.push(FinalitySnapshot::new(n, hash, &state)),
+            None if n == executed => {
+                eyre::bail!("no finality state for executed block {n} ({hash})")
+            }
+            None => tracing::debug!(
+                target: "reth::gnosis",
+                block = n,
+                %hash,
+                "AuRa finality persistence: committed block state not cached, skipping"
+            ),
+        }
+    }
+    store.save_all(&snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(b: u8) -> Address {
+        Address::from([b; 20])
+    }
+
+    fn hash(n: u64) -> B256 {
+        B256::left_padding_from(&n.to_be_bytes())
+    }
+
+    fn any_hash(_: u64, _: B256) -> bool {
+        true
+    }
+
+    fn temp_store(name: &str) -> FinalityStore {
+        let dir = std::env::temp_dir().join(format!(
+            "reth-gnosis-finality-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        FinalityStore::new(&dir)
+    }
+
+    /// Run the finality part of executing blocks `range` (each the child of
+    /// the previous one) the way the executor does: start from the parent's
+    /// state, push the signer and record the result under the block's hash.
+    fn execute_range(config: &GnosisEvmConfig, range: std::ops::RangeInclusive<u64>) {
+        for n in range {
+            let ctx = config
+                .aura_execution_ctx(n, hash(n - 1), None, Some(hash(n)))
+                .expect("pre-merge AuRa block");
+            assert!(!ctx.missing_parent_finality, "block {n}");
+            let mut rf = ctx.rolling_finality.lock().unwrap();
+            rf.push(n, addr((n % 4) as u8 + 1));
+            ctx.finality_cache
+                .as_ref()
+                .unwrap()
+                .insert(hash(n), rf.clone());
+        }
+    }
+
+    fn chiado_config(cache_capacity: usize) -> GnosisEvmConfig {
+        use crate::spec::{chains::CHIADO_GENESIS, gnosis_spec::GnosisChainSpec};
+        let spec = std::sync::Arc::new(GnosisChainSpec::from(CHIADO_GENESIS.clone()));
+        GnosisEvmConfig::new(spec, crate::evm_config::NoopHeaderLookup).with_finality_cache(
+            std::sync::Arc::new(crate::aura::finality_cache::FinalityCache::new(
+                cache_capacity,
+            )),
+        )
+    }
+
+    #[test]
+    fn state_roundtrips_through_snapshot_file() {
+        let store = temp_store("roundtrip");
+        let mut rf = RollingFinality::new(Vec::new());
+        rf.set_validators(vec![addr(1), addr(2), addr(3), addr(4)]);
+        rf.push(10, addr(1));
+        rf.push(11, addr(2));
+        rf.add_pending_transition(11, addr(0xaa));
+
+        store
+            .save(&FinalitySnapshot::new(11, hash(11), &rf))
+            .unwrap();
+        let loaded = store
+            .load_at_or_below(11, any_hash)
+            .unwrap()
+            .expect("snapshot present");
+        assert_eq!(loaded.block_number, 11);
+
+        // The restored tracker must finalize block 10 on the same push as
+        // the original, with the pending transition at 11 still queued.
+        let mut restored = RollingFinality::from_state(loaded.state);
+        assert_eq!(restored.push(12, addr(3)), rf.push(12, addr(3)));
+        assert_eq!(restored.pending_transitions(), rf.pending_transitions());
+        assert_eq!(restored.to_state(), rf.to_state());
+    }
+
+    #[test]
+    fn load_ignores_snapshots_above_head() {
+        let store = temp_store("above-head");
+        let rf = RollingFinality::new(vec![addr(1)]);
+        store
+            .save(&FinalitySnapshot::new(100, hash(100), &rf))
+            .unwrap();
+        store
+            .save(&FinalitySnapshot::new(101, hash(101), &rf))
+            .unwrap();
+        assert_eq!(
+            store
+                .load_at_or_below(100, any_hash)
+                .unwrap()
+                .map(|s| s.block_number),
+            Some(100)
+        );
+        assert!(store.load_at_or_below(99, any_hash).unwrap().is_none());
+    }
+
+    #[test]
+    fn load_skips_non_canonical_snapshots() {
+        let store = temp_store("non-canonical");
+        let rf = RollingFinality::new(vec![addr(1)]);
+        store
+            .save(&FinalitySnapshot::new(100, hash(100), &rf))
+            .unwrap();
+        // A sibling of block 101 was persisted and then unwound.
+        store
+            .save(&FinalitySnapshot::new(101, B256::repeat_byte(0xee), &rf))
+            .unwrap();
+
+        let loaded = store
+            .load_at_or_below(101, |n, h| h == hash(n))
+            .unwrap()
+            .map(|s| s.block_number);
+        assert_eq!(loaded, Some(100));
+        assert!(store.load(101, hash(101)).is_none());
+        assert!(store.load(101, B256::repeat_byte(0xee)).is_some());
+
+        // The canonical block 101 does not overwrite its sibling.
+        store
+            .save(&FinalitySnapshot::new(101, hash(101), &rf))
+            .unwrap();
+        assert!(store.load(101, B256::repeat_byte(0xee)).is_some());
+        let loaded = store
+            .load_at_or_below(101, |n, h| h == hash(n))
+            .unwrap()
+            .map(|s| s.block_hash);
+        assert_eq!(loaded, Some(hash(101)));
+    }
+
+    #[test]
+    fn prune_keeps_newest_snapshots_at_or_below_latest() {
+        let store = temp_store("prune");
+        let rf = RollingFinality::new(vec![addr(1)]);
+        // Left behind by an unwind from 10_000.
+        store
+            .save(&FinalitySnapshot::new(10_000, hash(10_000), &rf))
+            .unwrap();
+        let latest = 1_000 + SNAPSHOT_RETENTION as u64;
+        for n in 1_000..=latest {
+            store.save(&FinalitySnapshot::new(n, hash(n), &rf)).unwrap();
+        }
+
+        let blocks: Vec<u64> = store
+            .stored_blocks()
+            .unwrap()
+            .into_iter()
+            .map(|(n, _)| n)
+            .collect();
+        assert_eq!(blocks.len(), SNAPSHOT_RETENTION + 1);
+        assert!(!blocks.contains(&1_000), "oldest snapshot pruned");
+        assert!(blocks.contains(&latest));
+        assert!(blocks.contains(&10_000), "snapshot above latest kept");
+    }
+
+    #[test]
+    fn executed_pre_merge_range_is_snapshotted() {
+        let store = temp_store("executed-range");
+        let config = chiado_config(256);
+        execute_range(&config, 1..=300);
+
+        persist_executed_blocks(&config, &store, 300, None, |n| Some(hash(n))).unwrap();
+        let expected = config.finality_cache.get(&hash(300)).unwrap().to_state();
+        assert_eq!(store.load(300, hash(300)).unwrap().state, expected);
+        // Every retained committed block, not just the head.
+        let first = 300 - SNAPSHOT_RETENTION as u64 + 1;
+        assert!((first..=300).all(|n| store.load(n, hash(n)).is_some()));
+        assert!(store.load(first - 1, hash(first - 1)).is_none());
+
+        // The next checkpoint only adds the blocks committed since.
+        execute_range(&config, 301..=310);
+        persist_executed_blocks(&config, &store, 310, Some(300), |n| Some(hash(n))).unwrap();
+        assert!((301..=310).all(|n| store.load(n, hash(n)).is_some()));
+    }
+
+    #[test]
+    fn uncached_executed_head_fails_the_tick() {
+        let store = temp_store("evicted-head");
+        // The pipeline executed past the cache's capacity before the
+        // checkpoint was read, and there is nothing to recover it from.
+        let config = chiado_config(8);
+        execute_range(&config, 1..=20);
+
+        assert!(persist_executed_blocks(&config, &store, 10, None, |n| Some(hash(n))).is_err());
+        assert!(store.load(10, hash(10)).is_none());
+    }
+}
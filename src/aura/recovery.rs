@@ -1,7 +1,14 @@
-//! Receipt-replay reconstruction of [`RollingFinality`] state at startup.
+//! Reconstruction of [`RollingFinality`] state for a canonical block, used at
+//! startup and whenever the executor misses its parent in the in-memory
+//! [`crate::aura::finality_cache::FinalityCache`] (deep unwind or reorg).
 //!
-//! Inspired by Nethermind's `ContractBasedValidator.TryGetInitChangeFromPastBlocks`:
-//! walk back `reconstruction_lookback()` blocks from `head`, rebuild
+//! The primary source of truth is the persisted snapshot store
+//! ([`crate::aura::persistence`]); [`replay_from_state`] only has to cover the
+//! gap between the newest snapshot and the canonical head. When no snapshot
+//! exists (first start after upgrading, or a wiped datadir),
+//! [`reconstruct_finality_state`] falls back to a bounded replay inspired by
+//! Nethermind's `ContractBasedValidator.TryGetInitChangeFromPastBlocks`: walk
+//! back `reconstruction_lookback()` blocks from `head`, rebuild
 //! `pending_transitions` / `finalize_change_at` by replaying the rolling-finality
 //! state machine over historical headers + receipts.
 
@@ -12,6 +19,7 @@ use alloy_primitives::Address;
 use gnosis_primitives::header::GnosisHeader;
 
 use crate::aura::finality::RollingFinality;
+use crate::aura::persistence::FinalityStore;
 
 /// keccak256("InitiateChange(bytes32,address[])")
 pub const INITIATE_CHANGE_TOPIC: alloy_primitives::B256 =
@@ -33,20 +41,73 @@ pub trait ChainScanner: Send + Sync + std::fmt::Debug {
     fn receipts_by_block_number(&self, n: u64) -> Option<Vec<reth_ethereum_primitives::Receipt>>;
 }
 
+/// Rolling-finality state after canonical block `block`.
+///
+/// Prefers the newest persisted snapshot at or below `block` whose hash is on
+/// the canonical chain and replays the remaining gap; without one, falls back
+/// to [`reconstruct_finality_state`]. Callers must only ask for canonical
+/// blocks — `scanner` reads the canonical chain.
+pub fn recover_finality_at<S: ChainScanner + ?Sized>(
+    scanner: &S,
+    store: Option<&FinalityStore>,
+    block: u64,
+    validator_contract: Option<Address>,
+    posdao_transition: u64,
+    two_thirds_majority_transition: u64,
+) -> eyre::Result<RollingFinality> {
+    let snapshot = match store {
+        Some(store) => store.load_at_or_below(block, |n, hash| {
+            scanner
+                .header_by_number(n)
+                .is_some_and(|h| h.hash_slow() == hash)
+        })?,
+        None => None,
+    };
+    Ok(match snapshot {
+        Some(snapshot) => {
+            tracing::info!(
+                target: "reth::gnosis",
+                block,
+                snapshot_block = snapshot.block_number,
+                "AuRa recovery: loaded persisted rolling-finality snapshot"
+            );
+            replay_from_state(
+                scanner,
+                RollingFinality::from_state(snapshot.state),
+                snapshot.block_number,
+                block,
+                validator_contract,
+                posdao_transition,
+                two_thirds_majority_transition,
+            )
+        }
+        None => reconstruct_finality_state(
+            scanner,
+            block,
+            validator_contract,
+            posdao_transition,
+            two_thirds_majority_transition,
+        ),
+    })
+}
+
 /// Rebuild [`RollingFinality`] state for `head_block` by replaying receipts.
 ///
 /// `validator_contract == None` means the active set is `List`-typed; no
 /// `InitiateChange` is possible and an empty tracker is returned. The result's
 /// `validators` is intentionally empty — the next live block triggers a
 /// `getValidators()` refresh from the authoritative contract.
-pub fn reconstruct_finality_state<S: ChainScanner>(
+pub fn reconstruct_finality_state<S: ChainScanner + ?Sized>(
     scanner: &S,
     head_block: u64,
     validator_contract: Option<Address>,
     posdao_transition: u64,
+    two_thirds_majority_transition: u64,
 ) -> RollingFinality {
     let Some(contract) = validator_contract else {
-        return RollingFinality::from_recovered(BTreeMap::new(), None);
+        let mut rf = RollingFinality::from_recovered(BTreeMap::new(), None);
+        rf.set_two_thirds_majority_transition(two_thirds_majority_transition);
+        return rf;
     };
 
     let lookback = reconstruction_lookback();
@@ -72,32 +133,116 @@ pub fn reconstruct_finality_state<S: ChainScanner>(
         }
     }
 
-    // Pass 2: simulate take→scan→push in the same order as live execution
-    // (context_for_block(n).take_finalize_change(n), then finish() pushes the
-    // signer). Mismatched ordering would silently shift finalization timing.
+    // Pass 2: simulate take→scan→push in the same order as live execution.
     let mut sim = RollingFinality::new(discovered.clone());
     sim.set_validators(discovered);
+    sim.set_two_thirds_majority_transition(two_thirds_majority_transition);
+    replay_range(
+        &mut sim,
+        scanner,
+        start,
+        head_block,
+        Some(contract),
+        posdao_transition,
+    );
 
-    for n in start..=head_block {
-        let _ = sim.take_finalize_change(n);
+    let mut rf = RollingFinality::from_recovered(
+        sim.pending_transitions().clone(),
+        sim.finalize_change_at(),
+    );
+    rf.set_two_thirds_majority_transition(two_thirds_majority_transition);
+    rf
+}
+
+/// Bring a persisted tracker (state after `state_block`) forward to
+/// `head_block` by replaying receipts for the gap in between.
+///
+/// If a `finalizeChange` fires inside the gap, live execution would have
+/// refreshed the validator set from the contract at that point — which a
+/// receipt replay cannot do. In that case only the pending transitions and
+/// the scheduled `finalizeChange` are kept, and the validator set is left
+/// empty so the next live block triggers a `getValidators()` refresh.
+pub fn replay_from_state<S: ChainScanner + ?Sized>(
+    scanner: &S,
+    mut state: RollingFinality,
+    state_block: u64,
+    head_block: u64,
+    validator_contract: Option<Address>,
+    posdao_transition: u64,
+    two_thirds_majority_transition: u64,
+) -> RollingFinality {
+    // Chain-spec derived and not persisted — re-apply to restored states.
+    state.set_two_thirds_majority_transition(two_thirds_majority_transition);
+    if state_block >= head_block {
+        return state;
+    }
+
+    tracing::info!(
+        target: "reth::gnosis",
+        state_block,
+        head_block,
+        gap = head_block - state_block,
+        "AuRa recovery: replaying receipts on top of persisted rolling-finality state"
+    );
+
+    let validator_set_changed = replay_range(
+        &mut state,
+        scanner,
+        state_block + 1,
+        head_block,
+        validator_contract,
+        posdao_transition,
+    );
+    if validator_set_changed {
+        let mut rf = RollingFinality::from_recovered(
+            state.pending_transitions().clone(),
+            state.finalize_change_at(),
+        );
+        rf.set_two_thirds_majority_transition(two_thirds_majority_transition);
+        return rf;
+    }
+    state
+}
+
+/// Replay blocks `start..=end` on top of `sim` in the same order as live
+/// execution (`context_for_block(n).take_finalize_change(n)`, then `finish()`
+/// scans for `InitiateChange` and pushes the signer). Mismatched ordering
+/// would silently shift finalization timing.
+///
+/// Returns `true` if a `finalizeChange` fired in the range.
+fn replay_range<S: ChainScanner + ?Sized>(
+    sim: &mut RollingFinality,
+    scanner: &S,
+    start: u64,
+    end: u64,
+    validator_contract: Option<Address>,
+    posdao_transition: u64,
+) -> bool {
+    let mut finalized_change = false;
+    for n in start..=end {
+        finalized_change |= sim.take_finalize_change(n).is_some();
 
         let Some(header) = scanner.header_by_number(n) else {
             continue;
         };
-        let receipts = scanner.receipts_by_block_number(n).unwrap_or_default();
 
-        if receipts_contain_initiate_change(&receipts, contract) {
-            if n >= posdao_transition {
-                sim.add_pending_transition(n, contract);
-            } else {
-                sim.set_immediate_finalize(n + 1, contract);
+        if let Some(contract) = validator_contract {
+            let receipts = scanner.receipts_by_block_number(n).unwrap_or_default();
+            if receipts_contain_initiate_change(&receipts, contract) {
+                if n >= posdao_transition {
+                    sim.add_pending_transition(n, contract);
+                } else {
+                    sim.set_immediate_finalize(n + 1, contract);
+                }
             }
         }
 
-        sim.push(n, header.beneficiary);
+        // Like `finish`, which pushes signers from POSDAO on only.
+        if n >= posdao_transition {
+            sim.push(n, header.beneficiary);
+        }
     }
-
-    RollingFinality::from_recovered(sim.pending_transitions().clone(), sim.finalize_change_at())
+    finalized_change
 }
 
 /// True iff any receipt contains an `InitiateChange` event from `validator_contract`.
@@ -202,7 +347,7 @@ mod tests {
     #[test]
     fn returns_empty_state_when_no_validator_contract_active() {
         let scanner = MockScanner::default();
-        let mut rf = reconstruct_finality_state(&scanner, 100, None, 0);
+        let mut rf = reconstruct_finality_state(&scanner, 100, None, 0, u64::MAX);
         assert!(rf.pending_transitions().is_empty());
         assert_eq!(rf.finalize_change_at(), None);
         assert_eq!(rf.take_finalize_change(101), None);
@@ -220,7 +365,7 @@ mod tests {
             };
             scanner.put(n, addr(1), receipts);
         }
-        let mut rf = reconstruct_finality_state(&scanner, 100, Some(contract), 1000);
+        let mut rf = reconstruct_finality_state(&scanner, 100, Some(contract), 1000, u64::MAX);
         assert_eq!(rf.take_finalize_change(101), Some(contract));
     }
 
@@ -239,7 +384,7 @@ mod tests {
             };
             scanner.put(n, signer, receipts);
         }
-        let rf = reconstruct_finality_state(&scanner, 100, Some(contract), 0);
+        let rf = reconstruct_finality_state(&scanner, 100, Some(contract), 0, u64::MAX);
         let recovered_state_nonempty =
             !rf.pending_transitions().is_empty() || rf.finalize_change_at().is_some();
         assert!(recovered_state_nonempty);
@@ -260,8 +405,75 @@ mod tests {
             };
             scanner.put(n, addr(1), receipts);
         }
-        let mut rf = reconstruct_finality_state(&scanner, head, Some(contract), 0);
+        let mut rf = reconstruct_finality_state(&scanner, head, Some(contract), 0, u64::MAX);
         assert!(rf.pending_transitions().is_empty());
         assert_eq!(rf.take_finalize_change(head + 1), None);
     }
+
+    #[test]
+    fn persisted_pending_transition_survives_pruned_receipts() {
+        // The InitiateChange at block 10 is only known from the persisted
+        // state — its receipts are gone. Replaying the header-only gap must
+        // still finalize it and schedule finalizeChange for the next block.
+        let scanner = MockScanner::default();
+        let contract = addr(0xaa);
+        scanner.put(11, addr(2), vec![]);
+        scanner.put(12, addr(3), vec![]);
+
+        let mut state = RollingFinality::new(Vec::new());
+        state.set_validators(vec![addr(1), addr(2), addr(3), addr(4)]);
+        state.add_pending_transition(10, contract);
+        state.push(10, addr(1));
+
+        let mut rf = replay_from_state(&scanner, state, 10, 12, Some(contract), 0, u64::MAX);
+        assert!(rf.pending_transitions().is_empty());
+        assert_eq!(rf.take_finalize_change(13), Some(contract));
+    }
+
+    #[test]
+    fn replay_pushes_signers_from_posdao_transition_on() {
+        // An auto-discovering tracker restored before POSDAO must not pick up
+        // signers that execution never pushed.
+        let scanner = MockScanner::default();
+        scanner.put(11, addr(1), vec![]);
+        scanner.put(12, addr(2), vec![]);
+
+        let rf = replay_from_state(
+            &scanner,
+            RollingFinality::new(Vec::new()),
+            10,
+            12,
+            None,
+            12,
+            u64::MAX,
+        );
+        let state = rf.to_state();
+        assert_eq!(state.validators, vec![addr(2)]);
+        assert_eq!(state.headers, vec![(12, addr(2))]);
+    }
+
+    #[test]
+    fn replay_applies_two_thirds_majority_rule() {
+        // 6 validators, 4 unique signers over blocks 10..=13: a simple
+        // majority finalizes block 10, a two-thirds majority does not.
+        let scanner = MockScanner::default();
+        let contract = addr(0xaa);
+        for (n, signer) in [(11, 2), (12, 3), (13, 4)] {
+            scanner.put(n, addr(signer), vec![]);
+        }
+        let state = || {
+            let mut state = RollingFinality::new(Vec::new());
+            state.set_validators((1..=6).map(addr).collect());
+            state.add_pending_transition(10, contract);
+            state.push(10, addr(1));
+            state
+        };
+
+        let mut rf = replay_from_state(&scanner, state(), 10, 13, Some(contract), 0, u64::MAX);
+        assert_eq!(rf.take_finalize_change(14), Some(contract));
+
+        let mut rf = replay_from_state(&scanner, state(), 10, 13, Some(contract), 0, 11);
+        assert_eq!(rf.pending_transitions().len(), 1);
+        assert_eq!(rf.take_finalize_change(14), None);
+    }
 }
@@ -0,0 +1,336 @@
+//! `aura_*` JSON-RPC namespace: validator sets, seal authors, steps and
+//! rolling-finality state of the pre-merge AuRa chain.
+//!
+//! Registered for every configured transport in `main.rs` via
+//! `extend_rpc_modules`. Handlers read the database, call validator
+//! contracts and may replay receipts to rebuild finality state, so each runs
+//! on a blocking task rather than on the RPC server's async workers.
+
+use std::collections::BTreeMap;
+
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, Bytes, B256, U64};
+use gnosis_primitives::header::GnosisHeader;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::{
+        error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+        ErrorObjectOwned,
+    },
+};
+use reth_evm::{ConfigureEvm, Evm};
+use reth_primitives_traits::SealedHeader;
+use reth_revm::database::StateProviderDatabase;
+use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
+use serde::{Deserialize, Serialize};
+
+use crate::aura::{seal::recover_seal_author, validators::ValidatorSetKind};
+use crate::block::decode_address_array;
+use crate::evm_config::GnosisEvmConfig;
+
+/// AuRa introspection.
+#[rpc(server, namespace = "aura")]
+pub trait AuraApi {
+    /// Validator set that sealed `block`: the set active at its parent.
+    /// Contract-based sets are resolved by calling `getValidators()` on the
+    /// parent state, as the proposer check does.
+    #[method(name = "getValidators")]
+    async fn get_validators(&self, block: BlockNumberOrTag) -> RpcResult<AuraValidators>;
+
+    /// Validator that sealed `block`, recovered from its AuRa seal.
+    #[method(name = "getAuthor")]
+    async fn get_author(&self, block: BlockNumberOrTag) -> RpcResult<Address>;
+
+    /// AuRa step of `block`.
+    #[method(name = "getStep")]
+    async fn get_step(&self, block: BlockNumberOrTag) -> RpcResult<U64>;
+
+    /// Rolling-finality state after the latest block: validator set, pending
+    /// `InitiateChange` transitions and the scheduled `finalizeChange`.
+    /// `null` once the latest block is post-merge, where AuRa finality no
+    /// longer applies.
+    #[method(name = "getFinalityStatus")]
+    async fn get_finality_status(&self) -> RpcResult<Option<AuraFinalityStatus>>;
+}
+
+/// Kind of an AuRa validator set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuraValidatorSetKind {
+    List,
+    Contract,
+}
+
+/// Response of `aura_getValidators`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuraValidators {
+    pub block_number: U64,
+    pub kind: AuraValidatorSetKind,
+    /// Validator contract of a contract-based set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract: Option<Address>,
+    pub validators: Vec<Address>,
+}
+
+/// Response of `aura_getFinalityStatus`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuraFinalityStatus {
+    /// Block the state was taken after.
+    pub block_number: U64,
+    pub block_hash: B256,
+    /// Validator set the tracker counts signatures against.
+    pub validators: Vec<Address>,
+    /// Whether `validators` was fetched via `getValidators()` rather than
+    /// auto-discovered from block signers.
+    pub validators_sealed: bool,
+    /// Unfinalized `InitiateChange` events: block number -> validator contract.
+    pub pending_transitions: BTreeMap<u64, Address>,
+    /// Finalized transition whose `finalizeChange()` runs in the next block.
+    pub finalize_change_at: Option<(u64, Address)>,
+}
+
+/// [`AuraApiServer`] implementation over the node's provider and EVM config.
+#[derive(Debug, Clone)]
+pub struct AuraRpc<Provider> {
+    provider: Provider,
+    evm_config: GnosisEvmConfig,
+}
+
+impl<Provider> AuraRpc<Provider> {
+    /// `evm_config` must be the node's executor config, so finality state is
+    /// read from the same cache and snapshot store the executor writes. The
+    /// RPC only reads them.
+    pub fn new(provider: Provider, evm_config: GnosisEvmConfig) -> Self {
+        Self {
+            provider,
+            evm_config: evm_config.with_read_only_finality(),
+        }
+    }
+}
+
+impl<Provider> AuraRpc<Provider>
+where
+    Provider: BlockReaderIdExt<Header = GnosisHeader> + StateProviderFactory,
+{
+    /// Header of `block`, which must exist and be pre-merge.
+    fn aura_header(&self, block: BlockNumberOrTag) -> RpcResult<SealedHeader<GnosisHeader>> {
+        let header = self
+            .provider
+            .sealed_header_by_number_or_tag(block)
+            .map_err(internal_error)?
+            .ok_or_else(|| invalid_params(format!("block {block} not found")))?;
+        if !header.is_pre_merge() {
+            return Err(invalid_params(format!(
+                "block {} is not an AuRa block",
+                header.number
+            )));
+        }
+        Ok(header)
+    }
+
+    /// `getValidators()` on `contract`, evaluated on the parent state of
+    /// `header` (on its own state for genesis).
+    fn call_get_validators(
+        &self,
+        header: &SealedHeader<GnosisHeader>,
+        contract: Address,
+    ) -> RpcResult<Vec<Address>> {
+        let state_hash = if header.number == 0 {
+            header.hash()
+        } else {
+            header.parent_hash
+        };
+        let state = self
+            .provider
+            .state_by_block_hash(state_hash)
+            .map_err(internal_error)?;
+        let evm_env = self
+            .evm_config
+            .evm_env(header.header())
+            .map_err(internal_error)?;
+        let mut evm = self
+            .evm_config
+            .evm_with_env(StateProviderDatabase::new(state), evm_env);
+        // getValidators() selector = 0xb7ab4db5
+        let result = evm
+            .transact_system_call(
+                alloy_eips::eip4788::SYSTEM_ADDRESS,
+                contract,
+                Bytes::from_static(&[0xb7, 0xab, 0x4d, 0xb5]),
+            )
+            .map_err(internal_error)?
+            .result;
+        match result {
+            revm::context::result::ExecutionResult::Success { output, .. } => {
+                decode_address_array(&output.into_data()).map_err(|_| {
+                    internal_error(format!(
+                        "getValidators() at block {} returned undecodable data",
+                        header.number
+                    ))
+                })
+            }
+            other => Err(internal_error(format!(
+                "getValidators() at block {} did not succeed: {other:?}",
+                header.number
+            ))),
+        }
+    }
+
+    /// Blocking body of `aura_getValidators`.
+    fn validators(&self, block: BlockNumberOrTag) -> RpcResult<AuraValidators> {
+        let header = self.aura_header(block)?;
+        let kind = self
+            .evm_config
+            .chain_spec()
+            .aura_config
+            .as_ref()
+            .and_then(|c| c.validators.kind_at(header.number.saturating_sub(1)))
+            .ok_or_else(|| {
+                internal_error(format!("no AuRa validator set at block {}", header.number))
+            })?;
+        let block_number = U64::from(header.number);
+        Ok(match kind {
+            ValidatorSetKind::List(validators) => AuraValidators {
+                block_number,
+                kind: AuraValidatorSetKind::List,
+                contract: None,
+                validators: validators.clone(),
+            },
+            ValidatorSetKind::Contract { address } => AuraValidators {
+                block_number,
+                kind: AuraValidatorSetKind::Contract,
+                contract: Some(*address),
+                validators: self.call_get_validators(&header, *address)?,
+            },
+        })
+    }
+
+    /// Blocking body of `aura_getFinalityStatus`. Rebuilding the state may
+    /// replay receipts when it is neither cached nor snapshotted.
+    fn finality_status(&self) -> RpcResult<Option<AuraFinalityStatus>> {
+        let header = self
+            .provider
+            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)
+            .map_err(internal_error)?
+            .ok_or_else(|| internal_error("latest block not found"))?;
+        if !header.is_pre_merge() {
+            return Ok(None);
+        }
+        let finality = self
+            .evm_config
+            .finality_after(header.number, header.hash())
+            .ok_or_else(|| {
+                internal_error(format!(
+                    "AuRa finality state unavailable after block {}",
+                    header.number
+                ))
+            })?;
+        let state = finality.to_state();
+        Ok(Some(AuraFinalityStatus {
+            block_number: U64::from(header.number),
+            block_hash: header.hash(),
+            validators: state.validators,
+            validators_sealed: state.validators_sealed,
+            pending_transitions: state.pending_transitions,
+            finalize_change_at: state.finalize_change_at,
+        }))
+    }
+}
+
+impl<Provider> AuraRpc<Provider>
+where
+    Provider: BlockReaderIdExt<Header = GnosisHeader> + StateProviderFactory + Clone + 'static,
+{
+    /// Run `handler` off the async runtime.
+    async fn spawn_blocking<T: Send + 'static>(
+        &self,
+        handler: impl FnOnce(&Self) -> RpcResult<T> + Send + 'static,
+    ) -> RpcResult<T> {
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || handler(&this))
+            .await
+            .map_err(internal_error)?
+    }
+}
+
+#[async_trait]
+impl<Provider> AuraApiServer for AuraRpc<Provider>
+where
+    Provider: BlockReaderIdExt<Header = GnosisHeader> + StateProviderFactory + Clone + 'static,
+{
+    async fn get_validators(&self, block: BlockNumberOrTag) -> RpcResult<AuraValidators> {
+        self.spawn_blocking(move |this| this.validators(block))
+            .await
+    }
+
+    async fn get_author(&self, block: BlockNumberOrTag) -> RpcResult<Address> {
+        self.spawn_blocking(move |this| {
+            let header = this.aura_header(block)?;
+            recover_seal_author(header.header()).map_err(internal_error)
+        })
+        .await
+    }
+
+    async fn get_step(&self, block: BlockNumberOrTag) -> RpcResult<U64> {
+        self.spawn_blocking(move |this| {
+            let header = this.aura_header(block)?;
+            let step = header
+                .aura_step
+                .ok_or_else(|| internal_error("missing AuRa step"))?;
+            u64::try_from(step)
+                .map(U64::from)
+                .map_err(|_| internal_error(format!("AuRa step exceeds u64: {step}")))
+        })
+        .await
+    }
+
+    async fn get_finality_status(&self) -> RpcResult<Option<AuraFinalityStatus>> {
+        self.spawn_blocking(|this| this.finality_status()).await
+    }
+}
+
+fn internal_error(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+fn invalid_params(msg: impl Into<String>) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INVALID_PARAMS_CODE, msg.into(), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validators_response_shape() {
+        let list = AuraValidators {
+            block_number: U64::from(10),
+            kind: AuraValidatorSetKind::List,
+            contract: None,
+            validators: vec![Address::from([1; 20])],
+        };
+        assert_eq!(
+            serde_json::to_value(&list).unwrap(),
+            serde_json::json!({
+                "blockNumber": "0xa",
+                "kind": "list",
+                "validators": ["0x0101010101010101010101010101010101010101"],
+            })
+        );
+
+        let contract = AuraValidators {
+            kind: AuraValidatorSetKind::Contract,
+            contract: Some(Address::from([2; 20])),
+            ..list
+        };
+        let value = serde_json::to_value(&contract).unwrap();
+        assert_eq!(value["kind"], "contract");
+        assert_eq!(
+            value["contract"],
+            "0x0202020202020202020202020202020202020202"
+        );
+    }
+}
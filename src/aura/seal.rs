@@ -125,7 +125,7 @@ pub fn calculate_aura_difficulty(parent_step: u64, current_step: u64) -> U256 {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use alloy_primitives::{address, b256, bloom, bytes, fixed_bytes, FixedBytes};
 
@@ -160,7 +160,7 @@ mod tests {
     /// against `https://gnosis-chiado-rpc.publicnode.com/`). Used as a golden vector
     /// for `recover_seal_author`: changing the RLP field order or set in
     /// `compute_seal_hash` will flip the recovered signer.
-    fn chiado_block_100k_header() -> GnosisHeader {
+    pub(crate) fn chiado_block_100k_header() -> GnosisHeader {
         GnosisHeader {
             parent_hash: b256!(
                 "0xcec0385ac2b2aa8557e2ad9318ddb419a70d53eac388aa06d5ec028e2b2bf05d"
@@ -0,0 +1,166 @@
+//! Seal authors recovered during header validation, keyed by block hash.
+//!
+//! A secp256k1 recovery per header dominates AuRa header validation. For
+//! blocks with a contract-based validator set the proposer is checked again
+//! after execution (`GnosisConsensus::validate_contract_proposer`), which
+//! takes the author recovered for the header instead of recovering it twice.
+//!
+//! Recovery stays one header at a time, inline in header validation: reth
+//! validates headers one by one and has no hook for recovering a whole
+//! batch up front, so there is no parallel pre-verification path.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use alloy_primitives::{Address, B256};
+use gnosis_primitives::header::GnosisHeader;
+use reth_primitives_traits::SealedHeader;
+
+use crate::aura::seal::{recover_seal_author, SealError};
+
+/// Number of recovered authors kept. The engine executes a block right after
+/// validating its header, so a few blocks suffice there. During pipeline
+/// sync the Headers stage validates whole ranges long before execution, a
+/// gap no bounded cache covers; the post-execution check then recovers the
+/// author again.
+pub const SEAL_AUTHOR_CACHE_CAPACITY: usize = 1_024;
+
+/// Bounded map `block_hash -> seal author`, evicting the oldest insertion.
+///
+/// Only successful recoveries are stored: a header whose seal fails to
+/// recover is recovered again on every lookup, which reports the error.
+#[derive(Debug)]
+pub struct SealAuthorCache {
+    capacity: usize,
+    inner: Mutex<CacheInner>,
+}
+
+#[derive(Debug, Default)]
+struct CacheInner {
+    authors: HashMap<B256, Address>,
+    /// Insertion order, oldest first.
+    order: VecDeque<B256>,
+}
+
+impl CacheInner {
+    fn insert(&mut self, hash: B256, author: Address, capacity: usize) {
+        if self.authors.insert(hash, author).is_none() {
+            self.order.push_back(hash);
+        }
+        while self.order.len() > capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.authors.remove(&evicted);
+            }
+        }
+    }
+}
+
+impl Default for SealAuthorCache {
+    fn default() -> Self {
+        Self::new(SEAL_AUTHOR_CACHE_CAPACITY)
+    }
+}
+
+impl SealAuthorCache {
+    /// Empty cache holding at most `capacity` authors.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            inner: Mutex::new(CacheInner::default()),
+        }
+    }
+
+    /// Cached author of block `hash`.
+    pub fn get(&self, hash: &B256) -> Option<Address> {
+        self.inner.lock().ok()?.authors.get(hash).copied()
+    }
+
+    /// Author of `header`: the cached one if already recovered, otherwise
+    /// recovered now (and cached for later lookups of the same block).
+    pub fn author(&self, header: &SealedHeader<GnosisHeader>) -> Result<Address, SealError> {
+        let hash = header.hash();
+        if let Some(author) = self.get(&hash) {
+            return Ok(author);
+        }
+        let author = recover_seal_author(header.header())?;
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.insert(hash, author, self.capacity);
+        }
+        Ok(author)
+    }
+
+    /// Number of cached authors.
+    pub fn len(&self) -> usize {
+        self.inner.lock().map(|i| i.authors.len()).unwrap_or(0)
+    }
+
+    /// True if no author is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{FixedBytes, U256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    use crate::aura::seal::compute_seal_hash;
+
+    fn sealed_header(number: u64, key: u8) -> SealedHeader<GnosisHeader> {
+        let signer = PrivateKeySigner::from_bytes(&B256::repeat_byte(key)).unwrap();
+        let mut header = GnosisHeader {
+            number,
+            beneficiary: signer.address(),
+            aura_step: Some(U256::from(number)),
+            ..Default::default()
+        };
+        let sig = signer.sign_hash_sync(&compute_seal_hash(&header)).unwrap();
+        header.aura_seal = Some(FixedBytes::from(sig.as_bytes()));
+        SealedHeader::seal_slow(header)
+    }
+
+    #[test]
+    fn caches_recovered_authors() {
+        let headers: Vec<_> = (1..=32)
+            .map(|n| sealed_header(n, (n % 4 + 1) as u8))
+            .collect();
+        let cache = SealAuthorCache::default();
+        for header in &headers {
+            assert_eq!(cache.author(header).unwrap(), header.beneficiary);
+        }
+        assert_eq!(cache.len(), headers.len());
+        for header in &headers {
+            let expected = recover_seal_author(header.header()).unwrap();
+            assert_eq!(cache.get(&header.hash()), Some(expected));
+        }
+    }
+
+    #[test]
+    fn failed_recoveries_are_not_cached() {
+        let corrupted = SealedHeader::seal_slow(GnosisHeader {
+            number: 1,
+            aura_step: Some(U256::from(1u64)),
+            aura_seal: Some(FixedBytes::ZERO),
+            ..Default::default()
+        });
+
+        let cache = SealAuthorCache::default();
+        assert!(cache.author(&corrupted).is_err());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn evicts_oldest_authors() {
+        let headers: Vec<_> = (1..=3).map(|n| sealed_header(n, 1)).collect();
+        let cache = SealAuthorCache::new(2);
+        for header in &headers {
+            cache.author(header).unwrap();
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&headers[0].hash()).is_none());
+        assert!(cache.get(&headers[2].hash()).is_some());
+    }
+}
@@ -0,0 +1,483 @@
+//! AuRa transaction permission contract (`transactionPermissionContract`).
+//!
+//! The contract returns, per sender, a bit mask of the transaction types it
+//! may send. Its ABI grew over four versions, reported by
+//! `contractVersion()` (a contract without it is version 1):
+//!
+//! - v1: `allowedTxTypes(sender)`
+//! - v2: `allowedTxTypes(sender, to, value)`
+//! - v3: `allowedTxTypes(sender, to, value, gasPrice, data)`
+//! - v4: `allowedTxTypes(sender, to, value, maxFeePerGas, maxPriorityFeePerGas,
+//!   gasLimit, data)`
+//!
+//! `to` is the zero address for contract creations. A reverting
+//! `allowedTxTypes` call denies the transaction. From v2 on the contract also
+//! says whether its answer may be cached for the sender until the next block.
+//!
+//! Like Nethermind, every transaction of a block is checked on the parent
+//! state: [`denied_transactions`] runs before the block executes.
+
+use std::{collections::HashMap, ops::Deref};
+
+use alloy_consensus::{constants::KECCAK_EMPTY, Transaction};
+use alloy_evm::InvalidTxError;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_sol_types::SolCall;
+use reth_evm::Evm;
+use reth_transaction_pool::error::PoolTransactionError;
+use revm::{
+    context::result::{ExecutionResult, InvalidTransaction},
+    Database,
+};
+
+mod abi {
+    alloy_sol_macro::sol! {
+        function contractVersion() returns (uint256);
+    }
+
+    pub mod v1 {
+        alloy_sol_macro::sol! {
+            function allowedTxTypes(address sender) returns (uint32);
+        }
+    }
+
+    pub mod v2 {
+        alloy_sol_macro::sol! {
+            function allowedTxTypes(address sender, address to, uint256 value)
+                returns (uint32, bool);
+        }
+    }
+
+    pub mod v3 {
+        alloy_sol_macro::sol! {
+            function allowedTxTypes(
+                address sender,
+                address to,
+                uint256 value,
+                uint256 gasPrice,
+                bytes data
+            ) returns (uint32, bool);
+        }
+    }
+
+    pub mod v4 {
+        alloy_sol_macro::sol! {
+            function allowedTxTypes(
+                address sender,
+                address to,
+                uint256 value,
+                uint256 maxFeePerGas,
+                uint256 maxPriorityFeePerGas,
+                uint256 gasLimit,
+                bytes data
+            ) returns (uint32, bool);
+        }
+    }
+}
+
+/// Highest `contractVersion()` understood.
+pub const MAX_CONTRACT_VERSION: u64 = 4;
+
+/// Transaction type, as a bit of the `allowedTxTypes` mask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxPermission {
+    /// Value transfer to an account without code.
+    Basic = 0x1,
+    /// Call to an account with code.
+    Call = 0x2,
+    /// Contract creation.
+    Create = 0x4,
+}
+
+impl std::fmt::Display for TxPermission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Basic => "basic",
+            Self::Call => "call",
+            Self::Create => "create",
+        })
+    }
+}
+
+/// Transaction the permission contract does not allow.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "sender {sender} may not send {kind} transactions (permission contract {contract} v{version})"
+)]
+pub struct TxPermissionDenied {
+    pub sender: Address,
+    pub kind: TxPermission,
+    pub contract: Address,
+    pub version: u64,
+}
+
+impl InvalidTxError for TxPermissionDenied {
+    fn as_invalid_tx_err(&self) -> Option<&InvalidTransaction> {
+        None
+    }
+}
+
+/// Failure to check a transaction against the permission contract.
+#[derive(Debug, thiserror::Error)]
+pub enum TxPermissionError {
+    #[error(transparent)]
+    Denied(#[from] TxPermissionDenied),
+    #[error("permission contract {contract} has unsupported version {version}")]
+    UnsupportedVersion { contract: Address, version: u64 },
+    #[error("permission contract call failed: {0}")]
+    Call(String),
+}
+
+impl TxPermissionError {
+    pub(crate) fn call(err: impl std::fmt::Display) -> Self {
+        Self::Call(err.to_string())
+    }
+}
+
+impl PoolTransactionError for TxPermissionError {
+    fn is_bad_transaction(&self) -> bool {
+        // A peer may have checked the transaction against another head, where
+        // the contract allowed it; and a failing contract call is our problem.
+        false
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// `contractVersion()` of the permission contract on `evm`'s state; 1 if the
+/// call does not succeed.
+pub fn contract_version<E: Evm>(evm: &mut E, contract: Address) -> Result<u64, TxPermissionError> {
+    let output = match call(evm, contract, abi::contractVersionCall {}.abi_encode())? {
+        Some(output) => output,
+        None => return Ok(1),
+    };
+    let version =
+        abi::contractVersionCall::abi_decode_returns(&output).map_err(TxPermissionError::call)?;
+    let version = u64::try_from(version).unwrap_or(u64::MAX);
+    if version == 0 || version > MAX_CONTRACT_VERSION {
+        return Err(TxPermissionError::UnsupportedVersion { contract, version });
+    }
+    Ok(version)
+}
+
+/// Answer of `allowedTxTypes` for a sender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllowedTxTypes {
+    /// Bit mask of [`TxPermission`]s.
+    pub mask: u32,
+    /// Whether the answer holds for every transaction of the sender until the
+    /// next block: always for v1, as the contract says from v2 on.
+    pub cacheable: bool,
+}
+
+impl AllowedTxTypes {
+    /// Whether `kind` transactions are allowed.
+    pub const fn allows(&self, kind: TxPermission) -> bool {
+        self.mask & kind as u32 != 0
+    }
+}
+
+/// Kind of `tx` on `evm`'s state: a call if `to` has code.
+pub fn tx_permission_kind<E: Evm>(
+    evm: &mut E,
+    tx: &impl Transaction,
+) -> Result<TxPermission, TxPermissionError> {
+    let Some(to) = tx.to() else {
+        return Ok(TxPermission::Create);
+    };
+    let has_code = evm
+        .db_mut()
+        .basic(to)
+        .map_err(TxPermissionError::call)?
+        .is_some_and(|account| account.code_hash != KECCAK_EMPTY);
+    Ok(if has_code {
+        TxPermission::Call
+    } else {
+        TxPermission::Basic
+    })
+}
+
+/// `allowedTxTypes` of the `version` permission contract for `sender` sending
+/// `tx`, on `evm`'s state. A failing call allows nothing.
+pub fn allowed_tx_types<E: Evm>(
+    evm: &mut E,
+    contract: Address,
+    version: u64,
+    sender: Address,
+    tx: &impl Transaction,
+) -> Result<AllowedTxTypes, TxPermissionError> {
+    let data = allowed_tx_types_call(version, sender, tx);
+    let Some(output) = call(evm, contract, data)? else {
+        return Ok(AllowedTxTypes {
+            mask: 0,
+            cacheable: false,
+        });
+    };
+    let word = |index: usize| {
+        output
+            .get(index * 32..(index + 1) * 32)
+            .map(U256::from_be_slice)
+            .unwrap_or_default()
+    };
+    Ok(AllowedTxTypes {
+        // The mask is the first return word for every version.
+        mask: word(0).saturating_to(),
+        cacheable: version == 1 || !word(1).is_zero(),
+    })
+}
+
+/// Check that the `version` permission contract allows `sender` to send `tx`,
+/// on `evm`'s state.
+pub fn check_tx_permission<E: Evm>(
+    evm: &mut E,
+    contract: Address,
+    version: u64,
+    sender: Address,
+    tx: &impl Transaction,
+) -> Result<(), TxPermissionError> {
+    let kind = tx_permission_kind(evm, tx)?;
+    if !allowed_tx_types(evm, contract, version, sender, tx)?.allows(kind) {
+        return Err(TxPermissionDenied {
+            sender,
+            kind,
+            contract,
+            version,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Transactions among `txs` (hash, sender, transaction) the permission
+/// contract denies, checked on `evm`'s state, which must be the parent state
+/// of their block.
+pub fn denied_transactions<E, T>(
+    evm: &mut E,
+    contract: Address,
+    txs: impl IntoIterator<Item = (B256, Address, T)>,
+) -> Result<HashMap<B256, TxPermissionDenied>, TxPermissionError>
+where
+    E: Evm,
+    T: Deref<Target: Transaction + Sized>,
+{
+    let version = contract_version(evm, contract)?;
+    let mut denied = HashMap::new();
+    for (hash, sender, tx) in txs {
+        match check_tx_permission(evm, contract, version, sender, &*tx) {
+            Ok(()) => {}
+            Err(TxPermissionError::Denied(denial)) => {
+                denied.insert(hash, denial);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(denied)
+}
+
+/// `allowedTxTypes` calldata for `version`.
+fn allowed_tx_types_call(version: u64, sender: Address, tx: &impl Transaction) -> Vec<u8> {
+    let to = tx.to().unwrap_or_default();
+    match version {
+        1 => abi::v1::allowedTxTypesCall { sender }.abi_encode(),
+        2 => abi::v2::allowedTxTypesCall {
+            sender,
+            to,
+            value: tx.value(),
+        }
+        .abi_encode(),
+        3 => abi::v3::allowedTxTypesCall {
+            sender,
+            to,
+            value: tx.value(),
+            gasPrice: U256::from(tx.priority_fee_or_price()),
+            data: tx.input().clone(),
+        }
+        .abi_encode(),
+        _ => abi::v4::allowedTxTypesCall {
+            sender,
+            to,
+            value: tx.value(),
+            maxFeePerGas: U256::from(tx.max_fee_per_gas()),
+            maxPriorityFeePerGas: U256::from(tx.priority_fee_or_price()),
+            gasLimit: U256::from(tx.gas_limit()),
+            data: tx.input().clone(),
+        }
+        .abi_encode(),
+    }
+}
+
+/// Read-only system call to `contract`; `None` if it did not succeed.
+fn call<E: Evm>(
+    evm: &mut E,
+    contract: Address,
+    data: Vec<u8>,
+) -> Result<Option<Bytes>, TxPermissionError> {
+    let result = evm
+        .transact_system_call(
+            alloy_eips::eip4788::SYSTEM_ADDRESS,
+            contract,
+            Bytes::from(data),
+        )
+        .map_err(TxPermissionError::call)?
+        .result;
+    Ok(match result {
+        ExecutionResult::Success { output, .. } => Some(output.into_data()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{address, TxKind};
+    use revm::database::{CacheDB, EmptyDB};
+
+    use crate::service_tx::tests::{deploy, returning, reverting, test_evm};
+
+    const CONTRACT: Address = address!("0x4000000000000000000000000000000000000001");
+    const SENDER: Address = address!("0x1000000000000000000000000000000000000001");
+    const TARGET: Address = address!("0x2000000000000000000000000000000000000002");
+
+    /// Permission contract answering `word` to every call, so both
+    /// `contractVersion()` and the mask.
+    fn contract_answering(word: u8) -> CacheDB<EmptyDB> {
+        let mut db = CacheDB::new(EmptyDB::default());
+        let mut answer = [0; 32];
+        answer[31] = word;
+        deploy(&mut db, CONTRACT, returning(answer));
+        db
+    }
+
+    fn tx_to(to: TxKind) -> TxLegacy {
+        TxLegacy {
+            to,
+            gas_limit: 21_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn checks_the_transaction_kind_against_the_mask() {
+        // Version 2 with only calls allowed.
+        let mut db = contract_answering(TxPermission::Call as u8);
+        deploy(&mut db, TARGET, reverting());
+        let mut evm = test_evm(db);
+        let version = contract_version(&mut evm, CONTRACT).unwrap();
+        assert_eq!(version, 2);
+
+        let call = tx_to(TxKind::Call(TARGET));
+        assert!(check_tx_permission(&mut evm, CONTRACT, version, SENDER, &call).is_ok());
+        let transfer = tx_to(TxKind::Call(SENDER));
+        assert!(matches!(
+            check_tx_permission(&mut evm, CONTRACT, version, SENDER, &transfer),
+            Err(TxPermissionError::Denied(TxPermissionDenied {
+                kind: TxPermission::Basic,
+                ..
+            }))
+        ));
+        let create = tx_to(TxKind::Create);
+        assert!(matches!(
+            check_tx_permission(&mut evm, CONTRACT, version, SENDER, &create),
+            Err(TxPermissionError::Denied(TxPermissionDenied {
+                kind: TxPermission::Create,
+                ..
+            }))
+        ));
+        // No `cache` flag in the answer.
+        assert!(
+            !allowed_tx_types(&mut evm, CONTRACT, version, SENDER, &call)
+                .unwrap()
+                .cacheable
+        );
+    }
+
+    #[test]
+    fn contract_without_version_is_v1() {
+        let mut db = CacheDB::new(EmptyDB::default());
+        deploy(&mut db, CONTRACT, reverting());
+        let mut evm = test_evm(db);
+        assert_eq!(contract_version(&mut evm, CONTRACT).unwrap(), 1);
+        // A reverting `allowedTxTypes` allows nothing.
+        let transfer = tx_to(TxKind::Call(TARGET));
+        assert_eq!(
+            allowed_tx_types(&mut evm, CONTRACT, 1, SENDER, &transfer).unwrap(),
+            AllowedTxTypes {
+                mask: 0,
+                cacheable: false
+            }
+        );
+
+        // v1 answers always hold for the sender.
+        let mut evm = test_evm(contract_answering(TxPermission::Basic as u8));
+        assert_eq!(
+            allowed_tx_types(&mut evm, CONTRACT, 1, SENDER, &transfer).unwrap(),
+            AllowedTxTypes {
+                mask: TxPermission::Basic as u32,
+                cacheable: true
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_versions() {
+        let mut evm = test_evm(contract_answering(5));
+        assert!(matches!(
+            contract_version(&mut evm, CONTRACT),
+            Err(TxPermissionError::UnsupportedVersion { version: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn collects_denied_transactions_by_hash() {
+        let mut evm = test_evm(contract_answering(TxPermission::Basic as u8));
+        let (transfer, create) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let denied = denied_transactions(
+            &mut evm,
+            CONTRACT,
+            [
+                (transfer, SENDER, &tx_to(TxKind::Call(TARGET))),
+                (create, SENDER, &tx_to(TxKind::Create)),
+            ],
+        )
+        .unwrap();
+        assert_eq!(denied.len(), 1);
+        assert_eq!(denied[&create].kind, TxPermission::Create);
+    }
+
+    #[test]
+    fn encodes_each_version() {
+        let sender = address!("0x1000000000000000000000000000000000000001");
+        let tx = TxLegacy {
+            to: TxKind::Call(address!("0x2000000000000000000000000000000000000002")),
+            gas_price: 7,
+            gas_limit: 21_000,
+            ..Default::default()
+        };
+        let selectors: Vec<[u8; 4]> = (1..=MAX_CONTRACT_VERSION)
+            .map(|version| {
+                allowed_tx_types_call(version, sender, &tx)[..4]
+                    .try_into()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(
+            selectors,
+            [
+                abi::v1::allowedTxTypesCall::SELECTOR,
+                abi::v2::allowedTxTypesCall::SELECTOR,
+                abi::v3::allowedTxTypesCall::SELECTOR,
+                abi::v4::allowedTxTypesCall::SELECTOR,
+            ]
+        );
+        let v2 = abi::v2::allowedTxTypesCall::abi_decode(&allowed_tx_types_call(2, sender, &tx))
+            .unwrap();
+        assert_eq!(v2.sender, sender);
+        assert_eq!(
+            v2.to,
+            address!("0x2000000000000000000000000000000000000002")
+        );
+    }
+}
@@ -8,9 +8,10 @@ use std::collections::BTreeMap;
 /// `reportBenign()` on observed misbehavior). Reporting is a *block-producer*
 /// concern — a sync-only client doesn't originate reports; historical reports
 /// are baked into the chain as ordinary transactions and replay automatically.
-/// reth_gnosis is sync-only for AuRa (the chains are post-merge and will never
-/// produce new AuRa blocks), so we collapse both into one variant. The JSON
-/// parser still accepts both `safeContract` and `contract` field names.
+/// reth_gnosis only authors AuRa blocks on local devnets
+/// ([`crate::aura::author`]) and never reports misbehavior, so we collapse
+/// both into one variant. The JSON parser still accepts both `safeContract`
+/// and `contract` field names.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ValidatorSetKind {
     /// Static list of validators.
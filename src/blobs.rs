@@ -1,5 +1,6 @@
 use alloy_eips::eip7840::{BlobParams, BLOB_BASE_COST};
 use alloy_eips::BlobScheduleBlobParams;
+use alloy_genesis::Genesis;
 
 pub static CANCUN_BLOB_PARAMS: BlobParams = BlobParams {
     target_blob_count: 1,
@@ -36,3 +37,139 @@ pub fn gnosis_blob_schedule() -> BlobScheduleBlobParams {
         scheduled: vec![],
     }
 }
+
+/// Max blobs per transaction from Osaka on (EIP-7594).
+const MAX_BLOBS_PER_TX_OSAKA: u64 = 6;
+
+/// The most blob-parameter-only forks a chain spec may schedule, one per
+/// `Bpo1`..`Bpo5` hardfork.
+pub const MAX_BPO_FORKS: usize = 5;
+
+/// Errors raised while parsing the genesis `blobSchedule`.
+#[derive(Debug, thiserror::Error)]
+pub enum BlobScheduleError {
+    #[error("`{entry}`: target {target} exceeds max {max}")]
+    TargetExceedsMax {
+        entry: String,
+        target: u64,
+        max: u64,
+    },
+    #[error("{0} blob-parameter-only forks scheduled, at most {MAX_BPO_FORKS} are supported")]
+    TooManyBpoForks(usize),
+}
+
+/// Blob schedule of `genesis`: the built-in Gnosis parameters, overridden by
+/// the `config.blobSchedule` entries for `cancun`, `prague` and `osaka`, plus
+/// blob-parameter-only (BPO) forks keyed by activation timestamp.
+///
+/// ```json
+/// "blobSchedule": {
+///     "cancun": { "target": 1, "max": 2, "baseFeeUpdateFraction": 1112826 },
+///     "1780000000": { "target": 2, "max": 4, "baseFeeUpdateFraction": 1112826 }
+/// }
+/// ```
+///
+/// Genesis only carries target, max and update fraction; the minimum blob fee
+/// and blob base cost are kept from the fork the entry builds on. Other keys
+/// are ignored.
+pub fn genesis_blob_schedule(
+    genesis: &Genesis,
+) -> Result<BlobScheduleBlobParams, BlobScheduleError> {
+    let mut schedule = gnosis_blob_schedule();
+
+    for (entry, params) in &genesis.config.blob_schedule {
+        if params.target_blob_count > params.max_blob_count {
+            return Err(BlobScheduleError::TargetExceedsMax {
+                entry: entry.clone(),
+                target: params.target_blob_count,
+                max: params.max_blob_count,
+            });
+        }
+        match entry.as_str() {
+            "cancun" => schedule.cancun = with_counts(&schedule.cancun, params, false),
+            "prague" => schedule.prague = with_counts(&schedule.prague, params, false),
+            "osaka" => schedule.osaka = with_counts(&schedule.osaka, params, true),
+            _ => {
+                if let Ok(timestamp) = entry.parse::<u64>() {
+                    schedule
+                        .scheduled
+                        .push((timestamp, with_counts(&OSAKA_BLOB_PARAMS, params, true)));
+                }
+            }
+        }
+    }
+
+    if schedule.scheduled.len() > MAX_BPO_FORKS {
+        return Err(BlobScheduleError::TooManyBpoForks(schedule.scheduled.len()));
+    }
+    // Map keys sort as strings; activations must be ascending.
+    schedule.scheduled.sort_by_key(|(timestamp, _)| *timestamp);
+
+    Ok(schedule)
+}
+
+/// `base` with the blob counts and update fraction of the genesis `entry`.
+fn with_counts(base: &BlobParams, entry: &BlobParams, osaka: bool) -> BlobParams {
+    let max_blobs_per_tx = if osaka {
+        entry.max_blob_count.min(MAX_BLOBS_PER_TX_OSAKA)
+    } else {
+        entry.max_blob_count
+    };
+    BlobParams {
+        target_blob_count: entry.target_blob_count,
+        max_blob_count: entry.max_blob_count,
+        update_fraction: entry.update_fraction,
+        max_blobs_per_tx,
+        ..*base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::chains::{CHIADO_GENESIS, GNOSIS_GENESIS};
+    use serde_json::json;
+
+    #[test]
+    fn builtin_chain_specs_keep_constants() {
+        for genesis in [&*GNOSIS_GENESIS, &*CHIADO_GENESIS] {
+            let schedule = genesis_blob_schedule(genesis).unwrap();
+            assert_eq!(schedule.cancun, CANCUN_BLOB_PARAMS);
+            assert_eq!(schedule.prague, PRAGUE_BLOB_PARAMS);
+            assert_eq!(schedule.osaka, OSAKA_BLOB_PARAMS);
+            assert!(schedule.scheduled.is_empty());
+        }
+    }
+
+    #[test]
+    fn parses_bpo_forks_keyed_by_timestamp() {
+        let mut genesis = GNOSIS_GENESIS.clone();
+        genesis.config.blob_schedule = serde_json::from_value(json!({
+            "osaka": { "target": 1, "max": 2, "baseFeeUpdateFraction": 1112826 },
+            "1900000000": { "target": 4, "max": 8, "baseFeeUpdateFraction": 3338477 },
+            "1800000000": { "target": 2, "max": 4, "baseFeeUpdateFraction": 2225652 }
+        }))
+        .unwrap();
+
+        let schedule = genesis_blob_schedule(&genesis).unwrap();
+        assert_eq!(schedule.osaka, OSAKA_BLOB_PARAMS);
+        let timestamps: Vec<_> = schedule.scheduled.iter().map(|(ts, _)| *ts).collect();
+        assert_eq!(timestamps, vec![1_800_000_000, 1_900_000_000]);
+
+        let bpo2 = &schedule.scheduled[1].1;
+        assert_eq!((bpo2.target_blob_count, bpo2.max_blob_count), (4, 8));
+        assert_eq!(bpo2.max_blobs_per_tx, MAX_BLOBS_PER_TX_OSAKA);
+        assert_eq!(bpo2.min_blob_fee, OSAKA_BLOB_PARAMS.min_blob_fee);
+        assert_eq!(bpo2.blob_base_cost, BLOB_BASE_COST);
+    }
+
+    #[test]
+    fn rejects_target_above_max() {
+        let mut genesis = GNOSIS_GENESIS.clone();
+        genesis.config.blob_schedule = serde_json::from_value(json!({
+            "1800000000": { "target": 5, "max": 4, "baseFeeUpdateFraction": 2225652 }
+        }))
+        .unwrap();
+        assert!(genesis_blob_schedule(&genesis).is_err());
+    }
+}
@@ -1,19 +1,21 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap};
 
-use alloy_consensus::{Transaction, TransactionEnvelope, TxReceipt};
+use alloy_consensus::{
+    transaction::SignerRecoverable, Transaction, TransactionEnvelope, TxReceipt,
+};
 use alloy_eips::eip4895::Withdrawals;
 use alloy_eips::eip7002::WITHDRAWAL_REQUEST_TYPE;
 use alloy_eips::eip7251;
-use alloy_eips::{eip7685::Requests, Encodable2718};
+use alloy_eips::{eip7685::Requests, Decodable2718, Encodable2718};
 use alloy_evm::block::{ExecutableTx, GasOutput, StateDB};
 use alloy_evm::eth::EthTxResult;
-use alloy_evm::Evm;
 use alloy_evm::{
     block::state_changes::balance_increment_state,
     eth::eip6110::{self, parse_deposits_from_receipts},
     FromTxWithEncoded,
 };
-use alloy_primitives::B256;
+use alloy_evm::{Evm, EvmEnv};
+use alloy_primitives::{Bytes, B256};
 use reth_chainspec::EthereumHardforks;
 use reth_errors::{BlockExecutionError, BlockValidationError};
 use reth_evm::execute::InternalBlockExecutionError;
@@ -34,9 +36,11 @@ use revm::{context::result::ResultAndState, DatabaseCommit, Inspector};
 use revm_database::DatabaseCommitExt;
 use revm_primitives::{Address, Log};
 
+use crate::aura::tx_permission::{denied_transactions, TxPermissionDenied, TxPermissionError};
 use crate::evm::factory::GnosisEvmFactory;
 use crate::gnosis::{apply_post_block_system_calls, rewrite_bytecodes};
-use crate::spec::gnosis_spec::{GnosisChainSpec, GnosisHardForks};
+use crate::primitives::block::TransactionSigned;
+use crate::spec::gnosis_spec::GnosisChainSpec;
 
 /// Per-block context for AuRa-execution-mode blocks (pre-merge blocks of an
 /// AuRa chain). `Some(...)` exactly when the block is in pre-merge AuRa mode;
@@ -53,12 +57,35 @@ pub struct AuraExecutionCtx {
     /// address and feed the rolling-finality tracker.
     pub validator_contract: Option<Address>,
     /// Rolling finality tracker for `InitiateChange` finalization (POSDAO).
+    /// A private working copy of the parent block's state — never shared
+    /// with other blocks; `finish` records the result under `block_hash`.
     pub rolling_finality: std::sync::Arc<std::sync::Mutex<crate::aura::finality::RollingFinality>>,
+    /// The parent's tracker state was neither cached, persisted nor
+    /// recoverable; `rolling_finality` is then an empty placeholder and the
+    /// block is refused.
+    pub missing_parent_finality: bool,
+    /// Hash of the block being executed. The post-block tracker state is
+    /// recorded in `finality_cache` under this hash. `None` for payload
+    /// building, where the hash is not known until the block is sealed.
+    pub block_hash: Option<B256>,
+    /// Post-block tracker states keyed by block hash. `None` when the block
+    /// is replayed without recording its state (tracing).
+    pub finality_cache: Option<std::sync::Arc<crate::aura::finality_cache::FinalityCache>>,
     /// POSDAO activation block number. Rolling finality is only consulted
     /// when `block_num >= posdao_transition`. Before POSDAO, `InitiateChange`
     /// events use immediate finalization at `N+1`. Required (no Option,
     /// no sentinel — lifted from the chain spec's `aura.posdaoTransition`).
     pub posdao_transition: u64,
+    /// Contract whose `blockGasLimit()` dictates this block's gas limit, when
+    /// the block is in a `blockGasLimitContractTransitions` range.
+    pub block_gas_limit_contract: Option<Address>,
+    /// Gas limit of the parent block, for the ramp-rule fallback when the
+    /// gas limit contract returns no limit. `None` if the parent header could
+    /// not be looked up (CLI tools), which skips the fallback.
+    pub parent_gas_limit: Option<u64>,
+    /// Fixed `blockReward` (wei) credited to the block author, for chains
+    /// without a reward contract at this height.
+    pub fixed_block_reward: Option<u128>,
     /// AuRa pre-merge bytecode rewrites to apply at this exact block, if any.
     pub aura_bytecode_rewrites:
         Option<std::collections::BTreeMap<Address, alloy_primitives::Bytes>>,
@@ -85,6 +112,65 @@ pub struct GnosisBlockExecutionCtx<'a> {
     /// `aura`) because Gnosis post-merge still uses the POSDAO reward
     /// contract — the override applies pre- AND post-merge for AuRa chains.
     pub block_rewards_override: Option<Address>,
+    /// AuRa `transactionPermissionContract` every transaction of the block is
+    /// checked against. Like `block_rewards_override`, applies pre- and
+    /// post-merge.
+    pub tx_permission_contract: Option<Address>,
+    /// Transactions of the block, checked against `tx_permission_contract`
+    /// on the parent state when the executor is created. `None` for payload
+    /// building, where the builder checks each candidate itself.
+    pub transactions: Option<BlockTransactions<'a>>,
+}
+
+/// Transactions of a block known before it executes.
+#[derive(Debug, Clone, Copy)]
+pub enum BlockTransactions<'a> {
+    /// Body of a sealed block.
+    Signed(&'a [TransactionSigned]),
+    /// EIP-2718 encoded transactions of an engine API payload.
+    Encoded(&'a [Bytes]),
+}
+
+impl<'a> BlockTransactions<'a> {
+    /// `(hash, sender, transaction)` of each transaction. Transactions that do
+    /// not decode or recover are left out; the block fails on them anyway.
+    pub fn recovered(
+        self,
+    ) -> impl Iterator<Item = (B256, Address, Cow<'a, TransactionSigned>)> + 'a {
+        let txs: Box<dyn Iterator<Item = Cow<'a, TransactionSigned>> + 'a> = match self {
+            Self::Signed(txs) => Box::new(txs.iter().map(Cow::Borrowed)),
+            Self::Encoded(txs) => Box::new(txs.iter().filter_map(|tx| {
+                TransactionSigned::decode_2718_exact(tx.as_ref())
+                    .ok()
+                    .map(Cow::Owned)
+            })),
+        };
+        txs.filter_map(|tx| Some((*tx.tx_hash(), tx.recover_signer().ok()?, tx)))
+    }
+}
+
+impl GnosisBlockExecutionCtx<'_> {
+    /// Reward contract called in `finish`, given the chain's global
+    /// `blockRewardsContract`. See [`block_reward_contract`].
+    pub fn reward_contract(&self, global: Address) -> Option<Address> {
+        block_reward_contract(self.aura.is_some(), self.block_rewards_override, global)
+    }
+}
+
+/// Reward contract of a block. Pre-merge AuRa blocks (`is_aura`) only call
+/// the `blockRewardContractTransitions` contract of their height; without
+/// one they are rewarded by `blockReward`, if at all, and no contract is
+/// called. Post-merge blocks fall back to the global `blockRewardsContract`.
+pub fn block_reward_contract(
+    is_aura: bool,
+    aura_contract: Option<Address>,
+    global: Address,
+) -> Option<Address> {
+    if is_aura {
+        aura_contract
+    } else {
+        Some(aura_contract.unwrap_or(global))
+    }
 }
 
 // REF: https://github.com/alloy-rs/evm/blob/99d5b552c131e3419448c214e09474bf4f0d1e4b/crates/op-evm/src/block/mod.rs#L42
@@ -114,6 +200,12 @@ pub struct GnosisBlockExecutor<'a, Evm, R: ReceiptBuilder> {
 
     // Gnosis-specific fields
     block_rewards_address: Address,
+    /// Transactions of `ctx.transactions` the permission contract denies on
+    /// the parent state, by hash.
+    tx_permission_denied: HashMap<B256, TxPermissionDenied>,
+    /// Failure to check `ctx.transactions`, reported by
+    /// `apply_pre_execution_changes`.
+    tx_permission_error: Option<TxPermissionError>,
 }
 
 impl<'a, Evm, R> GnosisBlockExecutor<'a, Evm, R>
@@ -138,7 +230,23 @@ where
             spec: spec.clone(),
             receipt_builder,
             block_rewards_address,
+            tx_permission_denied: HashMap::new(),
+            tx_permission_error: None,
+        }
+    }
+
+    /// Sets the verdicts of the permission contract on `ctx.transactions`,
+    /// from [`crate::aura::tx_permission::denied_transactions`] on the parent
+    /// state.
+    pub fn with_tx_permissions(
+        mut self,
+        denied: Result<HashMap<B256, TxPermissionDenied>, TxPermissionError>,
+    ) -> Self {
+        match denied {
+            Ok(denied) => self.tx_permission_denied = denied,
+            Err(err) => self.tx_permission_error = Some(err),
         }
+        self
     }
 }
 
@@ -164,6 +272,76 @@ where
         Ok(result)
     }
 
+    /// Run a system call from `SYSTEM_ADDRESS` to `contract` without
+    /// committing its state diff, for view functions whose result must not
+    /// leave a trace in the state trie.
+    fn system_call_read_only(
+        &mut self,
+        contract: Address,
+        data: alloy_primitives::Bytes,
+    ) -> Result<revm::context::result::ExecutionResult<E::HaltReason>, E::Error> {
+        let revm::context::result::ResultAndState { result, .. } =
+            self.evm
+                .transact_system_call(alloy_eips::eip4788::SYSTEM_ADDRESS, contract, data)?;
+        Ok(result)
+    }
+
+    /// Check the header gas limit against `blockGasLimit()` on the block gas
+    /// limit contract, evaluated on the parent state. A revert or a zero
+    /// result means the contract sets no limit for this block, and the
+    /// parent's limit is kept, as Nethermind's `AuRaContractGasLimitOverride`
+    /// does.
+    fn validate_contract_gas_limit(
+        &mut self,
+        contract: Address,
+        block_num: u64,
+        parent_gas_limit: Option<u64>,
+    ) -> Result<(), BlockExecutionError> {
+        // blockGasLimit() selector = 0x7877a797
+        let block_gas_limit_data = alloy_primitives::Bytes::from_static(&[0x78, 0x77, 0xa7, 0x97]);
+        let result = self
+            .system_call_read_only(contract, block_gas_limit_data)
+            .map_err(|e| {
+                BlockExecutionError::Internal(InternalBlockExecutionError::Other(
+                    format!("AuRa blockGasLimit() syscall failed at block {block_num}: {e}").into(),
+                ))
+            })?;
+        let contract_limit = match result {
+            revm::context::result::ExecutionResult::Success { output, .. } => {
+                let data = output.into_data();
+                (data.len() >= 32)
+                    .then(|| alloy_primitives::U256::from_be_slice(&data[..32]))
+                    .filter(|limit| !limit.is_zero())
+            }
+            other => {
+                tracing::debug!(
+                    target: "reth::gnosis",
+                    block = block_num,
+                    %contract,
+                    ?other,
+                    "AuRa blockGasLimit() did not succeed, keeping the parent gas limit"
+                );
+                None
+            }
+        };
+
+        let gas_limit = self.evm.block().gas_limit();
+        let (expected, source) = match (contract_limit, parent_gas_limit) {
+            (Some(limit), _) => (limit, "blockGasLimit()"),
+            (None, Some(parent_gas_limit)) => {
+                (alloy_primitives::U256::from(parent_gas_limit), "parent")
+            }
+            (None, None) => return Ok(()),
+        };
+        if alloy_primitives::U256::from(gas_limit) != expected {
+            return Err(BlockExecutionError::msg(format!(
+                "AuRa block gas limit mismatch at block {block_num}: \
+                 header={gas_limit}, {source}={expected}"
+            )));
+        }
+        Ok(())
+    }
+
     /// Call `getValidators()` on the validator contract, commit the result,
     /// decode the returned address list, and seed the rolling-finality tracker.
     ///
@@ -232,7 +410,7 @@ where
 /// each address occupies 32 bytes (zero-padded high 12 bytes, address in low 20).
 /// Note: only the low 8 bytes of each 32-byte word are read; offsets/lengths
 /// above 2^64 will silently truncate (acceptable for realistic getValidators data).
-fn decode_address_array(data: &[u8]) -> Result<Vec<Address>, ()> {
+pub(crate) fn decode_address_array(data: &[u8]) -> Result<Vec<Address>, ()> {
     if data.len() < 64 {
         return Err(());
     }
@@ -270,14 +448,32 @@ where
     fn apply_pre_execution_changes(&mut self) -> Result<(), BlockExecutionError> {
         let block_num = self.evm.block().number().to::<u64>();
 
+        if let Some(err) = self.tx_permission_error.take() {
+            return Err(BlockExecutionError::msg(format!(
+                "transaction permission contract at block {block_num}: {err}"
+            )));
+        }
+
         // AuRa-execution-mode block (= pre-merge block of an AuRa chain).
         // For post-merge blocks and non-AuRa chains (including chains that
         // are post-merge from genesis), `self.ctx.aura` is None and this
         // entire branch is skipped — execution falls through to the standard
         // blockhashes / beacon-root system calls below.
         if let Some(aura) = self.ctx.aura.clone() {
+            if aura.missing_parent_finality {
+                return Err(BlockExecutionError::msg(format!(
+                    "AuRa rolling-finality state after parent {} of block {block_num} is unknown",
+                    self.ctx.parent_hash
+                )));
+            }
             let is_posdao = block_num >= aura.posdao_transition;
 
+            // Gas limit contract: read on the parent state, before any of
+            // this block's system calls touch it.
+            if let Some(contract) = aura.block_gas_limit_contract {
+                self.validate_contract_gas_limit(contract, block_num, aura.parent_gas_limit)?;
+            }
+
             // Initialize validator set via getValidators() if empty.
             // The transact_system_call result IS committed — it's a view function so
             // the only state changes are cache entries (nonce/beneficiary cleaned up
@@ -301,6 +497,16 @@ where
                             "Initialized rolling finality via getValidators()",
                         )?;
                     }
+                    // Nothing of this block has run yet, so this is the set
+                    // in the parent state, which the proposer was chosen from.
+                    aura.rolling_finality
+                        .lock()
+                        .map_err(|_| {
+                            BlockExecutionError::Internal(InternalBlockExecutionError::Other(
+                                "AuRa rolling-finality mutex poisoned".into(),
+                            ))
+                        })?
+                        .record_proposer_set();
                 }
             }
 
@@ -349,19 +555,40 @@ where
             }
         }
 
-        // Balancer-hardfork bytecode rewrites are chain-level (timestamp-based),
-        // not AuRa-specific — applied for both pre- and post-merge blocks.
-        // Only fires at the activation block (active in current, not in parent).
+        // Contract rewrite forks are chain-level, not AuRa-specific — applied
+        // for both pre- and post-merge blocks, only at the activation block
+        // (exact block, or active in current but not in parent for timestamp
+        // forks).
         let current_timestamp: u64 = self.evm.block().timestamp().to();
-        let is_balancer_active_now = self
-            .spec
-            .is_balancer_hardfork_active_at_timestamp(current_timestamp);
-        let was_balancer_active_in_parent = self
-            .spec
-            .is_balancer_hardfork_active_at_timestamp(self.ctx.parent_timestamp);
-        if is_balancer_active_now && !was_balancer_active_in_parent {
-            if let Some(config) = self.spec.balancer_hardfork_config.as_ref() {
-                rewrite_bytecodes(&mut self.evm, config);
+        for fork in
+            self.spec
+                .contract_rewrites_at(block_num, self.ctx.parent_timestamp, current_timestamp)
+        {
+            tracing::info!(
+                target: "reth::gnosis",
+                block = block_num,
+                fork = %fork.hardfork,
+                count = fork.accounts.len(),
+                "Applying contract rewrite fork"
+            );
+            rewrite_bytecodes(&mut self.evm, fork);
+        }
+
+        // AuRa `rewriteBytecodeTimestamp` rewrites use the same parent/child
+        // timestamp edge, so they also fire post-merge, where block numbers
+        // cannot be scheduled ahead of time.
+        if let Some(aura_config) = self.spec.aura_config.as_ref() {
+            for rewrites in aura_config
+                .timestamp_bytecode_rewrites(self.ctx.parent_timestamp, current_timestamp)
+            {
+                tracing::info!(
+                    target: "reth::gnosis",
+                    block = block_num,
+                    timestamp = current_timestamp,
+                    count = rewrites.len(),
+                    "Applying AuRa timestamp bytecode rewrites"
+                );
+                crate::gnosis::rewrite_aura_bytecodes(&mut self.evm, rewrites);
             }
         }
 
@@ -393,6 +620,19 @@ where
             );
         }
 
+        // AuRa transaction permission contract, answered on the parent state
+        // before the block started.
+        if !self.tx_permission_denied.is_empty() {
+            let hash = tx.tx().trie_hash();
+            if let Some(denied) = self.tx_permission_denied.get(&hash) {
+                return Err(BlockValidationError::InvalidTx {
+                    hash,
+                    error: Box::new(denied.clone()),
+                }
+                .into());
+            }
+        }
+
         // Execute transaction and return the result
         let result = self.evm.transact(tx_env).map_err(|err| {
             let hash = tx.tx().trie_hash();
@@ -448,10 +688,11 @@ where
     fn finish(
         mut self,
     ) -> Result<(Self::Evm, BlockExecutionResult<R::Receipt>), BlockExecutionError> {
-        let deposit_contract = self.spec.deposit_contract_address();
-        let deposit_contract = deposit_contract.unwrap_or_else(|| {
-            panic!("Deposit contract address is not set in the chain specification");
-        });
+        let deposit_contract = self.spec.deposit_contract_address().ok_or_else(|| {
+            BlockExecutionError::msg(
+                "Deposit contract address is not set in the chain specification",
+            )
+        })?;
         let timestamp = self.evm.block().timestamp();
         let withdrawals = self.ctx.withdrawals.as_deref();
         let beneficiary = self.evm.block().beneficiary();
@@ -492,14 +733,10 @@ where
         };
 
         // Gnosis-specific // Start
-        // Use the AuRa-specific reward contract if available, otherwise fall back to default.
-        // `block_rewards_override` lives at top level (not under `aura`) because Gnosis
-        // post-merge still uses POSDAO reward contracts — applies in both phases.
-        let reward_address = self
-            .ctx
-            .block_rewards_override
-            .unwrap_or(self.block_rewards_address);
-        let (balance_increments, _, reward_logs) = apply_post_block_system_calls(
+        // The AuRa reward contract and the fixed `blockReward` are exclusive
+        // per height; post-merge the global contract is the fallback.
+        let reward_address = self.ctx.reward_contract(self.block_rewards_address);
+        let (mut balance_increments, _, reward_logs) = apply_post_block_system_calls(
             &self.spec,
             reward_address,
             deposit_contract,
@@ -510,6 +747,12 @@ where
             &mut self.system_caller,
         )?;
 
+        // Fixed-amount AuRa rewards for chains without a reward contract at
+        // this height: the author is credited like a contract reward.
+        if let Some(reward) = self.ctx.aura.as_ref().and_then(|a| a.fixed_block_reward) {
+            *balance_increments.entry(beneficiary).or_default() += reward;
+        }
+
         // AuRa-execution-mode-only post-block work: InitiateChange detection +
         // signer push into rolling finality. Skipped for post-merge blocks and
         // non-AuRa chains.
@@ -571,6 +814,18 @@ where
                     rf.push(block_num, signer);
                 }
             }
+
+            // Record the post-block state under this block's hash so its
+            // children (and only its children) start from it. Persisting it
+            // is left to `persist_committed_finality`, once the block is
+            // committed.
+            if let (Some(block_hash), Some(finality_cache)) =
+                (aura.block_hash, aura.finality_cache.as_ref())
+            {
+                if let Ok(state) = aura.rolling_finality.lock() {
+                    finality_cache.insert(block_hash, state.clone());
+                }
+            }
         }
 
         // Gnosis-specific // End
@@ -695,21 +950,57 @@ where
         DB: StateDB,
         I: Inspector<EvmF::Context<DB>>,
     {
-        GnosisBlockExecutor::new(
+        // Nothing has executed yet, so `evm` is on the parent state. The
+        // permission calls run on a separate, uninspected EVM to keep them out
+        // of traces.
+        let mut evm = evm;
+        let tx_permissions = match (ctx.tx_permission_contract, ctx.transactions) {
+            (Some(contract), Some(transactions)) => {
+                let env = EvmEnv {
+                    cfg_env: evm.cfg_env().clone(),
+                    block_env: evm.block().clone(),
+                };
+                let mut checker = self.evm_factory.create_evm(evm.db_mut(), env);
+                Some(denied_transactions(
+                    &mut checker,
+                    contract,
+                    transactions.recovered(),
+                ))
+            }
+            _ => None,
+        };
+
+        let executor = GnosisBlockExecutor::new(
             evm,
             ctx,
             &self.spec,
             &self.receipt_builder,
             self.block_rewards_address,
-        )
+        );
+        match tx_permissions {
+            Some(denied) => executor.with_tx_permissions(denied),
+            None => executor,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::decode_address_array;
+    use super::{block_reward_contract, decode_address_array};
     use alloy_primitives::Address;
 
+    #[test]
+    fn aura_blocks_only_call_their_reward_contract() {
+        let aura = Address::repeat_byte(0xaa);
+        let global = Address::repeat_byte(0x99);
+        assert_eq!(block_reward_contract(true, Some(aura), global), Some(aura));
+        // No AuRa reward contract at this height: `blockReward` applies and
+        // the global contract is not called.
+        assert_eq!(block_reward_contract(true, None, global), None);
+        assert_eq!(block_reward_contract(false, Some(aura), global), Some(aura));
+        assert_eq!(block_reward_contract(false, None, global), Some(global));
+    }
+
     /// Build an ABI-encoded `address[]` payload for the given addresses.
     fn encode(addresses: &[Address]) -> Vec<u8> {
         let mut out = Vec::with_capacity(64 + addresses.len() * 32);
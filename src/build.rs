@@ -16,7 +16,7 @@ use reth_provider::BlockExecutionResult;
 use revm::context::Block;
 
 use crate::{
-    block::GnosisBlockExecutionCtx, primitives::block::GnosisBlock,
+    aura::author::AuraAuthor, block::GnosisBlockExecutionCtx, primitives::block::GnosisBlock,
     version::default_gnosis_extra_data_bytes,
 };
 
@@ -26,6 +26,9 @@ pub struct GnosisBlockAssembler<ChainSpec> {
     chain_spec: Arc<ChainSpec>,
     /// Extra data to use for the blocks.
     pub extra_data: Bytes,
+    /// AuRa signer for pre-merge blocks. Unset unless the node authors
+    /// blocks on an AuRa devnet (`--gnosis.aura-signer`).
+    pub aura_author: Option<Arc<AuraAuthor>>,
 }
 
 impl<ChainSpec> GnosisBlockAssembler<ChainSpec> {
@@ -34,6 +37,7 @@ impl<ChainSpec> GnosisBlockAssembler<ChainSpec> {
         Self {
             chain_spec,
             extra_data: default_gnosis_extra_data_bytes(),
+            aura_author: None,
         }
     }
 }
@@ -43,6 +47,7 @@ impl<ChainSpec> Clone for GnosisBlockAssembler<ChainSpec> {
         Self {
             chain_spec: self.chain_spec.clone(),
             extra_data: self.extra_data.clone(),
+            aura_author: self.aura_author.clone(),
         }
     }
 }
@@ -80,6 +85,7 @@ where
         } = input;
 
         let timestamp = evm_env.block_env.timestamp().saturating_to();
+        let number = evm_env.block_env.number().saturating_to();
 
         let transactions_root = proofs::calculate_transaction_root(&transactions);
         let receipts_root = Receipt::calculate_receipt_root_no_memo(receipts);
@@ -114,10 +120,9 @@ where
             } else {
                 // for the first post-fork block, both parent.blob_gas_used and
                 // parent.excess_blob_gas are evaluated as 0
-                Some(
-                    alloy_eips::eip7840::BlobParams::cancun()
-                        .next_block_excess_blob_gas_osaka(0, 0, 0),
-                )
+                self.chain_spec
+                    .blob_params_at_timestamp(timestamp)
+                    .map(|params| params.next_block_excess_blob_gas_osaka(0, 0, 0))
             };
         }
 
@@ -133,8 +138,11 @@ where
             timestamp,
             mix_hash: evm_env.block_env.prevrandao().unwrap_or_default(),
             nonce: BEACON_NONCE.into(),
-            base_fee_per_gas: Some(evm_env.block_env.basefee()),
-            number: evm_env.block_env.number().saturating_to(),
+            base_fee_per_gas: self
+                .chain_spec
+                .is_london_active_at_block(number)
+                .then(|| evm_env.block_env.basefee()),
+            number,
             gas_limit: evm_env.block_env.gas_limit(),
             difficulty: evm_env.block_env.difficulty(),
             gas_used: *gas_used,
@@ -147,8 +155,28 @@ where
             slot_number: None,
         };
 
+        let mut header: GnosisHeader = header.into();
+        // Pre-merge AuRa block: replace the beacon-style fields with the AuRa
+        // step, difficulty and seal.
+        if let Some(aura) = &ctx.aura {
+            let author = self.aura_author.as_ref().ok_or_else(|| {
+                BlockExecutionError::msg("building a pre-merge AuRa block requires an AuRa signer")
+            })?;
+            author
+                .seal_header(&mut header, parent.header())
+                .map_err(|err| BlockExecutionError::msg(err.to_string()))?;
+            // Built payloads are inserted into the engine tree without being
+            // executed again, so record the block's finality state under its
+            // sealed hash here; the next authored block starts from it.
+            if let (Some(finality_cache), Ok(state)) =
+                (aura.finality_cache.as_ref(), aura.rolling_finality.lock())
+            {
+                finality_cache.insert(header.hash_slow(), state.clone());
+            }
+        }
+
         Ok(GnosisBlock {
-            header: header.into(),
+            header,
             body: BlockBody {
                 transactions,
                 ommers: Default::default(),
@@ -0,0 +1,79 @@
+//! `reth gnosis` subcommands.
+
+use std::{fs, path::PathBuf};
+
+use alloy_genesis::Genesis;
+use clap::{Parser, Subcommand};
+use serde_json::Value;
+
+use crate::spec::{
+    check::check_genesis,
+    nethermind::{is_nethermind_chain_spec, nethermind_to_genesis},
+};
+
+/// Gnosis-specific utilities.
+#[derive(Debug, Parser)]
+pub struct GnosisCommand {
+    #[command(subcommand)]
+    command: GnosisSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum GnosisSubcommands {
+    /// Chain specification utilities.
+    #[command(name = "chainspec", subcommand)]
+    ChainSpec(ChainSpecSubcommands),
+}
+
+#[derive(Debug, Subcommand)]
+enum ChainSpecSubcommands {
+    /// Validate the Gnosis-specific fields of a chain spec file, reporting
+    /// every problem found.
+    #[command(name = "check")]
+    Check(CheckCommand),
+}
+
+/// `reth gnosis chainspec check <FILE>`.
+#[derive(Debug, Parser)]
+pub struct CheckCommand {
+    /// Path to the chain spec (genesis or Nethermind JSON) file.
+    #[arg(value_name = "FILE")]
+    path: PathBuf,
+}
+
+impl GnosisCommand {
+    /// Execute `gnosis` command
+    pub fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            GnosisSubcommands::ChainSpec(ChainSpecSubcommands::Check(command)) => command.execute(),
+        }
+    }
+}
+
+impl CheckCommand {
+    /// Execute `gnosis chainspec check` command
+    pub fn execute(self) -> eyre::Result<()> {
+        let path = self.path.display();
+        let raw = fs::read_to_string(&self.path)
+            .map_err(|e| eyre::eyre!("failed to read {path}: {e}"))?;
+        let value: Value =
+            serde_json::from_str(&raw).map_err(|e| eyre::eyre!("{path} is not JSON: {e}"))?;
+        let genesis: Genesis = if is_nethermind_chain_spec(&value) {
+            nethermind_to_genesis(&value)
+                .map_err(|e| eyre::eyre!("{path} is not a valid Nethermind chain spec: {e}"))?
+        } else {
+            serde_json::from_value(value)
+                .map_err(|e| eyre::eyre!("{path} is not a genesis file: {e}"))?
+        };
+
+        let problems = check_genesis(&genesis);
+        if problems.is_empty() {
+            println!("{path}: ok");
+            return Ok(());
+        }
+        for problem in &problems {
+            println!("{path}: {problem}");
+        }
+        eyre::bail!("{} problem(s) found in {path}", problems.len())
+    }
+}
@@ -1,14 +1,16 @@
-use std::{ffi::OsString, fmt, future::Future, sync::Arc};
+use std::{ffi::OsString, fmt, future::Future, path::PathBuf, sync::Arc};
 
-use crate::cli::import_era;
-use clap::{value_parser, Parser, Subcommand};
+use crate::cli::{chainspec, import_era, re_execute};
+use clap::{value_parser, CommandFactory, Parser, Subcommand};
 use reth::{
-    args::LogArgs,
+    args::{DatadirArgs, LogArgs},
     builder::{NodeBuilder, WithLaunchContext},
+    dirs::{DataDirPath, MaybePlatformPath},
     prometheus_exporter::install_prometheus_recorder,
     version::version_metadata,
     CliRunner,
 };
+use reth_chainspec::EthChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     common::CliComponentsBuilder,
@@ -17,7 +19,7 @@ use reth_cli_commands::{
     dump_genesis, export_era, import, init_cmd, init_state,
     launcher::FnLauncher,
     node::{self, NoArgs},
-    p2p, prune, re_execute, stage,
+    p2p, prune, stage,
 };
 use reth_consensus::FullConsensus;
 use reth_db::DatabaseEnv;
@@ -25,7 +27,7 @@ use reth_tracing::FileWorkerGuard;
 use tracing::info;
 
 use crate::{
-    aura::GnosisConsensus,
+    aura::{persistence::FinalityStore, GnosisConsensus},
     evm_config::{GnosisEvmConfig, NoopHeaderLookup},
     primitives::GnosisNodePrimitives,
     spec::gnosis_spec::{GnosisChainSpec, GnosisChainSpecParser},
@@ -133,11 +135,19 @@ where
         Fut: Future<Output = eyre::Result<()>>,
         C: ChainSpecParser<ChainSpec = GnosisChainSpec>,
     {
+        // `stage` and `import` continue from the Execution stage checkpoint,
+        // whose finality state the node persisted in the datadir. Without a
+        // provider there is no receipt replay for other parents; `re-execute`
+        // is our own command and wires it.
         let components = |spec: Arc<C::ChainSpec>| {
+            let data_dir = Self::command_data_dir(&spec);
+            let evm_config = GnosisEvmConfig::new(spec.clone(), NoopHeaderLookup)
+                .with_finality_store(FinalityStore::new(&data_dir));
+            let consensus =
+                GnosisConsensus::new(spec).with_finality_cache(evm_config.finality_cache.clone());
             (
-                GnosisEvmConfig::new(spec.clone(), NoopHeaderLookup),
-                Arc::new(GnosisConsensus::new(spec))
-                    as Arc<dyn FullConsensus<GnosisNodePrimitives>>,
+                evm_config,
+                Arc::new(consensus) as Arc<dyn FullConsensus<GnosisNodePrimitives>>,
             )
         };
 
@@ -210,9 +220,34 @@ where
                 runner.run_blocking_until_ctrl_c(command.execute::<GnosisNode>(rt))
             }
             Commands::ReExecute(command) => {
-                runner.run_until_ctrl_c(command.execute::<GnosisNode>(components, rt))
+                runner.run_blocking_until_ctrl_c(command.execute::<GnosisNode>(rt))
+            }
+            Commands::Gnosis(command) => command.execute(),
+        }
+    }
+
+    /// Data directory of the command being run. reth's commands keep their
+    /// `--datadir` to themselves and only hand the chain spec to the
+    /// [`CliComponentsBuilder`], so it is read back from the arguments.
+    fn command_data_dir(spec: &GnosisChainSpec) -> PathBuf {
+        let mut datadir = None;
+        if let Ok(matches) = Self::command().try_get_matches() {
+            let mut matches = &matches;
+            while let Some((_, sub)) = matches.subcommand() {
+                if let Ok(Some(dir)) = sub.try_get_one::<MaybePlatformPath<DataDirPath>>("datadir")
+                {
+                    datadir = Some(dir.clone());
+                }
+                matches = sub;
             }
         }
+        DatadirArgs {
+            datadir: datadir.unwrap_or_default(),
+            ..Default::default()
+        }
+        .resolve_datadir(spec.chain())
+        .data_dir()
+        .to_path_buf()
     }
 
     /// Initializes tracing with the configured options.
@@ -276,9 +311,12 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<C>),
-    /// Re-execute blocks in parallel to verify historical sync correctness.
+    /// Re-execute blocks to verify historical sync correctness.
     #[command(name = "re-execute")]
-    ReExecute(re_execute::Command<C>),
+    ReExecute(re_execute::ReExecuteCommand<C>),
+    /// Gnosis-specific utilities
+    #[command(name = "gnosis")]
+    Gnosis(chainspec::GnosisCommand),
 }
 
 impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
@@ -300,6 +338,7 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
             Self::Config(_) => None,
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::Gnosis(_) => None,
         }
     }
 }
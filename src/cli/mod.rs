@@ -1,3 +1,5 @@
+pub mod chainspec;
 pub mod era;
 pub mod gnosis_cli;
 pub mod import_era;
+pub mod re_execute;
@@ -0,0 +1,116 @@
+// Replaces reth's `re-execute` command (reth_cli_commands::re_execute).
+// reth hands its components builder only the chain spec, but re-executing a
+// pre-merge block needs the database: the AuRa rolling-finality state of the
+// first block's parent is recovered from persisted snapshots and canonical
+// receipts, like the node does on startup.
+
+//! Command that re-executes blocks from the database and checks the results
+//! against the stored receipts.
+use clap::Parser;
+use reth::version::version_metadata;
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_primitives_traits::BlockHeader;
+use reth_provider::{
+    BlockNumReader, BlockReader, ReceiptProvider, StateProviderFactory, TransactionVariant,
+};
+use reth_revm::database::StateProviderDatabase;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::{
+    aura::{persistence::FinalityStore, recovery::ProviderChainScanner},
+    evm_config::GnosisEvmConfig,
+    primitives::GnosisNodePrimitives,
+    spec::gnosis_spec::GnosisChainSpec,
+};
+
+/// Number of blocks between progress logs.
+const LOG_INTERVAL: u64 = 10_000;
+
+/// Re-executes a range of blocks on their parent's state and compares gas
+/// used and receipts with the stored ones.
+#[derive(Debug, Parser)]
+pub struct ReExecuteCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The height to start at.
+    #[arg(long, default_value = "1")]
+    from: u64,
+
+    /// The height to end at. Defaults to the latest block.
+    #[arg(long)]
+    to: Option<u64>,
+}
+
+impl<C: ChainSpecParser<ChainSpec = GnosisChainSpec>> ReExecuteCommand<C> {
+    /// Execute `re-execute` command
+    pub async fn execute<N>(self, runtime: reth::tasks::Runtime) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec, Primitives = GnosisNodePrimitives>,
+    {
+        info!(target: "reth::cli", "reth {} starting", version_metadata().short_version.as_ref());
+        eyre::ensure!(self.from > 0, "genesis cannot be re-executed");
+
+        let Environment {
+            provider_factory, ..
+        } = self.env.init::<N>(AccessRights::RO, runtime)?;
+        let data_dir = self
+            .env
+            .datadir
+            .clone()
+            .resolve_datadir(self.env.chain.chain())
+            .data_dir();
+
+        // Same finality sources as `GnosisExecutorBuilder::build_evm`, so the
+        // first block's parent state is recovered instead of missing.
+        let evm_config = GnosisEvmConfig::new(self.env.chain.clone(), provider_factory.clone())
+            .with_finality_store(FinalityStore::new(&data_dir))
+            .with_chain_scanner(ProviderChainScanner::new(provider_factory.clone()));
+
+        let to = match self.to {
+            Some(to) => to,
+            None => provider_factory.best_block_number()?,
+        };
+        for block_number in self.from..=to {
+            let block = provider_factory
+                .recovered_block(block_number.into(), TransactionVariant::NoHash)?
+                .ok_or_else(|| eyre::eyre!("block {block_number} not found"))?;
+            let state = provider_factory.history_by_block_number(block_number - 1)?;
+            let output = evm_config
+                .executor(StateProviderDatabase::new(state))
+                .execute(&block)?;
+
+            let expected_gas = block.header().gas_used();
+            eyre::ensure!(
+                output.result.gas_used == expected_gas,
+                "block {block_number}: re-executed gas used {} differs from stored {expected_gas}",
+                output.result.gas_used
+            );
+            let stored = provider_factory
+                .receipts_by_block(block_number.into())?
+                .unwrap_or_default();
+            eyre::ensure!(
+                output.result.receipts == stored,
+                "block {block_number}: re-executed receipts differ from stored receipts"
+            );
+
+            if block_number % LOG_INTERVAL == 0 {
+                info!(target: "reth::cli", block = block_number, to, "Re-executed blocks");
+            }
+        }
+
+        println!("✅ Re-executed blocks {}..={to}", self.from);
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> ReExecuteCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
@@ -1,48 +1,59 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use alloy_primitives::{Address, Bytes};
-use revm_primitives::{hex::FromHex, KECCAK_EMPTY};
-use revm_state::Bytecode;
+use alloy_primitives::Address;
+use reth_chainspec::ForkCondition;
 use serde_json::{self, Value};
 
-use crate::spec::gnosis_spec::BalancerHardforkConfig;
+use crate::spec::contract_rewrites::{
+    parse_code, AccountRewrite, ContractRewriteError, ContractRewriteFork,
+};
+use crate::spec::gnosis_spec::GnosisHardfork;
 
+/// Parse the legacy `balancerHardforkTime` / `balancerHardforkBytecodes`
+/// genesis keys into the equivalent `BalancerFork` contract rewrite. Returns
+/// `None` unless both keys are present.
+///
+/// Kept so custom genesis files written before `gnosisContractRewrites`
+/// still load.
 pub fn parse_balancer_hardfork_config(
     time_value: Option<&Value>,
     config_value: Option<&Value>,
-) -> Option<BalancerHardforkConfig> {
-    if time_value.is_none() || config_value.is_none() {
-        return None;
+) -> Result<Option<ContractRewriteFork>, ContractRewriteError> {
+    let (Some(time_value), Some(config_value)) = (time_value, config_value) else {
+        return Ok(None);
     };
 
-    let activation_time = serde_json::from_value::<u64>(time_value.unwrap().clone())
-        .expect("Error parsing balancerHardforkTime");
+    let activation_time = serde_json::from_value::<u64>(time_value.clone()).map_err(|source| {
+        ContractRewriteError::Json {
+            key: "balancerHardforkTime",
+            source,
+        }
+    })?;
 
-    let parsed_mapping =
-        serde_json::from_value::<HashMap<Address, String>>(config_value.unwrap().clone())
-            .expect("Error parsing balancerHardforkBytecodes");
+    let parsed_mapping = serde_json::from_value::<HashMap<Address, String>>(config_value.clone())
+        .map_err(|source| ContractRewriteError::Json {
+        key: "balancerHardforkBytecodes",
+        source,
+    })?;
 
-    let config = parsed_mapping
+    let hardfork = GnosisHardfork::BalancerFork;
+    let accounts = parsed_mapping
         .into_iter()
         .map(|(addr, bytecode_str)| {
-            let bytecode = if !bytecode_str.is_empty() {
-                Some(Bytecode::new_legacy(
-                    Bytes::from_hex(bytecode_str)
-                        .unwrap_or_else(|_| panic!("Unable to parse bytecode hex for {addr}")),
-                ))
-            } else {
-                None
-            };
-            let codehash = match &bytecode {
-                Some(code) => code.hash_slow(),
-                None => KECCAK_EMPTY,
-            };
-            (addr, bytecode, codehash)
+            let code = parse_code(hardfork.name(), addr, &bytecode_str)?;
+            Ok((
+                addr,
+                AccountRewrite {
+                    code: Some(code),
+                    ..Default::default()
+                },
+            ))
         })
-        .collect();
+        .collect::<Result<BTreeMap<_, _>, ContractRewriteError>>()?;
 
-    Some(BalancerHardforkConfig {
-        activation_time,
-        config,
-    })
+    Ok(Some(ContractRewriteFork {
+        hardfork,
+        activation: ForkCondition::Timestamp(activation_time),
+        accounts,
+    }))
 }
@@ -17,6 +17,8 @@ use revm_primitives::{hardfork::SpecId, Address, Bytes};
 use revm_primitives::{TxKind, U256};
 use revm_state::{Account, AccountInfo, AccountStatus};
 
+use crate::spec::fee_collector::FeeCollectorSchedule;
+
 // https://github.com/gnosischain/specs/blob/master/execution/withdrawals.md
 const TX_GAS_LIMIT: u64 = 30_000_000;
 
@@ -304,7 +306,18 @@ where
 /// Custom EVM configuration.
 #[derive(Debug, Clone, Default)]
 pub struct GnosisEvmFactory {
-    pub fee_collector_address: Address,
+    /// Resolved to the collector of each EVM's block in `create_evm`.
+    pub fee_collectors: FeeCollectorSchedule,
+}
+
+impl GnosisEvmFactory {
+    /// Fee collector of the block in `block_env`.
+    fn fee_collector(&self, block_env: &BlockEnv) -> Address {
+        self.fee_collectors.at(
+            block_env.number.saturating_to(),
+            block_env.timestamp.saturating_to(),
+        )
+    }
 }
 
 impl EvmFactory for GnosisEvmFactory {
@@ -319,6 +332,7 @@ impl EvmFactory for GnosisEvmFactory {
 
     fn create_evm<DB: Database>(&self, db: DB, input: EvmEnv) -> Self::Evm<DB, NoOpInspector> {
         let spec_id = input.cfg_env.spec;
+        let fee_collector = self.fee_collector(&input.block_env);
         let mut evm = Context::mainnet()
             .with_db(db)
             .with_cfg(input.cfg_env)
@@ -341,7 +355,7 @@ impl EvmFactory for GnosisEvmFactory {
         }
 
         GnosisEvm {
-            inner: super::gnosis_evm::GnosisEvm(evm, self.fee_collector_address),
+            inner: super::gnosis_evm::GnosisEvm(evm, fee_collector),
             inspect: false,
         }
     }
@@ -353,6 +367,7 @@ impl EvmFactory for GnosisEvmFactory {
         inspector: I,
     ) -> Self::Evm<DB, I> {
         let spec_id = input.cfg_env.spec;
+        let fee_collector = self.fee_collector(&input.block_env);
         let mut evm = Context::mainnet()
             .with_db(db)
             .with_cfg(input.cfg_env)
@@ -375,7 +390,7 @@ impl EvmFactory for GnosisEvmFactory {
         }
 
         GnosisEvm {
-            inner: super::gnosis_evm::GnosisEvm(evm, self.fee_collector_address),
+            inner: super::gnosis_evm::GnosisEvm(evm, fee_collector),
             inspect: true,
         }
     }
@@ -24,8 +24,14 @@ use std::{convert::Infallible, sync::Arc};
 
 use std::sync::Mutex;
 
+use crate::aura::finality::RollingFinality;
+use crate::aura::finality_cache::FinalityCache;
+use crate::aura::recovery::ChainScanner;
 use crate::blobs::CANCUN_BLOB_PARAMS;
-use crate::block::{AuraExecutionCtx, GnosisBlockExecutionCtx, GnosisBlockExecutorFactory};
+use crate::block::{
+    block_reward_contract, AuraExecutionCtx, BlockTransactions, GnosisBlockExecutionCtx,
+    GnosisBlockExecutorFactory,
+};
 use crate::build::GnosisBlockAssembler;
 use crate::evm::factory::GnosisEvmFactory;
 use crate::primitives::block::GnosisBlock;
@@ -40,55 +46,59 @@ use crate::spec::gnosis_spec::GnosisChainSpec;
 /// the correct pre-merge spec instead.
 pub fn gnosis_revm_spec(chain_spec: &GnosisChainSpec, header: &GnosisHeader) -> SpecId {
     if header.is_pre_merge() {
-        // For pre-merge headers, compute spec without Paris/TTD consideration
-        let block_number = header.number;
-        if chain_spec.is_london_active_at_block(block_number) {
-            SpecId::LONDON
-        } else if chain_spec.is_berlin_active_at_block(block_number) {
-            SpecId::BERLIN
-        } else if chain_spec
-            .fork(EthereumHardfork::Istanbul)
-            .active_at_block(block_number)
-        {
-            SpecId::ISTANBUL
-        } else if chain_spec
-            .fork(EthereumHardfork::Petersburg)
-            .active_at_block(block_number)
-        {
-            SpecId::PETERSBURG
-        } else if chain_spec
-            .fork(EthereumHardfork::Constantinople)
-            .active_at_block(block_number)
-        {
-            SpecId::CONSTANTINOPLE
-        } else if chain_spec
-            .fork(EthereumHardfork::Byzantium)
-            .active_at_block(block_number)
-        {
-            SpecId::BYZANTIUM
-        } else if chain_spec
-            .fork(EthereumHardfork::SpuriousDragon)
-            .active_at_block(block_number)
-        {
-            SpecId::SPURIOUS_DRAGON
-        } else if chain_spec
-            .fork(EthereumHardfork::Tangerine)
-            .active_at_block(block_number)
-        {
-            SpecId::TANGERINE
-        } else if chain_spec
-            .fork(EthereumHardfork::Homestead)
-            .active_at_block(block_number)
-        {
-            SpecId::HOMESTEAD
-        } else {
-            SpecId::FRONTIER
-        }
+        pre_merge_revm_spec(chain_spec, header.number)
     } else {
         revm_spec(chain_spec, header)
     }
 }
 
+/// revm SpecId for a pre-merge (AuRa) block, computed from block-number forks
+/// only, without Paris/TTD consideration.
+pub fn pre_merge_revm_spec(chain_spec: &GnosisChainSpec, block_number: u64) -> SpecId {
+    if chain_spec.is_london_active_at_block(block_number) {
+        SpecId::LONDON
+    } else if chain_spec.is_berlin_active_at_block(block_number) {
+        SpecId::BERLIN
+    } else if chain_spec
+        .fork(EthereumHardfork::Istanbul)
+        .active_at_block(block_number)
+    {
+        SpecId::ISTANBUL
+    } else if chain_spec
+        .fork(EthereumHardfork::Petersburg)
+        .active_at_block(block_number)
+    {
+        SpecId::PETERSBURG
+    } else if chain_spec
+        .fork(EthereumHardfork::Constantinople)
+        .active_at_block(block_number)
+    {
+        SpecId::CONSTANTINOPLE
+    } else if chain_spec
+        .fork(EthereumHardfork::Byzantium)
+        .active_at_block(block_number)
+    {
+        SpecId::BYZANTIUM
+    } else if chain_spec
+        .fork(EthereumHardfork::SpuriousDragon)
+        .active_at_block(block_number)
+    {
+        SpecId::SPURIOUS_DRAGON
+    } else if chain_spec
+        .fork(EthereumHardfork::Tangerine)
+        .active_at_block(block_number)
+    {
+        SpecId::TANGERINE
+    } else if chain_spec
+        .fork(EthereumHardfork::Homestead)
+        .active_at_block(block_number)
+    {
+        SpecId::HOMESTEAD
+    } else {
+        SpecId::FRONTIER
+    }
+}
+
 /// Returns a configuration environment for the EVM based on the given chain specification and timestamp.
 pub fn get_cfg_env(
     chain_spec: &GnosisChainSpec,
@@ -166,10 +176,19 @@ pub struct GnosisEvmConfig {
     chain_spec: Arc<GnosisChainSpec>,
     /// Header lookup for getting parent block timestamps.
     header_lookup: Arc<dyn HeaderLookup>,
-    /// Rolling finality tracker for AuRa consensus.
-    /// Tracks validator signatures to determine when InitiateChange blocks
-    /// become finalized (>50% unique validators signed).
-    pub rolling_finality: Arc<Mutex<crate::aura::finality::RollingFinality>>,
+    /// Rolling finality states for AuRa consensus, keyed by the hash of the
+    /// block they follow. Each executed block starts from its parent's entry
+    /// and records its own, so unwinds and reorgs never share a tracker.
+    pub finality_cache: Arc<FinalityCache>,
+    /// On-disk snapshots of committed finality states, read on cache misses.
+    /// Unset for CLI tools that must not touch the datadir.
+    finality_store: Option<Arc<crate::aura::persistence::FinalityStore>>,
+    /// Canonical-chain access used to rebuild a parent's finality state that
+    /// is neither cached nor persisted (e.g. after a deep pipeline unwind).
+    chain_scanner: Option<Arc<dyn ChainScanner>>,
+    /// Whether executed blocks record their finality state in
+    /// `finality_cache`. Off for re-execution outside import (tracing).
+    record_finality: bool,
 }
 
 impl Debug for GnosisEvmConfig {
@@ -179,6 +198,10 @@ impl Debug for GnosisEvmConfig {
             .field("block_assembler", &self.block_assembler)
             .field("chain_spec", &self.chain_spec)
             .field("header_lookup", &"<dyn HeaderLookup>")
+            .field("finality_cache", &self.finality_cache)
+            .field("finality_store", &self.finality_store)
+            .field("chain_scanner", &self.chain_scanner)
+            .field("record_finality", &self.record_finality)
             .finish()
     }
 }
@@ -189,43 +212,205 @@ impl GnosisEvmConfig {
         chain_spec: Arc<GnosisChainSpec>,
         header_lookup: impl HeaderLookup + 'static,
     ) -> Self {
-        // Parsing fields MANDATORY for GnosisBlockExecutorFactory
-        let fee_collector_address = chain_spec
-            .genesis()
-            .config
-            .extra_fields
-            .get("eip1559collector")
-            .expect("no eip1559collector field");
-        let fee_collector_address: Address = serde_json::from_value(fee_collector_address.clone())
-            .expect("failed to parse eip1559collector field");
-
-        let block_rewards_address = chain_spec
-            .genesis()
-            .config
-            .extra_fields
-            .get("blockRewardsContract")
-            .expect("no eip1559collector field");
-        let block_rewards_address: Address = serde_json::from_value(block_rewards_address.clone())
-            .expect("failed to parse eip1559collector field");
+        let fee_collectors = chain_spec.fee_collectors.clone();
+        let block_rewards_address = chain_spec.block_rewards_contract;
 
         Self {
             block_assembler: GnosisBlockAssembler::new(chain_spec.clone()),
             executor_factory: GnosisBlockExecutorFactory::new(
                 RethReceiptBuilder::default(),
                 (*chain_spec).clone(),
-                GnosisEvmFactory {
-                    fee_collector_address,
-                },
+                GnosisEvmFactory { fee_collectors },
                 block_rewards_address,
             ),
             chain_spec,
             header_lookup: Arc::new(header_lookup),
-            rolling_finality: Arc::new(Mutex::new(crate::aura::finality::RollingFinality::new(
-                Vec::new(),
-            ))),
+            finality_cache: Arc::new(FinalityCache::default()),
+            finality_store: None,
+            chain_scanner: None,
+            record_finality: true,
         }
     }
 
+    /// Record post-block AuRa finality states in `cache`, shared with the
+    /// consensus for its post-execution proposer check.
+    pub fn with_finality_cache(mut self, cache: Arc<FinalityCache>) -> Self {
+        self.finality_cache = cache;
+        self
+    }
+
+    /// Read persisted AuRa rolling-finality snapshots from `store` when a
+    /// parent state is not cached.
+    pub fn with_finality_store(mut self, store: crate::aura::persistence::FinalityStore) -> Self {
+        self.finality_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Resolve parent finality states as usual but record none: neither
+    /// recovered parent states nor the states executed blocks reach are
+    /// written to the finality cache. For replaying blocks that were already
+    /// imported, e.g. to trace them.
+    pub fn with_read_only_finality(mut self) -> Self {
+        self.record_finality = false;
+        self
+    }
+
+    /// Rebuild unknown parent finality states from canonical receipts via
+    /// `scanner`.
+    pub fn with_chain_scanner(mut self, scanner: impl ChainScanner + 'static) -> Self {
+        self.chain_scanner = Some(Arc::new(scanner));
+        self
+    }
+
+    /// Rolling-finality state after canonical block `block_number`, from the
+    /// persisted snapshots and receipt replay. Requires a chain scanner.
+    pub fn recover_finality(&self, block_number: u64) -> eyre::Result<Option<RollingFinality>> {
+        let (Some(aura_config), Some(scanner)) = (
+            self.chain_spec.aura_config.as_ref(),
+            self.chain_scanner.as_ref(),
+        ) else {
+            return Ok(None);
+        };
+        crate::aura::recovery::recover_finality_at(
+            scanner.as_ref(),
+            self.finality_store.as_deref(),
+            block_number,
+            aura_config.validators.contract_address_at(block_number),
+            aura_config.posdao_transition,
+            aura_config.two_thirds_majority_transition,
+        )
+        .map(Some)
+    }
+
+    /// Finality state after block `block_hash` (at `block_number`): the
+    /// in-memory cache first, then an exact persisted snapshot, then — if the
+    /// block is canonical — receipt replay. `None` if none of them apply.
+    /// Never writes to the cache.
+    pub fn finality_after(&self, block_number: u64, block_hash: B256) -> Option<RollingFinality> {
+        self.finality_cache
+            .get(&block_hash)
+            .or_else(|| self.load_finality(block_number, block_hash))
+    }
+
+    /// [`Self::finality_after`] without the cache lookup.
+    fn load_finality(&self, block_number: u64, block_hash: B256) -> Option<RollingFinality> {
+        if let Some(snapshot) = self
+            .finality_store
+            .as_ref()
+            .and_then(|store| store.load(block_number, block_hash))
+        {
+            return Some(RollingFinality::from_state(snapshot.state));
+        }
+        // Receipt replay reads the canonical chain, so it can only stand in
+        // for a canonical block (pipeline unwind + re-execution).
+        let is_canonical = self.chain_scanner.as_ref().is_some_and(|scanner| {
+            scanner
+                .header_by_number(block_number)
+                .is_some_and(|h| h.hash_slow() == block_hash)
+        });
+        if !is_canonical {
+            return None;
+        }
+        match self.recover_finality(block_number) {
+            Ok(state) => state,
+            Err(err) => {
+                tracing::warn!(
+                    target: "reth::gnosis",
+                    block = block_number,
+                    %err,
+                    "AuRa rolling finality: failed to recover parent state"
+                );
+                None
+            }
+        }
+    }
+
+    /// AuRa context for `block_number` (child of `parent_hash`), or `None` if
+    /// the block is not pre-merge on an AuRa chain. `block_hash` is `None`
+    /// when the block is being built rather than imported.
+    pub(crate) fn aura_execution_ctx(
+        &self,
+        block_number: u64,
+        parent_hash: B256,
+        parent_gas_limit: Option<u64>,
+        block_hash: Option<B256>,
+    ) -> Option<AuraExecutionCtx> {
+        let c = self.chain_spec.aura_config.as_ref()?;
+        if self.chain_spec.is_paris_active_at_block(block_number) {
+            return None;
+        }
+
+        // Genesis has no signers, so its state is the empty tracker.
+        let parent_state = self
+            .finality_cache
+            .get(&parent_hash)
+            .or_else(|| {
+                let state = self.load_finality(block_number.saturating_sub(1), parent_hash)?;
+                if self.record_finality {
+                    self.finality_cache.insert(parent_hash, state.clone());
+                }
+                Some(state)
+            })
+            .or_else(|| (block_number <= 1).then(|| RollingFinality::new(Vec::new())));
+        // An unknown parent state fails the block in the executor: starting
+        // from an empty tracker would miss pending `finalizeChange` calls.
+        let missing_parent_finality = parent_state.is_none();
+        let mut rf = parent_state.unwrap_or_else(|| RollingFinality::new(Vec::new()));
+        rf.set_two_thirds_majority_transition(c.two_thirds_majority_transition);
+
+        let mut finalize_change_address = self.compute_finalize_change_address(block_number);
+        // For POSDAO contract validators: check if a pending InitiateChange
+        // has been finalized by the parent's rolling-finality state.
+        if finalize_change_address.is_none() {
+            if let Some(addr) = rf.take_finalize_change(block_number) {
+                tracing::info!(
+                    target: "reth::gnosis",
+                    block = block_number,
+                    validator = %addr,
+                    "Rolling finality: finalizeChange triggered"
+                );
+                finalize_change_address = Some(addr);
+            }
+        }
+
+        Some(AuraExecutionCtx {
+            finalize_change_address,
+            validator_contract: c.validators.contract_address_at(block_number),
+            rolling_finality: Arc::new(Mutex::new(rf)),
+            missing_parent_finality,
+            block_hash,
+            finality_cache: self.record_finality.then(|| self.finality_cache.clone()),
+            posdao_transition: c.posdao_transition,
+            block_gas_limit_contract: c.block_gas_limit_contract_at(block_number),
+            parent_gas_limit,
+            fixed_block_reward: c.fixed_block_reward_at(block_number),
+            aura_bytecode_rewrites: c.rewrite_bytecode.get(&block_number).cloned(),
+        })
+    }
+
+    /// AuRa transaction permission contract checked for the transactions of
+    /// `block_number`, pre- and post-merge.
+    fn tx_permission_contract_at(&self, block_number: u64) -> Option<Address> {
+        self.chain_spec
+            .aura_config
+            .as_ref()
+            .and_then(|c| c.transaction_permission_contract_at(block_number))
+    }
+
+    /// Block rewards contract the executor calls for `block_number`, if any:
+    /// [`GnosisBlockExecutionCtx::reward_contract`] without building the
+    /// context.
+    pub fn block_reward_contract_at(&self, block_number: u64) -> Option<Address> {
+        let aura_config = self.chain_spec.aura_config.as_ref();
+        let is_aura =
+            aura_config.is_some() && !self.chain_spec.is_paris_active_at_block(block_number);
+        block_reward_contract(
+            is_aura,
+            aura_config.and_then(|c| c.block_reward_contract_at(block_number)),
+            self.chain_spec.block_rewards_contract,
+        )
+    }
+
     /// Returns the chain spec associated with this configuration.
     pub fn chain_spec(&self) -> &GnosisChainSpec {
         &self.chain_spec
@@ -247,6 +432,17 @@ impl GnosisEvmConfig {
         self.block_assembler.extra_data = extra_data;
         self
     }
+
+    /// Seal pre-merge blocks built from this config with `author`.
+    pub fn with_aura_author(mut self, author: Arc<crate::aura::author::AuraAuthor>) -> Self {
+        self.block_assembler.aura_author = Some(author);
+        self
+    }
+
+    /// Signer pre-merge blocks built from this config are sealed with.
+    pub fn aura_author(&self) -> Option<&Arc<crate::aura::author::AuraAuthor>> {
+        self.block_assembler.aura_author.as_ref()
+    }
 }
 
 impl ConfigureEvm for GnosisEvmConfig {
@@ -332,15 +528,27 @@ impl ConfigureEvm for GnosisEvmConfig {
         // ensure we're not missing any timestamp based hardforks
         let chain_spec = self.chain_spec();
         let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
-        let spec_id = revm_spec_by_timestamp_and_block_number(
-            chain_spec,
-            attributes.timestamp,
-            parent.number() + 1,
-        );
+        let next_number = parent.number() + 1;
+        // AuRa config when the next block is a pre-merge AuRa block — only
+        // built by a node authoring on an AuRa devnet.
+        let aura_config = chain_spec
+            .aura_config
+            .as_ref()
+            .filter(|_| !chain_spec.is_paris_active_at_block(next_number));
+        let is_pre_merge = aura_config.is_some();
+        let spec_id = if is_pre_merge {
+            pre_merge_revm_spec(chain_spec, next_number)
+        } else {
+            revm_spec_by_timestamp_and_block_number(chain_spec, attributes.timestamp, next_number)
+        };
 
         // configure evm env based on parent block
-        // next_evm_env is for building the next block (post-merge only)
-        let mut cfg = get_cfg_env(&self.chain_spec, spec_id, attributes.timestamp, false);
+        let mut cfg = get_cfg_env(
+            &self.chain_spec,
+            spec_id,
+            attributes.timestamp,
+            is_pre_merge,
+        );
 
         if let Some(blob_params) = &blob_params {
             cfg.set_max_blobs_per_tx(blob_params.max_blobs_per_tx);
@@ -373,11 +581,18 @@ impl ConfigureEvm for GnosisEvmConfig {
         let gas_limit = attributes.gas_limit;
 
         let block_env = BlockEnv {
-            number: U256::from(parent.number + 1),
+            number: U256::from(next_number),
             beneficiary: attributes.suggested_fee_recipient,
             timestamp: U256::from(attributes.timestamp),
-            difficulty: U256::ZERO,
-            prevrandao: Some(attributes.prev_randao),
+            difficulty: aura_config
+                .map(|c| {
+                    crate::aura::seal::calculate_aura_difficulty(
+                        crate::aura::author::parent_step(parent, &c.step_duration),
+                        c.step_at(attributes.timestamp),
+                    )
+                })
+                .unwrap_or_default(),
+            prevrandao: (!is_pre_merge).then_some(attributes.prev_randao),
             gas_limit,
             // calculate basefee based on parent block's gas usage
             basefee: basefee.unwrap_or_default(),
@@ -396,57 +611,31 @@ impl ConfigureEvm for GnosisEvmConfig {
         let block_number = block.header().number;
 
         // Look up parent header to get its timestamp for hardfork activation checks
-        let parent_timestamp = self
+        let parent = self
             .header_lookup
-            .header_by_hash(&block.header().parent_hash)
-            .map(|h| h.timestamp)
-            .unwrap_or(0);
+            .header_by_hash(&block.header().parent_hash);
+        let parent_timestamp = parent.as_ref().map(|h| h.timestamp).unwrap_or(0);
 
         // `aura` is `Some` only when (a) the chain has an AuRa config AND
         // (b) the block is pre-merge per the chain spec. Chains that are
         // post-merge from genesis (no `aura_config`, or aura_config but
         // Paris active at block 0) get `aura: None` for every block.
-        let aura = self.chain_spec.aura_config.as_ref().and_then(|c| {
-            let is_pre_merge = !self.chain_spec.is_paris_active_at_block(block_number);
-            if !is_pre_merge {
-                return None;
-            }
-            let mut finalize_change_address = self.compute_finalize_change_address(block_number);
-            // For POSDAO contract validators: check if a pending
-            // InitiateChange has been finalized by the rolling-finality
-            // tracker.
-            if finalize_change_address.is_none() {
-                if let Ok(mut rf) = self.rolling_finality.lock() {
-                    if let Some(addr) = rf.take_finalize_change(block_number) {
-                        tracing::info!(
-                            target: "reth::gnosis",
-                            block = block_number,
-                            validator = %addr,
-                            "Rolling finality: finalizeChange triggered"
-                        );
-                        finalize_change_address = Some(addr);
-                    }
-                }
-            }
-            Some(AuraExecutionCtx {
-                finalize_change_address,
-                validator_contract: c.validators.contract_address_at(block_number),
-                rolling_finality: self.rolling_finality.clone(),
-                posdao_transition: c.posdao_transition,
-                aura_bytecode_rewrites: c.rewrite_bytecode.get(&block_number).cloned(),
-            })
-        });
+        let aura = self.aura_execution_ctx(
+            block_number,
+            block.header().parent_hash,
+            parent.map(|h| h.gas_limit),
+            Some(block.hash()),
+        );
 
         // `block_rewards_override` is *not* under `aura`: Gnosis post-merge
         // still uses the POSDAO reward contract, so the override is computed
         // from `aura_config.block_reward_contract_transitions` for any block
         // of an AuRa chain — pre- AND post-merge.
-        let block_rewards_override = self.chain_spec.aura_config.as_ref().and_then(|c| {
-            c.block_reward_contract_transitions
-                .range(..=block_number)
-                .next_back()
-                .map(|(_, addr)| *addr)
-        });
+        let block_rewards_override = self
+            .chain_spec
+            .aura_config
+            .as_ref()
+            .and_then(|c| c.block_reward_contract_at(block_number));
 
         Ok(GnosisBlockExecutionCtx {
             parent_hash: block.header().parent_hash,
@@ -455,6 +644,8 @@ impl ConfigureEvm for GnosisEvmConfig {
             parent_timestamp,
             aura,
             block_rewards_override,
+            tx_permission_contract: self.tx_permission_contract_at(block_number),
+            transactions: Some(BlockTransactions::Signed(&block.body().transactions)),
         })
     }
 
@@ -468,26 +659,15 @@ impl ConfigureEvm for GnosisEvmConfig {
         // applies in pre-merge AuRa contexts — in fact reth only invokes
         // payload building for chains we can author for. For consistency
         // with `context_for_block` we still gate on (chain has AuRa config)
-        // AND (next_block is pre-merge per chain spec).
-        let aura = self.chain_spec.aura_config.as_ref().and_then(|c| {
-            let is_pre_merge = !self.chain_spec.is_paris_active_at_block(next_block);
-            if !is_pre_merge {
-                return None;
-            }
-            Some(AuraExecutionCtx {
-                finalize_change_address: self.compute_finalize_change_address(next_block),
-                validator_contract: c.validators.contract_address_at(next_block),
-                rolling_finality: self.rolling_finality.clone(),
-                posdao_transition: c.posdao_transition,
-                aura_bytecode_rewrites: c.rewrite_bytecode.get(&next_block).cloned(),
-            })
-        });
-        let block_rewards_override = self.chain_spec.aura_config.as_ref().and_then(|c| {
-            c.block_reward_contract_transitions
-                .range(..=next_block)
-                .next_back()
-                .map(|(_, addr)| *addr)
-        });
+        // AND (next_block is pre-merge per chain spec). The built block has
+        // no hash yet, so its post-block state is neither cached nor
+        // persisted.
+        let aura = self.aura_execution_ctx(next_block, parent.hash(), Some(parent.gas_limit), None);
+        let block_rewards_override = self
+            .chain_spec
+            .aura_config
+            .as_ref()
+            .and_then(|c| c.block_reward_contract_at(next_block));
         Ok(GnosisBlockExecutionCtx {
             parent_hash: parent.hash(),
             parent_beacon_block_root: attributes.parent_beacon_block_root,
@@ -495,6 +675,8 @@ impl ConfigureEvm for GnosisEvmConfig {
             parent_timestamp: parent.timestamp,
             aura,
             block_rewards_override,
+            tx_permission_contract: self.tx_permission_contract_at(next_block),
+            transactions: None,
         })
     }
     // modifications to EIP-1559 gas accounting handler has been moved to Handler in gnosis_evm.rs
@@ -582,6 +764,8 @@ impl ConfigureEngineEvm<ExecutionData> for GnosisEvmConfig {
             // Engine-API payloads are always post-merge — no AuRa execution mode.
             aura: None,
             block_rewards_override: None,
+            tx_permission_contract: self.tx_permission_contract_at(payload.payload.block_number()),
+            transactions: Some(BlockTransactions::Encoded(payload.payload.transactions())),
         })
     }
 
@@ -651,9 +835,13 @@ fn compute_finalize_change_address_from_validators(
 #[cfg(test)]
 mod tests {
     use super::compute_finalize_change_address_from_validators as fc;
+    use super::{GnosisEvmConfig, NoopHeaderLookup};
+    use crate::aura::finality::RollingFinality;
     use crate::aura::validators::{ValidatorSet, ValidatorSetKind};
-    use alloy_primitives::Address;
+    use crate::spec::{chains::CHIADO_GENESIS, gnosis_spec::GnosisChainSpec};
+    use alloy_primitives::{Address, B256};
     use std::collections::BTreeMap;
+    use std::sync::Arc;
 
     fn addr(b: u8) -> Address {
         Address::from([b; 20])
@@ -822,4 +1010,110 @@ mod tests {
         assert_eq!(fc(&v, 1), None);
         assert_eq!(fc(&v, 2), None);
     }
+
+    #[test]
+    fn unknown_parent_finality_is_flagged() {
+        let spec = Arc::new(GnosisChainSpec::from(CHIADO_GENESIS.clone()));
+        let config = GnosisEvmConfig::new(spec, NoopHeaderLookup);
+        let parent = B256::repeat_byte(1);
+        let missing = |n| {
+            config
+                .aura_execution_ctx(n, parent, None, Some(B256::repeat_byte(2)))
+                .unwrap()
+                .missing_parent_finality
+        };
+
+        // Block 1 starts from genesis' empty tracker.
+        assert!(!missing(1));
+        // No cache entry, snapshot store or chain scanner to recover from.
+        assert!(missing(100));
+
+        config
+            .finality_cache
+            .insert(parent, RollingFinality::new(Vec::new()));
+        assert!(!missing(100));
+    }
+
+    #[test]
+    fn read_only_finality_records_nothing() {
+        let spec = Arc::new(GnosisChainSpec::from(CHIADO_GENESIS.clone()));
+        let config = GnosisEvmConfig::new(spec, NoopHeaderLookup);
+        let parent = B256::repeat_byte(1);
+        config
+            .finality_cache
+            .insert(parent, RollingFinality::new(Vec::new()));
+
+        let recording = config
+            .aura_execution_ctx(100, parent, None, Some(B256::repeat_byte(2)))
+            .unwrap();
+        assert!(recording.finality_cache.is_some());
+
+        // Same cache, read for the parent but not handed to the executor.
+        let read_only = config.clone().with_read_only_finality();
+        let ctx = read_only
+            .aura_execution_ctx(100, parent, None, Some(B256::repeat_byte(2)))
+            .unwrap();
+        assert!(!ctx.missing_parent_finality);
+        assert!(ctx.finality_cache.is_none());
+    }
+
+    /// Canonical chain of empty blocks `1..=head`, each signed by one of four
+    /// validators.
+    #[derive(Debug)]
+    struct EmptyChain {
+        head: u64,
+    }
+
+    impl crate::aura::recovery::ChainScanner for EmptyChain {
+        fn header_by_number(&self, n: u64) -> Option<gnosis_primitives::header::GnosisHeader> {
+            (n <= self.head).then(|| gnosis_primitives::header::GnosisHeader {
+                number: n,
+                beneficiary: addr((n % 4) as u8 + 1),
+                ..Default::default()
+            })
+        }
+        fn receipts_by_block_number(
+            &self,
+            n: u64,
+        ) -> Option<Vec<reth_ethereum_primitives::Receipt>> {
+            (n <= self.head).then(Vec::new)
+        }
+    }
+
+    #[test]
+    fn re_execution_recovers_pre_merge_parent_finality() {
+        // `re-execute --from 100`: nothing is cached, block 99 is canonical.
+        let spec = Arc::new(GnosisChainSpec::from(CHIADO_GENESIS.clone()));
+        let chain = EmptyChain { head: 99 };
+        let parent = crate::aura::recovery::ChainScanner::header_by_number(&chain, 99)
+            .unwrap()
+            .hash_slow();
+        let config = GnosisEvmConfig::new(spec.clone(), NoopHeaderLookup).with_chain_scanner(chain);
+        let ctx = config
+            .aura_execution_ctx(100, parent, None, Some(B256::repeat_byte(2)))
+            .unwrap();
+        assert!(!ctx.missing_parent_finality);
+        assert!(config.finality_cache.get(&parent).is_some());
+
+        // `stage run` from the Execution checkpoint: the node's snapshot of
+        // the checkpoint block is enough without a chain scanner.
+        let dir = std::env::temp_dir().join(format!(
+            "reth-gnosis-finality-stage-run-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = crate::aura::persistence::FinalityStore::new(&dir);
+        store
+            .save(&crate::aura::persistence::FinalitySnapshot::new(
+                99,
+                parent,
+                &RollingFinality::new(vec![addr(1)]),
+            ))
+            .unwrap();
+        let config = GnosisEvmConfig::new(spec, NoopHeaderLookup).with_finality_store(store);
+        let ctx = config
+            .aura_execution_ctx(100, parent, None, Some(B256::repeat_byte(2)))
+            .unwrap();
+        assert!(!ctx.missing_parent_finality);
+    }
 }
@@ -1,5 +1,6 @@
 use crate::errors::GnosisBlockExecutionError;
-use crate::spec::gnosis_spec::{BalancerHardforkConfig, GnosisHardForks};
+use crate::spec::contract_rewrites::ContractRewriteFork;
+use crate::spec::gnosis_spec::GnosisHardForks;
 use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_eips::eip4895::{Withdrawal, Withdrawals};
 use alloy_primitives::U256;
@@ -20,7 +21,7 @@ use revm::{
     context::result::{ExecutionResult, Output, ResultAndState},
     DatabaseCommit,
 };
-use revm_state::{Account, AccountInfo};
+use revm_state::{Account, AccountInfo, EvmStorageSlot};
 use std::fmt::Display;
 
 // Codegen from https://github.com/gnosischain/specs/blob/master/execution/withdrawals.md
@@ -103,6 +104,22 @@ where
     }
 }
 
+/// Selector of the block rewards contract's `reward(address[],uint16[])`.
+pub(crate) const BLOCK_REWARDS_SELECTOR: [u8; 4] = rewardCall::SELECTOR;
+
+/// `(receiver, amount)` pairs returned by the block rewards contract, in
+/// contract order.
+pub(crate) fn decode_block_rewards(
+    output: &[u8],
+) -> Result<Vec<(Address, U256)>, alloy_sol_types::Error> {
+    let result = rewardCall::abi_decode_returns(output)?;
+    Ok(result
+        .receiversNative
+        .into_iter()
+        .zip(result.rewardsNative)
+        .collect())
+}
+
 /// Applies the post-block call to the block rewards POSDAO contract, using the given block,
 /// Ref: <https://github.com/gnosischain/specs/blob/master/execution/posdao-post-merge.md>
 ///
@@ -170,7 +187,7 @@ where
         }
     };
 
-    let result = rewardCall::abi_decode_returns(output_bytes.as_ref()).map_err(|e| {
+    let rewards = decode_block_rewards(output_bytes.as_ref()).map_err(|e| {
         BlockExecutionError::from(GnosisBlockExecutionError::CustomErrorMessage {
             message: format!(
                 "error parsing block rewards contract system call return {:?}: {}",
@@ -192,12 +209,8 @@ where
     evm.db_mut().commit(state);
 
     let mut balance_increments = AddressMap::default();
-    for (address, amount) in result
-        .receiversNative
-        .iter()
-        .zip(result.rewardsNative.iter())
-    {
-        *balance_increments.entry(*address).or_default() += amount.to::<u128>();
+    for (address, amount) in rewards {
+        *balance_increments.entry(address).or_default() += amount.to::<u128>();
     }
 
     Ok((balance_increments, reward_logs))
@@ -218,7 +231,7 @@ where
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn apply_post_block_system_calls<SPEC>(
     chain_spec: &SPEC,
-    block_rewards_contract: Address,
+    block_rewards_contract: Option<Address>,
     withdrawal_contract: Address,
     block_timestamp: u64,
     withdrawals: Option<&Withdrawals>,
@@ -239,8 +252,12 @@ where
             apply_withdrawals_contract_call(withdrawal_contract, withdrawals, evm, system_caller)?;
     }
 
-    let (balance_increments, reward_logs) =
-        apply_block_rewards_contract_call(block_rewards_contract, coinbase, evm, system_caller)?;
+    let (balance_increments, reward_logs) = match block_rewards_contract {
+        Some(contract) => {
+            apply_block_rewards_contract_call(contract, coinbase, evm, system_caller)?
+        }
+        None => Default::default(),
+    };
 
     Ok((balance_increments, withdrawal_requests, reward_logs))
 }
@@ -288,48 +305,86 @@ pub fn rewrite_aura_bytecodes(
     evm.db_mut().commit(state);
 }
 
+/// Apply the account rewrites of a contract rewrite fork. Accounts already in
+/// the target state are skipped, so applying a fork twice is a no-op.
 pub fn rewrite_bytecodes(
     evm: &mut impl Evm<DB: Database + DatabaseCommit>,
-    balancer_hardfork_config: &BalancerHardforkConfig,
+    fork: &ContractRewriteFork,
 ) {
     let mut state: AddressMap<Account> = Default::default();
-    for (addr, code, expected_code_hash) in &balancer_hardfork_config.config {
+    for (addr, rewrite) in &fork.accounts {
         let original_account_info = evm
             .db_mut()
             .basic(*addr)
             .unwrap_or_default()
             .unwrap_or_default();
-        if &original_account_info.code_hash == expected_code_hash {
+
+        let mut modified_account_info = original_account_info.clone();
+        if let Some(code) = &rewrite.code {
+            modified_account_info.code_hash = rewrite.code_hash().unwrap_or(KECCAK_EMPTY);
+            modified_account_info.code = code.clone();
+        }
+        if let Some(balance) = rewrite.balance {
+            modified_account_info.balance = balance;
+        }
+        if let Some(nonce) = rewrite.nonce {
+            modified_account_info.nonce = nonce;
+        }
+
+        let mut storage: HashMap<U256, EvmStorageSlot> = HashMap::default();
+        for (slot, value) in &rewrite.storage {
+            let present = evm.db_mut().storage(*addr, *slot).unwrap_or_default();
+            if present != *value {
+                storage.insert(*slot, EvmStorageSlot::new_changed(present, *value, 0));
+            }
+        }
+
+        if modified_account_info.code_hash == original_account_info.code_hash
+            && modified_account_info.balance == original_account_info.balance
+            && modified_account_info.nonce == original_account_info.nonce
+            && storage.is_empty()
+        {
             // No need to rewrite
             tracing::trace!(">>> Skipping rewrite for address: {}", addr);
-            tracing::trace!("    Code hash matches expected: {}", expected_code_hash);
             continue;
         }
-        let modified_account_info = AccountInfo {
-            code_hash: if let Some(ref code) = code {
-                code.hash_slow()
-            } else {
-                KECCAK_EMPTY
-            },
-            code: code.clone(),
-            ..original_account_info
-        };
+        tracing::info!(
+            target: "reth::gnosis",
+            fork = %fork.hardfork,
+            %addr,
+            from = %original_account_info.code_hash,
+            to = %modified_account_info.code_hash,
+            slots = storage.len(),
+            "Rewriting account"
+        );
         let account = Account {
             info: modified_account_info,
-            storage: HashMap::default(),
+            storage,
             status: revm_state::AccountStatus::Touched,
             transaction_id: 0,
-            original_info: Box::new(original_account_info.clone()),
+            original_info: Box::new(original_account_info),
         };
-        tracing::info!(
-            "Rewriting Bytecode >>> Addr: {}; From: {}; To: {}",
-            addr,
-            original_account_info.code_hash,
-            account.info.code_hash
-        );
         state.insert(*addr, account);
     }
 
     // commit the modified accounts to the EVM database
     evm.db_mut().commit(state);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolValue;
+
+    #[test]
+    fn decodes_block_rewards_in_contract_order() {
+        let receivers = vec![Address::repeat_byte(2), Address::repeat_byte(1)];
+        let amounts = vec![U256::from(5), U256::from(7)];
+        let output = (receivers.clone(), amounts.clone()).abi_encode_params();
+        assert_eq!(
+            decode_block_rewards(&output).unwrap(),
+            vec![(receivers[0], amounts[0]), (receivers[1], amounts[1])]
+        );
+        assert!(decode_block_rewards(&[]).is_err());
+    }
+}
@@ -16,7 +16,6 @@ use reth_node_builder::{
     BuilderContext, DebugNode, FullNodeTypes, Node, NodeAdapter, NodeTypes,
     PayloadAttributesBuilder, PayloadTypes,
 };
-use reth_node_ethereum::EthereumEthApiBuilder;
 use reth_provider::{EthStorage, HeaderProvider};
 use spec::gnosis_spec::GnosisChainSpec;
 use std::sync::Arc;
@@ -28,7 +27,7 @@ use crate::{
         block::{BlockBody, GnosisBlock, TransactionSigned},
         GnosisNodePrimitives,
     },
-    rpc::GnosisNetwork,
+    rpc::GnosisEthApiBuilder,
 };
 
 pub mod aura;
@@ -49,16 +48,62 @@ mod payload_builder;
 mod pool;
 mod primitives;
 mod rpc;
+pub mod service_tx;
 pub mod spec;
 mod testing;
+pub mod trace;
 pub mod version;
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, clap::Args)]
+#[derive(Debug, Clone, PartialEq, Eq, clap::Args)]
 #[command(next_help_heading = "Gnosis")]
 pub struct GnosisArgs {
-    /// Sample arg to test
-    #[arg(long = "gnosis.sample-arg", value_name = "SAMPLE_ARG")]
-    pub sample_arg: Option<String>,
+    /// Admit zero-gas-price service transactions from senders certified by the
+    /// Certifier contract, resolved through the genesis `aura.registrar`.
+    ///
+    /// Certified transactions are only included in AuRa blocks, which waive
+    /// the base fee for them.
+    #[arg(long = "gnosis.service-transactions", default_value_t = false)]
+    pub service_transactions: bool,
+
+    /// Gas available to service transactions in each built block.
+    #[arg(
+        long = "gnosis.service-tx-gas-limit",
+        value_name = "GAS",
+        default_value_t = service_tx::DEFAULT_SERVICE_TX_GAS_LIMIT
+    )]
+    pub service_tx_gas_limit: u64,
+
+    /// Author AuRa blocks with the secret key in this file (hex-encoded).
+    ///
+    /// Intended for private AuRa devnets only. The node builds a block at
+    /// every step (`timestamp / stepDuration`) for which the chain's validator
+    /// set names this key's address as proposer, until the merge block.
+    #[arg(long = "gnosis.aura-signer", value_name = "KEYFILE")]
+    pub aura_signer: Option<std::path::PathBuf>,
+}
+
+impl GnosisArgs {
+    /// CLI defaults: service transactions disabled.
+    pub const DEFAULT: Self = Self {
+        service_transactions: false,
+        service_tx_gas_limit: service_tx::DEFAULT_SERVICE_TX_GAS_LIMIT,
+        aura_signer: None,
+    };
+
+    /// Per-block gas budget for service transactions, 0 when disabled.
+    pub const fn service_tx_gas_budget(&self) -> u64 {
+        if self.service_transactions {
+            self.service_tx_gas_limit
+        } else {
+            0
+        }
+    }
+}
+
+impl Default for GnosisArgs {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
 }
 
 /// Type configuration for a regular Gnosis node.
@@ -70,13 +115,17 @@ pub struct GnosisNode {
 
 impl GnosisNode {
     pub const fn new() -> Self {
-        let args = GnosisArgs { sample_arg: None };
+        Self::with_args(GnosisArgs::DEFAULT)
+    }
+
+    /// Node configured by `args`.
+    pub const fn with_args(args: GnosisArgs) -> Self {
         Self { args }
     }
 
     /// Returns the components for the given [GnosisArgs].
     pub fn components<Node>(
-        _args: &GnosisArgs,
+        args: &GnosisArgs,
     ) -> ComponentsBuilder<
         Node,
         GnosisPoolBuilder,
@@ -97,13 +146,22 @@ impl GnosisNode {
             >,
         >,
     {
+        // The executor records each block's post-execution AuRa state that the
+        // consensus reads back for the contract-based proposer check.
+        let finality_cache = Arc::new(crate::aura::finality_cache::FinalityCache::default());
         ComponentsBuilder::default()
             .node_types::<Node>()
-            .pool(GnosisPoolBuilder::default())
-            .executor(GnosisExecutorBuilder::default())
-            .payload(BasicPayloadServiceBuilder::default())
+            .pool(GnosisPoolBuilder::default().with_service_transactions(args.service_transactions))
+            .executor(
+                GnosisExecutorBuilder::new(finality_cache.clone())
+                    .with_aura_signer(args.aura_signer.clone()),
+            )
+            .payload(BasicPayloadServiceBuilder::new(
+                GnosisPayloadBuilder::default()
+                    .with_service_tx_gas_limit(args.service_tx_gas_budget()),
+            ))
             .network(GnosisNetworkBuilder::default())
-            .consensus(GnosisConsensusBuilder::default())
+            .consensus(GnosisConsensusBuilder::new(finality_cache))
     }
 }
 
@@ -145,8 +203,7 @@ impl<N: FullNodeComponents<Types = Self>> DebugNode<N> for GnosisNode {
 }
 
 /// Add-ons w.r.t. gnosis
-pub type GnosisAddOns<N> =
-    RpcAddOns<N, EthereumEthApiBuilder<GnosisNetwork>, GnosisEngineValidatorBuilder>;
+pub type GnosisAddOns<N> = RpcAddOns<N, GnosisEthApiBuilder, GnosisEngineValidatorBuilder>;
 
 impl<N> Node<N> for GnosisNode
 where
@@ -174,9 +231,29 @@ where
 }
 
 /// A regular Gnosis evm and executor builder.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct GnosisExecutorBuilder;
+pub struct GnosisExecutorBuilder {
+    finality_cache: Arc<crate::aura::finality_cache::FinalityCache>,
+    aura_signer: Option<std::path::PathBuf>,
+}
+
+impl GnosisExecutorBuilder {
+    /// Executor builder recording AuRa finality states into `finality_cache`.
+    pub fn new(finality_cache: Arc<crate::aura::finality_cache::FinalityCache>) -> Self {
+        Self {
+            finality_cache,
+            aura_signer: None,
+        }
+    }
+
+    /// Seal built pre-merge blocks with the key in `keyfile`
+    /// (`--gnosis.aura-signer`).
+    pub fn with_aura_signer(mut self, keyfile: Option<std::path::PathBuf>) -> Self {
+        self.aura_signer = keyfile;
+        self
+    }
+}
 
 impl<Node> ExecutorBuilder<Node> for GnosisExecutorBuilder
 where
@@ -185,6 +262,7 @@ where
         Provider: HeaderProvider<Header = GnosisHeader>
                       + reth_storage_api::ReceiptProvider<Receipt = reth_ethereum_primitives::Receipt>
                       + reth_storage_api::BlockNumReader
+                      + reth_storage_api::StageCheckpointReader
                       + std::fmt::Debug
                       + Clone
                       + Unpin
@@ -196,38 +274,56 @@ where
     async fn build_evm(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::EVM> {
         let provider = ctx.provider().clone();
         let chain_spec = ctx.chain_spec();
-        let evm_config = GnosisEvmConfig::new(chain_spec.clone(), provider.clone());
+        let finality_store =
+            crate::aura::persistence::FinalityStore::new(&ctx.config().datadir().data_dir());
+        let mut evm_config = GnosisEvmConfig::new(chain_spec.clone(), provider.clone())
+            .with_finality_cache(self.finality_cache)
+            .with_finality_store(finality_store.clone())
+            .with_chain_scanner(crate::aura::recovery::ProviderChainScanner::new(
+                provider.clone(),
+            ));
+        if let Some(keyfile) = self.aura_signer.as_deref() {
+            let step_duration = chain_spec
+                .aura_config
+                .as_ref()
+                .map(|c| c.step_duration.clone())
+                .ok_or_else(|| eyre::eyre!("--gnosis.aura-signer requires an AuRa chain spec"))?;
+            let author = crate::aura::author::AuraAuthor::new(
+                crate::aura::author::AuraSigner::from_keyfile(keyfile)?,
+                step_duration,
+            );
+            evm_config = evm_config.with_aura_author(Arc::new(author));
+        }
 
         // Pre-merge AuRa head only — post-merge nodes never enter the AuRa path.
-        if let Some(aura_config) = chain_spec.aura_config.as_ref() {
+        // Seed the finality cache with the head's state so the first imported
+        // block finds its parent without a cache miss.
+        if chain_spec.aura_config.is_some() {
             use reth_chainspec::EthereumHardforks;
-            use reth_storage_api::BlockNumReader;
+            use reth_storage_api::{BlockHashReader, BlockNumReader};
             let head = provider.best_block_number()?;
             if !chain_spec.is_paris_active_at_block(head) {
-                let scanner = crate::aura::recovery::ProviderChainScanner::new(provider);
-                let validator_contract = aura_config.validators.contract_address_at(head);
-                let recovered = crate::aura::recovery::reconstruct_finality_state(
-                    &scanner,
-                    head,
-                    validator_contract,
-                    aura_config.posdao_transition,
-                );
-                if !recovered.pending_transitions().is_empty()
-                    || recovered.finalize_change_at().is_some()
-                {
+                let head_hash = provider
+                    .block_hash(head)?
+                    .ok_or_else(|| eyre::eyre!("missing hash for canonical head {head}"))?;
+                if let Some(recovered) = evm_config.recover_finality(head)? {
                     tracing::info!(
                         target: "reth::gnosis",
                         head,
+                        validators = recovered.validator_count(),
                         pending = recovered.pending_transitions().len(),
                         finalize_at = ?recovered.finalize_change_at(),
                         "AuRa recovery: restored rolling-finality state"
                     );
-                    let mut rf = evm_config
-                        .rolling_finality
-                        .lock()
-                        .map_err(|_| eyre::eyre!("AuRa rolling-finality mutex poisoned"))?;
-                    *rf = recovered;
+                    evm_config.finality_cache.insert(head_hash, recovered);
                 }
+                ctx.task_executor().spawn_task(
+                    crate::aura::persistence::persist_committed_finality(
+                        provider.clone(),
+                        evm_config.clone(),
+                        finality_store,
+                    ),
+                );
             }
         }
 
@@ -238,7 +334,17 @@ where
 /// A basic Gnosis consensus builder.
 #[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct GnosisConsensusBuilder;
+pub struct GnosisConsensusBuilder {
+    finality_cache: Arc<crate::aura::finality_cache::FinalityCache>,
+}
+
+impl GnosisConsensusBuilder {
+    /// Consensus builder reading post-execution AuRa validator sets from
+    /// `finality_cache` (shared with [`GnosisExecutorBuilder`]).
+    pub fn new(finality_cache: Arc<crate::aura::finality_cache::FinalityCache>) -> Self {
+        Self { finality_cache }
+    }
+}
 
 impl<Node> ConsensusBuilder<Node> for GnosisConsensusBuilder
 where
@@ -249,7 +355,11 @@ where
     type Consensus = Arc<dyn FullConsensus<GnosisNodePrimitives>>;
 
     async fn build_consensus(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
-        Ok(Arc::new(GnosisConsensus::new(ctx.chain_spec())))
+        Ok(Arc::new(
+            GnosisConsensus::new(ctx.chain_spec())
+                .with_finality_cache(self.finality_cache)
+                .with_equivocation_evidence_dir(&ctx.config().datadir().data_dir()),
+        ))
     }
 }
 
@@ -3,14 +3,18 @@ use reth::api::FullNodeComponents;
 use reth::args::DefaultStorageValues;
 use reth_cli_commands::common::EnvironmentArgs;
 use reth_cli_commands::download::DownloadDefaults;
+use reth_gnosis::aura::author::{run_aura_authoring, EngineAuthoringBackend};
+use reth_gnosis::aura::rpc::{AuraApiServer, AuraRpc};
 use reth_gnosis::cli::gnosis_cli::Commands;
 use reth_gnosis::engine::GnosisEngineValidator;
 use reth_gnosis::initialize::download_init_state::{CHIADO_DOWNLOAD_SPEC, GNOSIS_DOWNLOAD_SPEC};
 use reth_gnosis::initialize::import_and_ensure_state::download_and_import_init_state;
 use reth_gnosis::initialize::SNAPSHOT_API_URL;
+use reth_gnosis::trace::debug::{GnosisDebugApiServer, GnosisDebugRpc};
+use reth_gnosis::trace::parity::{GnosisTraceApiServer, GnosisTraceRpc};
 use reth_gnosis::{
     cli::gnosis_cli::GnosisCli, spec::gnosis_spec::GnosisChainSpecParser,
-    version::init_gnosis_version, GnosisNode,
+    version::init_gnosis_version, GnosisArgs, GnosisNode,
 };
 use reth_rpc::ValidationApi;
 use reth_rpc_api::servers::BlockSubmissionValidationApiServer;
@@ -25,7 +29,7 @@ static ALLOC: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 /// Gnosis-specific extension args attached to the `node` command.
 ///
 /// Surfaced as a `Gnosis` group in `reth node --help`.
-#[derive(Debug, Clone, Copy, Default, Args)]
+#[derive(Debug, Clone, Default, Args)]
 #[command(next_help_heading = "Gnosis")]
 pub struct GnosisExt {
     /// Download and import a canonical post-merge state snapshot before sync starts.
@@ -41,6 +45,9 @@ pub struct GnosisExt {
     /// this flag.
     #[arg(long = "gnosis.import-post-merge-state", default_value_t = false)]
     pub import_post_merge_state: bool,
+
+    #[command(flatten)]
+    pub node: GnosisArgs,
 }
 
 type CliGnosis = GnosisCli<GnosisChainSpecParser, GnosisExt>;
@@ -118,14 +125,17 @@ fn reject_migrate_v2() {
 }
 
 fn run_reth(cli: CliGnosis) {
-    if let Err(err) = cli.run(|builder, _| async move {
+    if let Err(err) = cli.run(|builder, ext| async move {
         let handle = builder
-            .node(GnosisNode::new())
+            .node(GnosisNode::with_args(ext.node.clone()))
             .extend_rpc_modules(|ctx| {
+                // Only block import records AuRa finality states; the RPC
+                // modules replay and validate blocks without writing them.
+                let evm_config = ctx.node().evm_config().clone().with_read_only_finality();
                 let validation_api = ValidationApi::new(
                     ctx.provider().clone(),
                     Arc::new(ctx.node().consensus().clone()),
-                    ctx.node().evm_config().clone(),
+                    evm_config.clone(),
                     ctx.config().rpc.flashbots_config(),
                     ctx.node().task_executor().clone(),
                     Arc::new(GnosisEngineValidator::new(ctx.config().chain.clone())),
@@ -134,6 +144,33 @@ fn run_reth(cli: CliGnosis) {
                     RethRpcModule::Flashbots,
                     validation_api.into_rpc(),
                 )?;
+
+                let aura_api = AuraRpc::new(ctx.provider().clone(), evm_config.clone());
+                ctx.modules.merge_configured(aura_api.into_rpc())?;
+
+                // Replaces reth's `debug_traceBlockByNumber` / `ByHash`; reth's
+                // debug API still answers requests without `includeSystemCalls`.
+                let debug_api = GnosisDebugRpc::new(
+                    ctx.provider().clone(),
+                    evm_config.clone(),
+                    ctx.registry.debug_api(),
+                );
+                ctx.modules.add_or_replace_if_module_configured(
+                    RethRpcModule::Debug,
+                    debug_api.into_rpc(),
+                )?;
+
+                // Replaces reth's `trace_block` / `trace_filter`, whose reward
+                // entries follow Ethereum's proof-of-work schedule.
+                let trace_api = GnosisTraceRpc::new(
+                    ctx.provider().clone(),
+                    evm_config,
+                    ctx.registry.trace_api(),
+                );
+                ctx.modules.add_or_replace_if_module_configured(
+                    RethRpcModule::Trace,
+                    trace_api.into_rpc(),
+                )?;
                 Ok(())
             })
             .launch_with_debug_capabilities()
@@ -142,6 +179,29 @@ fn run_reth(cli: CliGnosis) {
         // Log fork IDs after node startup when tracing is initialized
         handle.node.chain_spec().log_all_fork_ids();
 
+        // `--gnosis.aura-signer`: the node's evm config carries the author.
+        if let Some(author) = handle.node.evm_config.aura_author().cloned() {
+            let chain_spec = handle.node.chain_spec();
+            let backend = EngineAuthoringBackend::new(
+                handle.node.provider.clone(),
+                chain_spec.clone(),
+                handle.node.add_ons_handle.beacon_engine_handle.clone(),
+                handle.node.add_ons_handle.engine_events.clone(),
+                handle.node.payload_builder_handle.clone(),
+            );
+            let finality_cache = handle.node.evm_config.finality_cache.clone();
+            handle
+                .node
+                .task_executor
+                .spawn_critical("aura authoring", async move {
+                    if let Err(err) =
+                        run_aura_authoring(author, chain_spec, finality_cache, backend).await
+                    {
+                        tracing::error!(target: "reth::gnosis", %err, "AuRa authoring stopped");
+                    }
+                });
+        }
+
         handle.node_exit_future.await
     }) {
         eprintln!("Error: {err:?}");
@@ -24,6 +24,7 @@ use reth_ethereum_engine_primitives::{
 use reth_ethereum_payload_builder::EthereumBuilderConfig;
 use reth_ethereum_primitives::TransactionSigned;
 use reth_evm::{
+    env::EvmEnv,
     execute::{BlockBuilder, BlockBuilderOutcome},
     ConfigureEvm, Evm, NextBlockEnvAttributes,
 };
@@ -31,7 +32,7 @@ use reth_node_api::PayloadAttributes;
 use reth_node_builder::{BuiltPayload, PayloadBuilderError};
 use reth_payload_builder::{BlobSidecars, PayloadId};
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
-use reth_primitives_traits::SealedBlock;
+use reth_primitives_traits::{Recovered, SealedBlock};
 use reth_provider::{ChainSpecProvider, StateProviderFactory};
 use reth_revm::{database::StateProviderDatabase, db::State};
 use reth_transaction_pool::{
@@ -44,7 +45,9 @@ use revm_primitives::U256;
 use tracing::{debug, trace, warn};
 
 use crate::{
+    aura::tx_permission::{self, contract_version, TxPermissionError},
     primitives::{block::GnosisBlock, GnosisNodePrimitives},
+    service_tx::is_service_transaction,
     spec::gnosis_spec::GnosisChainSpec,
 };
 
@@ -63,6 +66,8 @@ pub struct GnosisPayloadBuilder<Pool, Client, GnosisEvmConfig> {
     evm_config: GnosisEvmConfig,
     /// Payload builder configuration.
     builder_config: EthereumBuilderConfig,
+    /// Gas available to service transactions per block, 0 to leave them out.
+    service_tx_gas_limit: u64,
 }
 
 impl<Pool, Client, EvmConfig> GnosisPayloadBuilder<Pool, Client, EvmConfig> {
@@ -77,8 +82,15 @@ impl<Pool, Client, EvmConfig> GnosisPayloadBuilder<Pool, Client, EvmConfig> {
             pool,
             evm_config,
             builder_config,
+            service_tx_gas_limit: 0,
         }
     }
+
+    /// Includes certified service transactions up to `gas_limit` gas per block.
+    pub const fn with_service_tx_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.service_tx_gas_limit = gas_limit;
+        self
+    }
 }
 
 // Default implementation of [PayloadBuilder] for unit type
@@ -101,6 +113,7 @@ where
             self.client.clone(),
             self.pool.clone(),
             self.builder_config.clone(),
+            self.service_tx_gas_limit,
             args,
             // evm_env,
             |attributes| self.pool.best_transactions_with_attributes(attributes),
@@ -125,6 +138,7 @@ where
             self.client.clone(),
             self.pool.clone(),
             self.builder_config.clone(),
+            self.service_tx_gas_limit,
             args,
             |attributes| self.pool.best_transactions_with_attributes(attributes),
         )?
@@ -142,6 +156,9 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// Service transactions are included first, using up to `service_tx_gas_limit`
+/// gas, in blocks that waive their base fee (pre-merge AuRa blocks).
 #[inline]
 #[allow(clippy::too_many_arguments)]
 pub fn default_ethereum_payload<EvmConfig, Pool, Client, F>(
@@ -149,6 +166,7 @@ pub fn default_ethereum_payload<EvmConfig, Pool, Client, F>(
     client: Client,
     pool: Pool,
     builder_config: EthereumBuilderConfig,
+    service_tx_gas_limit: u64,
     args: BuildArguments<EthPayloadAttributes, GnosisBuiltPayload>,
     best_txs: F,
 ) -> Result<BuildOutcome<GnosisBuiltPayload>, PayloadBuilderError>
@@ -247,6 +265,89 @@ where
         .map(|withdrawals| withdrawals.length())
         .unwrap_or(0);
 
+    let next_number = parent_header.number + 1;
+
+    // The executor checks a block's transactions against the AuRa transaction permission
+    // contract on the parent state before running it, which is not possible for a block whose
+    // transactions are picked as it is built. Candidates are checked here instead, on the same
+    // state and block environment.
+    let mut tx_permissions = chain_spec
+        .aura_config
+        .as_ref()
+        .and_then(|aura| aura.transaction_permission_contract_at(next_number))
+        .map(|contract| {
+            let evm_env = EvmEnv {
+                cfg_env: builder.evm().cfg_env().clone(),
+                block_env: builder.evm().block().clone(),
+            };
+            let mut evm =
+                evm_config.evm_with_env(StateProviderDatabase::new(&state_provider), evm_env);
+            contract_version(&mut evm, contract).map(|version| (evm, contract, version))
+        })
+        .transpose()
+        .map_err(PayloadBuilderError::other)?;
+    let mut check_tx_permission = |tx: &Recovered<TransactionSigned>| match &mut tx_permissions {
+        Some((evm, contract, version)) => {
+            match tx_permission::check_tx_permission(
+                evm,
+                *contract,
+                *version,
+                tx.signer(),
+                tx.inner(),
+            ) {
+                Ok(()) => Ok(None),
+                Err(TxPermissionError::Denied(denied)) => Ok(Some(denied)),
+                Err(err) => Err(PayloadBuilderError::other(err)),
+            }
+        }
+        None => Ok(None),
+    };
+
+    // Service transactions never cover the base fee, so they sit in the basefee sub-pool and
+    // never come out of `best_txs`. Only AuRa blocks, which waive the base fee for them, can
+    // carry them.
+    if service_tx_gas_limit > 0
+        && chain_spec.aura_config.is_some()
+        && !chain_spec.is_paris_active_at_block(next_number)
+    {
+        let mut service_gas_used = 0;
+        for pool_tx in service_transactions(&pool) {
+            if service_gas_used + pool_tx.gas_limit() > service_tx_gas_limit
+                || cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit
+            {
+                continue;
+            }
+
+            if cancel.is_cancelled() {
+                return Ok(BuildOutcome::Cancelled);
+            }
+
+            let tx = pool_tx.to_consensus();
+            if let Some(denied) = check_tx_permission(&tx)? {
+                trace!(target: "payload_builder", %denied, ?tx, "skipping denied service transaction");
+                continue;
+            }
+            let gas_used = match builder.execute_transaction(tx.clone()) {
+                Ok(gas_output) => gas_output.tx_gas_used(),
+                Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
+                    error,
+                    ..
+                })) => {
+                    trace!(target: "payload_builder", %error, ?tx, "skipping invalid service transaction");
+                    continue;
+                }
+                Err(err) => return Err(PayloadBuilderError::evm(err)),
+            };
+
+            block_transactions_rlp_length += tx.inner().length();
+            service_gas_used += gas_used;
+            cumulative_gas_used += gas_used;
+        }
+        if service_gas_used > 0 {
+            debug!(target: "payload_builder", id=%payload_id, service_gas_used, "included service transactions");
+        }
+    }
+
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -338,6 +439,15 @@ where
             };
         }
 
+        if let Some(denied) = check_tx_permission(&tx)? {
+            trace!(target: "payload_builder", %denied, ?tx, "skipping denied transaction and its descendants");
+            best_txs.mark_invalid(
+                &pool_tx,
+                &InvalidPoolTransactionError::other(TxPermissionError::Denied(denied)),
+            );
+            continue;
+        }
+
         let gas_used = match builder.execute_transaction(tx.clone()) {
             Ok(gas_output) => gas_output.tx_gas_used(),
             Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
@@ -430,6 +540,19 @@ where
     })
 }
 
+/// Certified service transactions in the pool, each sender's in nonce order.
+fn service_transactions<Pool: TransactionPool>(
+    pool: &Pool,
+) -> Vec<Arc<ValidPoolTransaction<Pool::Transaction>>> {
+    let mut txs: Vec<_> = pool
+        .queued_transactions()
+        .into_iter()
+        .filter(|tx| is_service_transaction(&tx.transaction))
+        .collect();
+    txs.sort_by_key(|tx| (tx.sender(), tx.nonce()));
+    txs
+}
+
 #[derive(Debug, Clone)]
 pub struct GnosisBuiltPayload {
     /// Identifier of the payload
@@ -15,9 +15,18 @@ use crate::{
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct GnosisPayloadBuilder;
+pub struct GnosisPayloadBuilder {
+    /// Gas available to service transactions per block, 0 to leave them out.
+    service_tx_gas_limit: u64,
+}
 
 impl GnosisPayloadBuilder {
+    /// Includes certified service transactions up to `gas_limit` gas per block.
+    pub const fn with_service_tx_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.service_tx_gas_limit = gas_limit;
+        self
+    }
+
     /// A helper method initializing [`crate::payload::GnosisPayloadBuilder`] with
     /// the given EVM config.
     pub fn build<Types, Node, Evm, Pool>(
@@ -51,7 +60,8 @@ impl GnosisPayloadBuilder {
             pool,
             evm_config,
             EthereumBuilderConfig::new().with_gas_limit(gas_limit),
-        ))
+        )
+        .with_service_tx_gas_limit(self.service_tx_gas_limit))
     }
 }
 
@@ -2,30 +2,423 @@
 // https://github.com/paradigmxyz/reth/blob/74cc561917642e8024e8aeab16a85da554a7db4e/crates/ethereum/node/src/node.rs#L443
 // Changes are made to EPOCH_SLOTS (16 slots on Gnosis vs 32 slots on Ethereum)
 
-use std::time::SystemTime;
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
 
+use alloy_consensus::{BlockHeader, Transaction};
 use alloy_eips::eip7840::BlobParams;
+use alloy_primitives::{Address, B256};
+use futures_util::{Stream, StreamExt};
 use reth_chainspec::EthChainSpec;
 use reth_evm::ConfigureEvm;
-use reth_node_api::PrimitivesTy;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_node_api::{BlockTy, HeaderTy, PrimitivesTy};
 use reth_node_builder::{
     components::{PoolBuilder, TxPoolBuilder},
     node::{FullNodeTypes, NodeTypes},
     BuilderContext,
 };
+use reth_primitives_traits::SealedBlock;
+use reth_provider::{CanonStateNotification, CanonStateSubscriptions, ChainSpecProvider};
+use reth_revm::database::StateProviderDatabase;
+use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore,
+    error::InvalidPoolTransactionError,
+    validate::{EthTransactionValidator, TransactionValidationOutcome, TransactionValidator},
+    CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin,
+    TransactionValidationTaskExecutor,
 };
 use tracing::{debug, info};
 
-use crate::{primitives::GnosisNodePrimitives, spec::gnosis_spec::GnosisChainSpec};
+use crate::{
+    aura::tx_permission::{
+        allowed_tx_types, contract_version, tx_permission_kind, AllowedTxTypes, TxPermission,
+        TxPermissionDenied, TxPermissionError,
+    },
+    primitives::GnosisNodePrimitives,
+    service_tx::{is_service_transaction, ServiceTransactionError, ServiceTxCertifier},
+    spec::gnosis_spec::GnosisChainSpec,
+};
+
+/// Transaction pool of a Gnosis node, validated by [`GnosisTransactionValidator`].
+pub type GnosisTransactionPool<Client, S, Evm, T = EthPooledTransaction> = Pool<
+    TransactionValidationTaskExecutor<GnosisTransactionValidator<Client, T, Evm>>,
+    CoinbaseTipOrdering<T>,
+    S,
+>;
 
 /// Slots per epoch on Gnosis Chain
 const EPOCH_SLOTS: u64 = 16;
 
+/// Blob cache metrics.
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool.blob_cache")]
+struct BlobCacheMetrics {
+    /// Maximum number of blob sidecars kept in the blob store cache.
+    max_entries: Gauge,
+    /// Number of times the blob store cache was resized after a fork changed
+    /// the target blob count.
+    resizes: Counter,
+}
+
+/// Blob store cache entries for `blob_params`.
+///
+/// Derive the blob cache size from the target blob count, to auto scale it by multiplying it
+/// with the slot count for 2 epochs
+fn blob_cache_entries(blob_params: &BlobParams) -> u32 {
+    (blob_params.target_blob_count * EPOCH_SLOTS * 2) as u32
+}
+
+/// Resize the blob store cache whenever the canonical head enters a fork with a different
+/// target blob count, so a node started before the fork does not run undersized until restart.
+async fn maintain_blob_cache_size<St>(
+    chain_spec: Arc<GnosisChainSpec>,
+    blob_store: DiskFileBlobStore,
+    mut canonical_state: St,
+    mut current_size: u32,
+) where
+    St: Stream<Item = CanonStateNotification<GnosisNodePrimitives>> + Unpin,
+{
+    let metrics = BlobCacheMetrics::default();
+    metrics.max_entries.set(current_size as f64);
+
+    while let Some(notification) = canonical_state.next().await {
+        let timestamp = notification.tip().header().timestamp();
+        let Some(blob_params) = chain_spec.blob_params_at_timestamp(timestamp) else {
+            continue;
+        };
+        let size = blob_cache_entries(&blob_params);
+        if size == current_size {
+            continue;
+        }
+
+        info!(
+            target: "reth::gnosis",
+            from = current_size,
+            to = size,
+            timestamp,
+            target_blob_count = blob_params.target_blob_count,
+            "Resizing blob store cache"
+        );
+        blob_store.set_max_cached_entries(size);
+        metrics.max_entries.set(size as f64);
+        metrics.resizes.increment(1);
+        current_size = size;
+    }
+}
+
+/// Per-address lookups made on the state of one block, dropped once they are
+/// asked for on another.
+#[derive(Debug)]
+pub struct BlockCache<V> {
+    entries: Mutex<(B256, HashMap<Address, V>)>,
+}
+
+impl<V> Default for BlockCache<V> {
+    fn default() -> Self {
+        Self {
+            entries: Mutex::new((B256::ZERO, HashMap::new())),
+        }
+    }
+}
+
+impl<V: Clone> BlockCache<V> {
+    /// The value for `address` on the state of block `hash`, if cached.
+    pub fn get(&self, hash: B256, address: Address) -> Option<V> {
+        let (cached_hash, entries) = &mut *self.entries.lock().unwrap();
+        if *cached_hash != hash {
+            *cached_hash = hash;
+            entries.clear();
+        }
+        entries.get(&address).cloned()
+    }
+
+    /// Caches `value` for `address` on the state of block `hash`, unless the
+    /// cache has moved on to another block.
+    pub fn insert(&self, hash: B256, address: Address, value: V) {
+        let (cached_hash, entries) = &mut *self.entries.lock().unwrap();
+        if *cached_hash == hash {
+            entries.insert(address, value);
+        }
+    }
+
+    /// The value for `address` on the state of block `hash`, computed by
+    /// `lookup` on a miss. Failed lookups are not cached.
+    pub fn get_or_try_insert_with<E>(
+        &self,
+        hash: B256,
+        address: Address,
+        lookup: impl FnOnce() -> Result<V, E>,
+    ) -> Result<V, E> {
+        if let Some(value) = self.get(hash, address) {
+            return Ok(value);
+        }
+        let value = lookup()?;
+        self.insert(hash, address, value.clone());
+        Ok(value)
+    }
+}
+
+/// Checks transactions against the AuRa transaction permission contract on
+/// the latest state, as the next block will.
+#[derive(Debug)]
+pub struct TxPermissionFilter<Client, Evm> {
+    client: Client,
+    evm_config: Evm,
+    /// `contractVersion()` per permission contract.
+    versions: BlockCache<u64>,
+    /// `allowedTxTypes` per sender, for answers the contract lets us cache.
+    allowed: BlockCache<AllowedTxTypes>,
+}
+
+impl<Client, Evm> TxPermissionFilter<Client, Evm>
+where
+    Evm: ConfigureEvm,
+    Client: BlockReaderIdExt<Header = HeaderTy<Evm::Primitives>>
+        + StateProviderFactory
+        + ChainSpecProvider<ChainSpec = GnosisChainSpec>,
+{
+    pub fn new(client: Client, evm_config: Evm) -> Self {
+        Self {
+            client,
+            evm_config,
+            versions: BlockCache::default(),
+            allowed: BlockCache::default(),
+        }
+    }
+
+    /// Whether the contract active for the next block allows `sender` to send `tx`.
+    fn check(&self, sender: Address, tx: &impl Transaction) -> Result<(), TxPermissionError> {
+        let header = self
+            .client
+            .latest_header()
+            .map_err(TxPermissionError::call)?
+            .ok_or_else(|| TxPermissionError::Call("no latest header".into()))?;
+        let Some(contract) = self
+            .client
+            .chain_spec()
+            .aura_config
+            .as_ref()
+            .and_then(|aura| aura.transaction_permission_contract_at(header.number() + 1))
+        else {
+            return Ok(());
+        };
+        let hash = header.hash();
+        let cached = self.allowed.get(hash, sender);
+        let deny = |kind, version| -> Result<(), TxPermissionError> {
+            Err(TxPermissionDenied {
+                sender,
+                kind,
+                contract,
+                version,
+            }
+            .into())
+        };
+
+        // A cached answer settles creations, and calls and transfers alike
+        // unless it allows only one of them.
+        if let (Some(allowed), Some(version)) = (cached, self.versions.get(hash, contract)) {
+            let kind = match tx.to() {
+                None => Some(TxPermission::Create),
+                Some(_)
+                    if allowed.allows(TxPermission::Basic)
+                        == allowed.allows(TxPermission::Call) =>
+                {
+                    Some(TxPermission::Basic)
+                }
+                Some(_) => None,
+            };
+            if let Some(kind) = kind {
+                return if allowed.allows(kind) {
+                    Ok(())
+                } else {
+                    deny(kind, version)
+                };
+            }
+        }
+
+        let state = self
+            .client
+            .state_by_block_hash(hash)
+            .map_err(TxPermissionError::call)?;
+        let evm_env = self
+            .evm_config
+            .evm_env(header.header())
+            .map_err(TxPermissionError::call)?;
+        let mut evm = self
+            .evm_config
+            .evm_with_env(StateProviderDatabase::new(state), evm_env);
+        let version = self
+            .versions
+            .get_or_try_insert_with(hash, contract, || contract_version(&mut evm, contract))?;
+        let kind = tx_permission_kind(&mut evm, tx)?;
+        let allowed = match cached {
+            Some(allowed) => allowed,
+            None => {
+                let allowed = allowed_tx_types(&mut evm, contract, version, sender, tx)?;
+                if allowed.cacheable {
+                    self.allowed.insert(hash, sender, allowed);
+                }
+                allowed
+            }
+        };
+        if allowed.allows(kind) {
+            Ok(())
+        } else {
+            deny(kind, version)
+        }
+    }
+}
+
+/// Pool validator applying Gnosis admission rules on top of
+/// [`EthTransactionValidator`]: AuRa transaction permissions and certified
+/// service transactions.
+///
+/// With service transactions enabled the pool runs with a zero minimal
+/// protocol base fee so they reach this validator; every other transaction is
+/// held to `minimal_protocol_basefee` here instead. Admitted service
+/// transactions never cover the base fee and stay parked in the pool's basefee
+/// sub-pool, which the payload builder drains separately.
+///
+/// Rules needing contract calls run only on transactions the Ethereum
+/// validator accepted, inside the validation future like its own state reads.
+#[derive(Debug)]
+pub struct GnosisTransactionValidator<Client, Tx, Evm> {
+    inner: EthTransactionValidator<Client, Tx, Evm>,
+    minimal_protocol_basefee: u64,
+    certifier: Option<ServiceTxCertifier<Client, Evm>>,
+    tx_permissions: Option<TxPermissionFilter<Client, Evm>>,
+}
+
+impl<Client, Tx, Evm> GnosisTransactionValidator<Client, Tx, Evm> {
+    /// Validator rejecting service transactions unless `certifier` is set,
+    /// and checking transaction permissions if `tx_permissions` is set.
+    pub fn new(
+        inner: EthTransactionValidator<Client, Tx, Evm>,
+        minimal_protocol_basefee: u64,
+        certifier: Option<ServiceTxCertifier<Client, Evm>>,
+        tx_permissions: Option<TxPermissionFilter<Client, Evm>>,
+    ) -> Self {
+        Self {
+            inner,
+            minimal_protocol_basefee,
+            certifier,
+            tx_permissions,
+        }
+    }
+
+    /// The wrapped Ethereum validator.
+    pub const fn inner(&self) -> &EthTransactionValidator<Client, Tx, Evm> {
+        &self.inner
+    }
+}
+
+impl<Client, Tx, Evm> GnosisTransactionValidator<Client, Tx, Evm>
+where
+    Tx: PoolTransaction,
+    Evm: ConfigureEvm,
+    Client: BlockReaderIdExt<Header = HeaderTy<Evm::Primitives>>
+        + StateProviderFactory
+        + ChainSpecProvider<ChainSpec = GnosisChainSpec>,
+{
+    /// Gnosis checks that need no state, run before the Ethereum ones.
+    fn check_fees(&self, transaction: &Tx) -> Result<(), InvalidPoolTransactionError> {
+        if !is_service_transaction(transaction) {
+            if transaction.max_fee_per_gas() < self.minimal_protocol_basefee as u128 {
+                return Err(InvalidPoolTransactionError::Underpriced);
+            }
+        } else if self.certifier.is_none() {
+            return Err(InvalidPoolTransactionError::other(
+                ServiceTransactionError::Disabled,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Gnosis checks calling contracts on the latest state, run on
+    /// transactions the Ethereum validator accepted.
+    fn check_sender(&self, transaction: &Tx) -> Result<(), InvalidPoolTransactionError> {
+        let sender = transaction.sender();
+        if let Some(tx_permissions) = &self.tx_permissions {
+            tx_permissions
+                .check(sender, transaction)
+                .map_err(InvalidPoolTransactionError::other)?;
+        }
+
+        match &self.certifier {
+            Some(certifier) if is_service_transaction(transaction) => certifier
+                .check(sender)
+                .map_err(InvalidPoolTransactionError::other),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<Client, Tx, Evm> TransactionValidator for GnosisTransactionValidator<Client, Tx, Evm>
+where
+    Tx: PoolTransaction,
+    Evm: ConfigureEvm,
+    Client: BlockReaderIdExt<Header = HeaderTy<Evm::Primitives>>
+        + StateProviderFactory
+        + ChainSpecProvider<ChainSpec = GnosisChainSpec>
+        + std::fmt::Debug
+        + Send
+        + Sync,
+    EthTransactionValidator<Client, Tx, Evm>:
+        TransactionValidator<Transaction = Tx, Block = BlockTy<Evm::Primitives>>,
+{
+    type Transaction = Tx;
+    type Block = BlockTy<Evm::Primitives>;
+
+    fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> impl Future<Output = TransactionValidationOutcome<Self::Transaction>> + Send {
+        let checked = self.check_fees(&transaction);
+        async move {
+            if let Err(err) = checked {
+                return TransactionValidationOutcome::Invalid(transaction, err);
+            }
+            let outcome = self.inner.validate_transaction(origin, transaction).await;
+            if let TransactionValidationOutcome::Valid { transaction, .. } = &outcome {
+                let transaction = transaction.transaction();
+                if let Err(err) = self.check_sender(transaction) {
+                    return TransactionValidationOutcome::Invalid(transaction.clone(), err);
+                }
+            }
+            outcome
+        }
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock<Self::Block>) {
+        self.inner.on_new_head_block(new_tip_block)
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-pub struct GnosisPoolBuilder {}
+pub struct GnosisPoolBuilder {
+    /// Admit zero-gas-price transactions from senders certified by the
+    /// registrar's Certifier contract.
+    service_transactions: bool,
+}
+
+impl GnosisPoolBuilder {
+    /// Enables or disables admission of certified service transactions.
+    pub const fn with_service_transactions(mut self, enabled: bool) -> Self {
+        self.service_transactions = enabled;
+        self
+    }
+}
 
 impl<Types, Node, Evm> PoolBuilder<Node, Evm> for GnosisPoolBuilder
 where
@@ -33,39 +426,70 @@ where
     Node: FullNodeTypes<Types = Types>,
     Evm: ConfigureEvm<Primitives = PrimitivesTy<Types>> + Clone + 'static,
 {
-    type Pool = EthTransactionPool<Node::Provider, DiskFileBlobStore, Evm>;
+    type Pool = GnosisTransactionPool<Node::Provider, DiskFileBlobStore, Evm>;
 
     async fn build_pool(
         self,
         ctx: &BuilderContext<Node>,
         evm_config: Evm,
     ) -> eyre::Result<Self::Pool> {
-        let pool_config = ctx.pool_config();
+        let mut pool_config = ctx.pool_config();
 
         let blobs_disabled = ctx.config().txpool.disable_blobs_support
             || ctx.config().txpool.blobpool_max_count == 0;
 
-        let blob_cache_size = if let Some(blob_cache_size) = pool_config.blob_cache_size {
-            Some(blob_cache_size)
+        // get the current blob params for the current timestamp, fallback to the chain's Cancun
+        // params
+        let chain_spec = ctx.chain_spec();
+        let current_timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_secs();
+        let blob_params = chain_spec
+            .blob_params_at_timestamp(current_timestamp)
+            .unwrap_or(chain_spec.blob_params.cancun);
+        let blob_cache_size = pool_config
+            .blob_cache_size
+            .unwrap_or_else(|| blob_cache_entries(&blob_params));
+
+        let blob_store = reth_node_builder::components::create_blob_store_with_cache(
+            ctx,
+            Some(blob_cache_size),
+        )?;
+
+        // A user-configured cache size is kept as is; otherwise follow the target blob count
+        // across fork activations
+        if pool_config.blob_cache_size.is_none() && !blobs_disabled {
+            ctx.task_executor().spawn_task(maintain_blob_cache_size(
+                chain_spec.clone(),
+                blob_store.clone(),
+                ctx.provider().canonical_state_stream(),
+                blob_cache_size,
+            ));
+        }
+
+        // Service transactions pay no fees at all, so the pool-wide minimum is lifted and the
+        // validator applies it to every other transaction instead
+        let minimal_protocol_basefee = pool_config.minimal_protocol_basefee;
+        let mut certifier = if self.service_transactions {
+            let registrar = chain_spec
+                .aura_config
+                .as_ref()
+                .and_then(|aura| aura.registrar)
+                .ok_or_else(|| {
+                    eyre::eyre!("service transactions require an `aura.registrar` in genesis")
+                })?;
+            pool_config.minimal_protocol_basefee = 0;
+            info!(target: "reth::cli", %registrar, "Service transactions enabled");
+            Some(ServiceTxCertifier::new(
+                ctx.provider().clone(),
+                evm_config.clone(),
+                registrar,
+            ))
         } else {
-            // get the current blob params for the current timestamp, fallback to default Cancun
-            // params
-            let current_timestamp = SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)?
-                .as_secs();
-            let blob_params = ctx
-                .chain_spec()
-                .blob_params_at_timestamp(current_timestamp)
-                .unwrap_or_else(BlobParams::cancun);
-
-            // Derive the blob cache size from the target blob count, to auto scale it by
-            // multiplying it with the slot count for 2 epochs
-            Some((blob_params.target_blob_count * EPOCH_SLOTS * 2) as u32)
+            None
         };
 
-        let blob_store =
-            reth_node_builder::components::create_blob_store_with_cache(ctx, blob_cache_size)?;
-
+        let permission_evm_config = evm_config.clone();
         let validator =
             TransactionValidationTaskExecutor::eth_builder(ctx.provider().clone(), evm_config)
                 .set_eip4844(!blobs_disabled)
@@ -89,6 +513,20 @@ where
             });
         }
 
+        let mut tx_permissions = chain_spec
+            .aura_config
+            .as_ref()
+            .is_some_and(|aura| aura.transaction_permission_contract.is_some())
+            .then(|| TxPermissionFilter::new(ctx.provider().clone(), permission_evm_config));
+        let validator = validator.map(|validator| {
+            GnosisTransactionValidator::new(
+                validator,
+                minimal_protocol_basefee,
+                certifier.take(),
+                tx_permissions.take(),
+            )
+        });
+
         let transaction_pool = TxPoolBuilder::new(ctx)
             .with_validator(validator)
             .build_and_spawn_maintenance_task(blob_store, pool_config)?;
@@ -99,3 +537,46 @@ where
         Ok(transaction_pool)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn block_cache_is_per_block() {
+        let cache = BlockCache::default();
+        let sender = address!("0x1000000000000000000000000000000000000001");
+        let (block, next_block) = (B256::with_last_byte(1), B256::with_last_byte(2));
+
+        let lookup = |value: bool| move || Ok::<_, ()>(value);
+        assert_eq!(
+            cache.get_or_try_insert_with(block, sender, lookup(true)),
+            Ok(true)
+        );
+        assert_eq!(
+            cache.get_or_try_insert_with(block, sender, lookup(false)),
+            Ok(true)
+        );
+        assert_eq!(
+            cache.get_or_try_insert_with(next_block, sender, lookup(false)),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn block_cache_skips_failed_lookups() {
+        let cache = BlockCache::default();
+        let sender = address!("0x1000000000000000000000000000000000000001");
+        let block = B256::with_last_byte(1);
+
+        assert_eq!(
+            cache.get_or_try_insert_with(block, sender, || Err("down")),
+            Err("down")
+        );
+        assert_eq!(
+            cache.get_or_try_insert_with(block, sender, || Ok::<_, &str>(true)),
+            Ok(true)
+        );
+    }
+}
@@ -1,7 +1,22 @@
 // NOTE: Needed for AddOns
 
+use alloy_consensus::{BlockHeader, Sealed};
+use alloy_network_primitives::HeaderResponse;
+use alloy_primitives::{Address, BlockHash, Bloom, Bytes, FixedBytes, B256, B64, U256};
+use reth::{
+    api::{FullNodeComponents, FullNodeTypes, NodeTypes},
+    tasks::Runtime,
+};
+use reth_node_builder::rpc::{EthApiBuilder, EthApiCtx};
+use reth_node_ethereum::EthereumEthApiBuilder;
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_primitives_traits::SealedHeader;
 use reth_rpc::RpcTypes;
+use reth_rpc_convert::FromConsensusHeader;
+use serde::{Deserialize, Serialize};
 
+use crate::aura::seal::recover_seal_author;
+use crate::evm_config::GnosisEvmConfig;
 use crate::primitives::block::GnosisHeader;
 
 /// The gnosis RPC network types
@@ -10,8 +25,282 @@ use crate::primitives::block::GnosisHeader;
 pub struct GnosisNetwork;
 
 impl RpcTypes for GnosisNetwork {
-    type Header = alloy_rpc_types_eth::Header<GnosisHeader>;
+    type Header = GnosisRpcHeader;
     type Receipt = alloy_rpc_types_eth::TransactionReceipt;
     type TransactionRequest = alloy_rpc_types_eth::transaction::TransactionRequest;
     type TransactionResponse = alloy_rpc_types_eth::Transaction;
 }
+
+/// RPC header. Pre-merge blocks additionally carry Nethermind's AuRa fields:
+/// the seal `author`, `step` and `signature`. Post-merge blocks serialize as
+/// the plain [`alloy_rpc_types_eth::Header`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GnosisRpcHeader {
+    #[serde(flatten)]
+    pub inner: alloy_rpc_types_eth::Header<GnosisHeader>,
+    /// Validator recovered from the AuRa seal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<Address>,
+    /// AuRa step, as a plain JSON number like Nethermind's `BlockForRpc`
+    /// (not a hex quantity).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<u64>,
+    /// AuRa seal signature (`r || s || v`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<FixedBytes<65>>,
+}
+
+impl FromConsensusHeader<GnosisHeader> for GnosisRpcHeader {
+    fn from_consensus_header(header: SealedHeader<GnosisHeader>, block_size: usize) -> Self {
+        // A seal that does not recover cannot be in the database (header
+        // validation rejects it), so `author` is only ever absent post-merge.
+        let author = header
+            .is_pre_merge()
+            .then(|| recover_seal_author(header.header()).ok())
+            .flatten();
+        let step = header.aura_step.and_then(|step| u64::try_from(step).ok());
+        let signature = header.aura_seal;
+        let (header, hash) = header.split();
+        Self {
+            inner: alloy_rpc_types_eth::Header::from_consensus(
+                Sealed::new_unchecked(header, hash),
+                None,
+                Some(U256::from(block_size)),
+            ),
+            author,
+            step,
+            signature,
+        }
+    }
+}
+
+impl HeaderResponse for GnosisRpcHeader {
+    fn hash(&self) -> BlockHash {
+        self.inner.hash
+    }
+}
+
+impl BlockHeader for GnosisRpcHeader {
+    fn parent_hash(&self) -> B256 {
+        self.inner.parent_hash()
+    }
+
+    fn ommers_hash(&self) -> B256 {
+        self.inner.ommers_hash()
+    }
+
+    fn beneficiary(&self) -> Address {
+        self.inner.beneficiary()
+    }
+
+    fn state_root(&self) -> B256 {
+        self.inner.state_root()
+    }
+
+    fn transactions_root(&self) -> B256 {
+        self.inner.transactions_root()
+    }
+
+    fn receipts_root(&self) -> B256 {
+        self.inner.receipts_root()
+    }
+
+    fn withdrawals_root(&self) -> Option<B256> {
+        self.inner.withdrawals_root()
+    }
+
+    fn logs_bloom(&self) -> Bloom {
+        self.inner.logs_bloom()
+    }
+
+    fn difficulty(&self) -> U256 {
+        self.inner.difficulty()
+    }
+
+    fn number(&self) -> u64 {
+        self.inner.number()
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.inner.gas_limit()
+    }
+
+    fn gas_used(&self) -> u64 {
+        self.inner.gas_used()
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.inner.timestamp()
+    }
+
+    fn mix_hash(&self) -> Option<B256> {
+        self.inner.mix_hash()
+    }
+
+    fn nonce(&self) -> Option<B64> {
+        self.inner.nonce()
+    }
+
+    fn base_fee_per_gas(&self) -> Option<u64> {
+        self.inner.base_fee_per_gas()
+    }
+
+    fn blob_gas_used(&self) -> Option<u64> {
+        self.inner.blob_gas_used()
+    }
+
+    fn excess_blob_gas(&self) -> Option<u64> {
+        self.inner.excess_blob_gas()
+    }
+
+    fn parent_beacon_block_root(&self) -> Option<B256> {
+        self.inner.parent_beacon_block_root()
+    }
+
+    fn requests_hash(&self) -> Option<B256> {
+        self.inner.requests_hash()
+    }
+
+    fn extra_data(&self) -> &Bytes {
+        self.inner.extra_data()
+    }
+}
+
+/// Builds reth's `eth` API, which reth's `debug` and `trace` modules replay
+/// blocks through, on the node's components with a [`GnosisEvmConfig`] that
+/// never writes to the AuRa finality cache. Only block import records
+/// finality states; a replay on a cache miss would otherwise store its
+/// receipt-replay recovery of the parent in the shared cache.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct GnosisEthApiBuilder {
+    inner: EthereumEthApiBuilder<GnosisNetwork>,
+}
+
+impl<N> EthApiBuilder<N> for GnosisEthApiBuilder
+where
+    N: FullNodeComponents<Evm = GnosisEvmConfig>,
+    EthereumEthApiBuilder<GnosisNetwork>: EthApiBuilder<ReadOnlyFinality<N>>,
+{
+    type EthApi =
+        <EthereumEthApiBuilder<GnosisNetwork> as EthApiBuilder<ReadOnlyFinality<N>>>::EthApi;
+
+    async fn build_eth_api(self, ctx: EthApiCtx<'_, N>) -> eyre::Result<Self::EthApi> {
+        let components = ReadOnlyFinality::new(ctx.components.clone());
+        self.inner
+            .build_eth_api(EthApiCtx {
+                components: &components,
+                config: ctx.config,
+                cache: ctx.cache,
+            })
+            .await
+    }
+}
+
+/// Node components `N` with their [`GnosisEvmConfig`] switched to
+/// [`GnosisEvmConfig::with_read_only_finality`]. Everything else is `N`'s.
+#[derive(Debug, Clone)]
+pub struct ReadOnlyFinality<N> {
+    inner: N,
+    evm_config: GnosisEvmConfig,
+}
+
+impl<N: FullNodeComponents<Evm = GnosisEvmConfig>> ReadOnlyFinality<N> {
+    pub fn new(inner: N) -> Self {
+        let evm_config = inner.evm_config().clone().with_read_only_finality();
+        Self { inner, evm_config }
+    }
+}
+
+impl<N: FullNodeComponents<Evm = GnosisEvmConfig>> FullNodeTypes for ReadOnlyFinality<N> {
+    type Types = N::Types;
+    type DB = N::DB;
+    type Provider = N::Provider;
+}
+
+impl<N: FullNodeComponents<Evm = GnosisEvmConfig>> FullNodeComponents for ReadOnlyFinality<N> {
+    type Pool = N::Pool;
+    type Evm = GnosisEvmConfig;
+    type Consensus = N::Consensus;
+    type Network = N::Network;
+
+    fn pool(&self) -> &Self::Pool {
+        self.inner.pool()
+    }
+
+    fn evm_config(&self) -> &Self::Evm {
+        &self.evm_config
+    }
+
+    fn consensus(&self) -> &Self::Consensus {
+        self.inner.consensus()
+    }
+
+    fn network(&self) -> &Self::Network {
+        self.inner.network()
+    }
+
+    fn payload_builder_handle(&self) -> &PayloadBuilderHandle<<N::Types as NodeTypes>::Payload> {
+        self.inner.payload_builder_handle()
+    }
+
+    fn provider(&self) -> &Self::Provider {
+        self.inner.provider()
+    }
+
+    fn task_executor(&self) -> &Runtime {
+        self.inner.task_executor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn post_merge_header_keeps_standard_shape() {
+        let header = SealedHeader::seal_slow(GnosisHeader {
+            number: 1,
+            mix_hash: Some(B256::ZERO),
+            nonce: Some(B64::ZERO),
+            ..Default::default()
+        });
+        let rpc = GnosisRpcHeader::from_consensus_header(header.clone(), 500);
+        assert_eq!(rpc.author, None);
+        assert_eq!(
+            serde_json::to_value(&rpc).unwrap(),
+            serde_json::to_value(alloy_rpc_types_eth::Header::from_consensus(
+                Sealed::new_unchecked(header.header().clone(), header.hash()),
+                None,
+                Some(U256::from(500)),
+            ))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn pre_merge_header_carries_aura_fields() {
+        let header = SealedHeader::seal_slow(crate::aura::seal::tests::chiado_block_100k_header());
+        let rpc = GnosisRpcHeader::from_consensus_header(header.clone(), 500);
+        // Chiado block 100000 was sealed by its beneficiary.
+        assert_eq!(
+            rpc.author,
+            Some(alloy_primitives::address!(
+                "0x60f1cf46b42df059b98acf67c1dd7771b100e124"
+            ))
+        );
+
+        let value = serde_json::to_value(&rpc).unwrap();
+        assert_eq!(value["number"], "0x186a0");
+        assert_eq!(
+            value["author"],
+            "0x60f1cf46b42df059b98acf67c1dd7771b100e124"
+        );
+        assert_eq!(value["step"], serde_json::json!(332890827u64));
+        assert_eq!(
+            value["signature"],
+            "0xf6d76ffe58e0ee70301cdf4365d9c1f5ee6de675211e19d8ce23b522ecd940913ac52b0acfece44a38086d783a89fe061e21b1f6ec8860bdbba4bb26a77ace4600"
+        );
+        assert_eq!(value["hash"], serde_json::to_value(header.hash()).unwrap());
+    }
+}
@@ -0,0 +1,288 @@
+//! Service transactions: zero-gas-price transactions from senders whitelisted
+//! by the on-chain Certifier.
+//!
+//! The Certifier is resolved through the AuRa name registrar (`aura.registrar`
+//! in genesis) as `getAddress(keccak256("service_transaction_checker"), "A")`
+//! and answers `certified(address)`. Execution already exempts these
+//! transactions from the base fee in pre-merge London+ blocks (see
+//! [`crate::evm_config::get_cfg_env`]); the pool admits them after asking the
+//! Certifier through [`ServiceTxCertifier`], and refuses them once the next
+//! block is post-merge, where the payload builder no longer includes them.
+
+use std::any::Any;
+
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_primitives::{keccak256, Address};
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolCall;
+use reth_chainspec::EthereumHardforks;
+use reth_evm::{ConfigureEvm, Evm};
+use reth_node_api::HeaderTy;
+use reth_provider::ChainSpecProvider;
+use reth_revm::database::StateProviderDatabase;
+use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
+use reth_transaction_pool::error::PoolTransactionError;
+use revm::context::result::ExecutionResult;
+
+use crate::{pool::BlockCache, spec::gnosis_spec::GnosisChainSpec};
+
+/// Registrar name of the Certifier contract.
+const SERVICE_TRANSACTION_CHECKER: &str = "service_transaction_checker";
+
+/// Registrar key holding a name's address.
+const REGISTRAR_ADDRESS_KEY: &str = "A";
+
+/// Default gas budget for service transactions per block.
+pub const DEFAULT_SERVICE_TX_GAS_LIMIT: u64 = 2_000_000;
+
+sol!(
+    function getAddress(bytes32 name, string key) returns (address);
+);
+
+sol!(
+    function certified(address candidate) returns (bool);
+);
+
+/// Whether `tx` is a service transaction: no gas price and no priority fee,
+/// matching the basefee exemption in `reward_beneficiary`.
+pub fn is_service_transaction(tx: &impl Transaction) -> bool {
+    tx.max_fee_per_gas() == 0 && tx.max_priority_fee_per_gas().unwrap_or_default() == 0
+}
+
+/// Reasons a service transaction is refused by the pool.
+#[derive(Debug, thiserror::Error)]
+pub enum ServiceTransactionError {
+    #[error("service transactions are disabled")]
+    Disabled,
+    #[error("service transactions are not accepted after the merge")]
+    PostMerge,
+    #[error("sender {0} is not certified for service transactions")]
+    NotCertified(Address),
+    #[error("certifier lookup failed: {0}")]
+    Lookup(String),
+}
+
+impl PoolTransactionError for ServiceTransactionError {
+    fn is_bad_transaction(&self) -> bool {
+        // Certification is on-chain state the peer cannot be expected to know.
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Answers `certified(sender)` at the latest block.
+#[derive(Debug)]
+pub struct ServiceTxCertifier<Client, EvmConfig> {
+    client: Client,
+    evm_config: EvmConfig,
+    registrar: Address,
+    cache: BlockCache<bool>,
+}
+
+impl<Client, EvmConfig> ServiceTxCertifier<Client, EvmConfig>
+where
+    EvmConfig: ConfigureEvm,
+    Client: BlockReaderIdExt<Header = HeaderTy<EvmConfig::Primitives>>
+        + StateProviderFactory
+        + ChainSpecProvider<ChainSpec = GnosisChainSpec>,
+{
+    pub fn new(client: Client, evm_config: EvmConfig, registrar: Address) -> Self {
+        Self {
+            client,
+            evm_config,
+            registrar,
+            cache: BlockCache::default(),
+        }
+    }
+
+    /// Whether `sender` may send service transactions in the block after the
+    /// latest one: that block is pre-merge and the Certifier lists `sender`
+    /// on the latest state.
+    pub fn check(&self, sender: Address) -> Result<(), ServiceTransactionError> {
+        let header = self
+            .client
+            .latest_header()
+            .map_err(lookup_error)?
+            .ok_or_else(|| ServiceTransactionError::Lookup("no latest header".into()))?;
+        if self
+            .client
+            .chain_spec()
+            .is_paris_active_at_block(header.number() + 1)
+        {
+            return Err(ServiceTransactionError::PostMerge);
+        }
+
+        let certified = self
+            .cache
+            .get_or_try_insert_with(header.hash(), sender, || {
+                let state = self
+                    .client
+                    .state_by_block_hash(header.hash())
+                    .map_err(lookup_error)?;
+                let evm_env = self
+                    .evm_config
+                    .evm_env(header.header())
+                    .map_err(lookup_error)?;
+                let mut evm = self
+                    .evm_config
+                    .evm_with_env(StateProviderDatabase::new(state), evm_env);
+                is_certified(&mut evm, self.registrar, sender)
+            })?;
+        if !certified {
+            return Err(ServiceTransactionError::NotCertified(sender));
+        }
+        Ok(())
+    }
+}
+
+/// Whether the Certifier registered with `registrar` lists `sender`, on
+/// `evm`'s state. `false` if the registrar has no Certifier.
+pub fn is_certified<E: Evm>(
+    evm: &mut E,
+    registrar: Address,
+    sender: Address,
+) -> Result<bool, ServiceTransactionError> {
+    let mut call = |contract: Address, data: Vec<u8>| {
+        let result = evm
+            .transact_system_call(alloy_eips::eip4788::SYSTEM_ADDRESS, contract, data.into())
+            .map_err(lookup_error)?
+            .result;
+        match result {
+            ExecutionResult::Success { output, .. } => Ok(output.into_data()),
+            other => Err(ServiceTransactionError::Lookup(format!(
+                "call to {contract} did not succeed: {other:?}"
+            ))),
+        }
+    };
+
+    let output = call(
+        registrar,
+        getAddressCall {
+            name: keccak256(SERVICE_TRANSACTION_CHECKER),
+            key: REGISTRAR_ADDRESS_KEY.to_string(),
+        }
+        .abi_encode(),
+    )?;
+    let certifier = getAddressCall::abi_decode_returns(&output).map_err(lookup_error)?;
+    if certifier.is_zero() {
+        return Ok(false);
+    }
+    let output = call(certifier, certifiedCall { candidate: sender }.abi_encode())?;
+    certifiedCall::abi_decode_returns(&output).map_err(lookup_error)
+}
+
+fn lookup_error(err: impl std::fmt::Display) -> ServiceTransactionError {
+    ServiceTransactionError::Lookup(err.to_string())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_consensus::{TxEip1559, TxLegacy};
+    use alloy_primitives::{address, Bytes, U256};
+    use reth_evm::EvmFactory;
+    use revm::{
+        bytecode::Bytecode,
+        database::{CacheDB, EmptyDB},
+        state::AccountInfo,
+    };
+
+    use crate::{evm::factory::GnosisEvmFactory, spec::fee_collector::FeeCollectorSchedule};
+
+    const REGISTRAR: Address = address!("0x6000000000000000000000000000000000000000");
+    const CERTIFIER: Address = address!("0x7000000000000000000000000000000000000000");
+    const SENDER: Address = address!("0x1000000000000000000000000000000000000001");
+
+    /// Code returning `word` for any call.
+    pub(crate) fn returning(word: [u8; 32]) -> Bytecode {
+        // PUSH32 word, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN
+        let mut code = vec![0x7f];
+        code.extend_from_slice(&word);
+        code.extend_from_slice(&[0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3]);
+        Bytecode::new_raw(Bytes::from(code))
+    }
+
+    /// Code reverting every call.
+    pub(crate) fn reverting() -> Bytecode {
+        // PUSH1 0, PUSH1 0, REVERT
+        Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xfd]))
+    }
+
+    pub(crate) fn deploy(db: &mut CacheDB<EmptyDB>, address: Address, code: Bytecode) {
+        db.insert_account_info(
+            address,
+            AccountInfo {
+                balance: U256::ZERO,
+                nonce: 1,
+                code_hash: code.hash_slow(),
+                code: Some(code),
+                account_id: None,
+            },
+        );
+    }
+
+    /// Looks `SENDER` up with the registrar answering `certifier` and, if
+    /// set, the Certifier running `certifier_code`.
+    fn lookup(
+        certifier: Address,
+        certifier_code: Option<Bytecode>,
+    ) -> Result<bool, ServiceTransactionError> {
+        let mut db = CacheDB::new(EmptyDB::default());
+        deploy(&mut db, REGISTRAR, returning(certifier.into_word().0));
+        if let Some(code) = certifier_code {
+            deploy(&mut db, certifier, code);
+        }
+        is_certified(&mut test_evm(db), REGISTRAR, SENDER)
+    }
+
+    /// Gnosis EVM over `db`.
+    pub(crate) fn test_evm(db: CacheDB<EmptyDB>) -> impl Evm<DB = CacheDB<EmptyDB>> {
+        let factory = GnosisEvmFactory {
+            fee_collectors: FeeCollectorSchedule::fixed(Address::ZERO),
+        };
+        factory.create_evm(db, Default::default())
+    }
+
+    #[test]
+    fn detects_service_transactions() {
+        assert!(is_service_transaction(&TxLegacy::default()));
+        assert!(is_service_transaction(&TxEip1559::default()));
+        assert!(!is_service_transaction(&TxLegacy {
+            gas_price: 1,
+            ..Default::default()
+        }));
+        assert!(!is_service_transaction(&TxEip1559 {
+            max_priority_fee_per_gas: 1,
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn registrar_lookup_calldata() {
+        // getAddress(bytes32,string) selector
+        let data = getAddressCall {
+            name: keccak256(SERVICE_TRANSACTION_CHECKER),
+            key: REGISTRAR_ADDRESS_KEY.to_string(),
+        }
+        .abi_encode();
+        assert_eq!(&data[..4], &[0x67, 0x95, 0xdb, 0xcd][..]);
+    }
+
+    #[test]
+    fn asks_the_registered_certifier() {
+        let mut yes = [0; 32];
+        yes[31] = 1;
+        assert!(lookup(CERTIFIER, Some(returning(yes))).unwrap());
+        assert!(!lookup(CERTIFIER, Some(returning([0; 32]))).unwrap());
+        // No Certifier in the registrar.
+        assert!(!lookup(Address::ZERO, None).unwrap());
+        // A failing Certifier is a lookup error, not a refusal.
+        assert!(matches!(
+            lookup(CERTIFIER, Some(reverting())),
+            Err(ServiceTransactionError::Lookup(_))
+        ));
+    }
+}
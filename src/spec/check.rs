@@ -0,0 +1,185 @@
+//! Validation of the Gnosis-specific genesis `config` fields, backing
+//! `reth gnosis chainspec check`.
+//!
+//! Every check runs independently so a single pass reports all problems of a
+//! chain spec, rather than the first one the node would trip over.
+
+use core::fmt;
+
+use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
+use alloy_genesis::Genesis;
+
+use crate::blobs::{genesis_blob_schedule, gnosis_blob_schedule};
+use crate::spec::fee_collector::{load_fee_collectors, FEE_COLLECTOR_KEY};
+use crate::spec::gnosis_spec::{
+    genesis_address, load_aura_config, load_contract_rewrites, load_merge_block,
+};
+
+/// A problem found in a chain spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSpecProblem {
+    /// Genesis `config` field the problem is in.
+    pub field: String,
+    pub message: String,
+}
+
+impl ChainSpecProblem {
+    fn new(field: impl Into<String>, message: impl fmt::Display) -> Self {
+        Self {
+            field: field.into(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ChainSpecProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Check every Gnosis-specific field of `genesis`: the fee collector
+/// schedule, the block rewards contract, the deposit contract, the merge
+/// block, the `aura` section, the contract rewrite forks and the blob
+/// schedule.
+pub fn check_genesis(genesis: &Genesis) -> Vec<ChainSpecProblem> {
+    let mut problems = Vec::new();
+
+    match load_fee_collectors(genesis) {
+        Ok(Some(_)) => {}
+        Ok(None) => problems.push(ChainSpecProblem::new(
+            FEE_COLLECTOR_KEY,
+            format!("missing `{FEE_COLLECTOR_KEY}` field"),
+        )),
+        Err(err) => problems.push(ChainSpecProblem::new(FEE_COLLECTOR_KEY, err)),
+    }
+    if let Err(err) = genesis_address(genesis, "blockRewardsContract") {
+        problems.push(ChainSpecProblem::new("blockRewardsContract", err));
+    }
+    if genesis.config.deposit_contract_address.is_none() {
+        problems.push(ChainSpecProblem::new(
+            "depositContractAddress",
+            "missing; required to collect deposit requests",
+        ));
+    }
+    if let Err(err) = load_merge_block(genesis) {
+        problems.push(ChainSpecProblem::new("terminalBlockNumber", err));
+    }
+    if let Err(err) = load_aura_config(genesis) {
+        problems.push(ChainSpecProblem::new("aura", err));
+    }
+    if let Err(err) = load_contract_rewrites(genesis) {
+        problems.push(ChainSpecProblem::new("gnosisContractRewrites", err));
+    }
+    check_blob_schedule(genesis, &mut problems);
+
+    problems
+}
+
+/// Genesis `blobSchedule` entries must name a fork with blob parameters or be
+/// a blob-parameter-only fork after Osaka, keyed by its activation timestamp.
+fn check_blob_schedule(genesis: &Genesis, problems: &mut Vec<ChainSpecProblem>) {
+    let schedule = match genesis_blob_schedule(genesis) {
+        Ok(schedule) => schedule,
+        Err(err) => {
+            problems.push(ChainSpecProblem::new("blobSchedule", err));
+            gnosis_blob_schedule()
+        }
+    };
+
+    for name in genesis.config.blob_schedule.keys() {
+        let field = format!("blobSchedule.{name}");
+        if matches!(name.as_str(), "cancun" | "prague" | "osaka") {
+            continue;
+        }
+        let Ok(timestamp) = name.parse::<u64>() else {
+            problems.push(ChainSpecProblem::new(
+                field,
+                "neither a fork with blob parameters nor an activation timestamp; ignored",
+            ));
+            continue;
+        };
+        if genesis
+            .config
+            .osaka_time
+            .is_none_or(|osaka| timestamp <= osaka)
+        {
+            problems.push(ChainSpecProblem::new(
+                field,
+                "blob-parameter-only forks must activate after `osakaTime`",
+            ));
+        }
+    }
+
+    // Pre-`blobSchedule` Gnosis fields, expressed in gas.
+    let cancun = &schedule.cancun;
+    let legacy: [(&str, u128); 4] = [
+        ("minBlobGasPrice", cancun.min_blob_fee),
+        (
+            "maxBlobGasPerBlock",
+            (cancun.max_blob_count * DATA_GAS_PER_BLOB) as u128,
+        ),
+        (
+            "targetBlobGasPerBlock",
+            (cancun.target_blob_count * DATA_GAS_PER_BLOB) as u128,
+        ),
+        ("blobGasPriceUpdateFraction", cancun.update_fraction),
+    ];
+    for (key, expected) in legacy {
+        let Some(value) = genesis.config.extra_fields.get(key) else {
+            continue;
+        };
+        match serde_json::from_value::<u128>(value.clone()) {
+            Ok(value) if value == expected => {}
+            Ok(value) => problems.push(ChainSpecProblem::new(
+                key,
+                format!("{value} differs from the Cancun blob schedule's {expected}"),
+            )),
+            Err(err) => problems.push(ChainSpecProblem::new(key, format!("malformed: {err}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::chains::{CHIADO_GENESIS, GNOSIS_GENESIS};
+    use serde_json::json;
+
+    #[test]
+    fn builtin_chain_specs_pass() {
+        assert_eq!(check_genesis(&GNOSIS_GENESIS), vec![]);
+        assert_eq!(check_genesis(&CHIADO_GENESIS), vec![]);
+    }
+
+    #[test]
+    fn reports_all_problems_at_once() {
+        let mut genesis = GNOSIS_GENESIS.clone();
+        let fields = &mut genesis.config.extra_fields;
+        fields.remove("eip1559collector");
+        fields.insert("blockRewardsContract".to_string(), json!("0x12"));
+        fields.insert("aura".to_string(), json!({ "stepDuration": "soon" }));
+        fields.insert(
+            "gnosisContractRewrites".to_string(),
+            json!([{ "name": "NoActivation" }]),
+        );
+        fields.insert("maxBlobGasPerBlock".to_string(), json!(393216));
+        genesis.config.deposit_contract_address = None;
+
+        let fields: Vec<_> = check_genesis(&genesis)
+            .into_iter()
+            .map(|problem| problem.field)
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                "eip1559collector",
+                "blockRewardsContract",
+                "depositContractAddress",
+                "aura",
+                "gnosisContractRewrites",
+                "maxBlobGasPerBlock",
+            ]
+        );
+    }
+}
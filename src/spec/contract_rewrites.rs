@@ -0,0 +1,373 @@
+//! Genesis-configured contract rewrite forks (`gnosisContractRewrites`).
+//!
+//! Each fork is a named hardfork activated at a block or a timestamp that
+//! irregularly modifies a set of accounts: code, storage slots, balance and
+//! nonce. Every fork is registered in the chain spec as its own
+//! [`GnosisHardfork`], so it shows up in the hardfork listing and in fork IDs,
+//! and [`rewrite_bytecodes`](crate::gnosis::rewrite_bytecodes) applies it at
+//! its activation block.
+//!
+//! ```json
+//! "gnosisContractRewrites": [
+//!     {
+//!         "name": "BalancerFork",
+//!         "timestamp": 1766419900,
+//!         "accounts": {
+//!             "0x506D1f9EFe24f0d47853aDca907EB8d89AE03207": { "code": "0x6080..." },
+//!             "0x1111111111111111111111111111111111111111": {
+//!                 "balance": "0x0",
+//!                 "nonce": 1,
+//!                 "storage": { "0x0": "0x1" }
+//!             }
+//!         }
+//!     }
+//! ]
+//! ```
+//!
+//! An empty `code` string removes the account's code. Fields left out are not
+//! touched.
+
+use std::collections::{BTreeMap, HashSet};
+
+use alloy_primitives::{hex::FromHex, Address, Bytes, U256};
+use reth_chainspec::{EthereumHardfork, ForkCondition};
+use revm_primitives::{B256, KECCAK_EMPTY};
+use revm_state::Bytecode;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::spec::gnosis_spec::GnosisHardfork;
+
+/// Genesis `config` key holding the contract rewrite forks.
+pub const CONTRACT_REWRITES_KEY: &str = "gnosisContractRewrites";
+
+/// Error parsing a contract rewrite fork from genesis.
+#[derive(Debug, thiserror::Error)]
+pub enum ContractRewriteError {
+    #[error("malformed `{key}`: {source}")]
+    Json {
+        key: &'static str,
+        source: serde_json::Error,
+    },
+    #[error("contract rewrite fork `{0}` must set exactly one of `block` or `timestamp`")]
+    Activation(String),
+    #[error("contract rewrite fork `{fork}`: invalid code hex for {address}")]
+    Code { fork: String, address: Address },
+    #[error("contract rewrite fork `{0}` is defined more than once")]
+    DuplicateName(String),
+    #[error("contract rewrite fork name `{0}` is reserved for a built-in hardfork")]
+    ReservedName(String),
+}
+
+/// Modifications applied to one account by a [`ContractRewriteFork`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountRewrite {
+    /// New code. `Some(None)` removes the code; `None` leaves it untouched.
+    pub code: Option<Option<Bytecode>>,
+    /// Storage slots to set.
+    pub storage: BTreeMap<U256, U256>,
+    pub balance: Option<U256>,
+    pub nonce: Option<u64>,
+}
+
+impl AccountRewrite {
+    /// Code hash after the rewrite, if the code is rewritten.
+    pub fn code_hash(&self) -> Option<B256> {
+        self.code
+            .as_ref()
+            .map(|code| code.as_ref().map_or(KECCAK_EMPTY, |code| code.hash_slow()))
+    }
+}
+
+/// A named hardfork irregularly rewriting accounts at its activation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractRewriteFork {
+    pub hardfork: GnosisHardfork,
+    /// [`ForkCondition::Block`] or [`ForkCondition::Timestamp`].
+    pub activation: ForkCondition,
+    pub accounts: BTreeMap<Address, AccountRewrite>,
+}
+
+impl ContractRewriteFork {
+    /// Whether the block `number` at `timestamp`, whose parent has
+    /// `parent_timestamp`, is the fork's activation block. Timestamp forks
+    /// fire on the first block at or past the activation time, so a fork
+    /// whose exact time has no block still applies exactly once.
+    pub fn activates_at(&self, number: u64, parent_timestamp: u64, timestamp: u64) -> bool {
+        match self.activation {
+            ForkCondition::Block(block) => number == block,
+            ForkCondition::Timestamp(time) => parent_timestamp < time && time <= timestamp,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawContractRewriteFork {
+    name: String,
+    #[serde(default)]
+    block: Option<u64>,
+    #[serde(default)]
+    timestamp: Option<u64>,
+    #[serde(default)]
+    accounts: BTreeMap<Address, RawAccountRewrite>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawAccountRewrite {
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    storage: BTreeMap<U256, U256>,
+    #[serde(default)]
+    balance: Option<U256>,
+    #[serde(default)]
+    nonce: Option<u64>,
+}
+
+/// Parse the `gnosisContractRewrites` section. A missing section yields no
+/// forks.
+pub fn parse_contract_rewrites(
+    value: Option<&Value>,
+) -> Result<Vec<ContractRewriteFork>, ContractRewriteError> {
+    let Some(value) = value else {
+        return Ok(Vec::new());
+    };
+    let raw =
+        serde_json::from_value::<Vec<RawContractRewriteFork>>(value.clone()).map_err(|source| {
+            ContractRewriteError::Json {
+                key: CONTRACT_REWRITES_KEY,
+                source,
+            }
+        })?;
+
+    raw.into_iter()
+        .map(|fork| {
+            let activation = match (fork.block, fork.timestamp) {
+                (Some(block), None) => ForkCondition::Block(block),
+                (None, Some(time)) => ForkCondition::Timestamp(time),
+                _ => return Err(ContractRewriteError::Activation(fork.name)),
+            };
+            let accounts = fork
+                .accounts
+                .into_iter()
+                .map(|(address, account)| {
+                    let code = account
+                        .code
+                        .map(|code| parse_code(&fork.name, address, &code))
+                        .transpose()?;
+                    Ok((
+                        address,
+                        AccountRewrite {
+                            code,
+                            storage: account.storage,
+                            balance: account.balance,
+                            nonce: account.nonce,
+                        },
+                    ))
+                })
+                .collect::<Result<_, ContractRewriteError>>()?;
+            Ok(ContractRewriteFork {
+                hardfork: GnosisHardfork::contract_rewrite(&fork.name)?,
+                activation,
+                accounts,
+            })
+        })
+        .collect()
+}
+
+/// Reject forks sharing a name: hardforks are keyed by name, so a duplicate
+/// would silently replace the other fork's activation.
+pub fn check_unique_names(forks: &[ContractRewriteFork]) -> Result<(), ContractRewriteError> {
+    let mut seen = HashSet::new();
+    for fork in forks {
+        let name = fork.hardfork.name();
+        if !seen.insert(name) {
+            return Err(ContractRewriteError::DuplicateName(name.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Hex bytecode of a rewrite; an empty string removes the code.
+pub(crate) fn parse_code(
+    fork: &str,
+    address: Address,
+    code: &str,
+) -> Result<Option<Bytecode>, ContractRewriteError> {
+    if code.is_empty() {
+        return Ok(None);
+    }
+    Bytes::from_hex(code)
+        .map(|code| Some(Bytecode::new_legacy(code)))
+        .map_err(|_| ContractRewriteError::Code {
+            fork: fork.to_string(),
+            address,
+        })
+}
+
+/// Whether `name` belongs to a built-in Ethereum or Gnosis hardfork.
+pub(crate) fn is_reserved_name(name: &str) -> bool {
+    name.parse::<EthereumHardfork>().is_ok() || name == GnosisHardfork::POSDAOActivation.name()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_block_and_timestamp_forks() {
+        let value = json!([
+            {
+                "name": "TokenUpgrade",
+                "block": 100,
+                "accounts": {
+                    "0x1111111111111111111111111111111111111111": {
+                        "code": "0x6080",
+                        "storage": { "0x0": "0x1" },
+                        "balance": "0x10",
+                        "nonce": 1
+                    }
+                }
+            },
+            {
+                "name": "BalancerFork",
+                "timestamp": 1000,
+                "accounts": {
+                    "0x2222222222222222222222222222222222222222": { "code": "" }
+                }
+            }
+        ]);
+        let forks = parse_contract_rewrites(Some(&value)).unwrap();
+        assert_eq!(forks.len(), 2);
+
+        let upgrade = &forks[0];
+        assert_eq!(upgrade.hardfork.name(), "TokenUpgrade");
+        assert_eq!(upgrade.activation, ForkCondition::Block(100));
+        let account = &upgrade.accounts[&Address::from([0x11; 20])];
+        assert_eq!(
+            account.code,
+            Some(Some(Bytecode::new_legacy(Bytes::from_static(&[
+                0x60, 0x80
+            ]))))
+        );
+        assert_eq!(account.storage[&U256::ZERO], U256::from(1));
+        assert_eq!(account.balance, Some(U256::from(16)));
+        assert_eq!(account.nonce, Some(1));
+
+        let balancer = &forks[1];
+        assert_eq!(balancer.hardfork, GnosisHardfork::BalancerFork);
+        assert_eq!(balancer.activation, ForkCondition::Timestamp(1000));
+        let account = &balancer.accounts[&Address::from([0x22; 20])];
+        assert_eq!(account.code_hash(), Some(KECCAK_EMPTY));
+        assert!(account.balance.is_none() && account.nonce.is_none());
+
+        assert!(!balancer.activates_at(5, 990, 999));
+        assert!(balancer.activates_at(5, 999, 1005));
+        assert!(!balancer.activates_at(6, 1005, 1010));
+        assert!(upgrade.activates_at(100, 0, 0));
+        assert!(!upgrade.activates_at(101, 0, 0));
+    }
+
+    #[test]
+    fn returns_errors_instead_of_panicking() {
+        let both = json!([{ "name": "A", "block": 1, "timestamp": 1 }]);
+        assert!(matches!(
+            parse_contract_rewrites(Some(&both)),
+            Err(ContractRewriteError::Activation(_))
+        ));
+
+        let bad_code = json!([{
+            "name": "A",
+            "block": 1,
+            "accounts": { "0x1111111111111111111111111111111111111111": { "code": "0xzz" } }
+        }]);
+        assert!(matches!(
+            parse_contract_rewrites(Some(&bad_code)),
+            Err(ContractRewriteError::Code { .. })
+        ));
+
+        let reserved = json!([{ "name": "Shanghai", "timestamp": 1 }]);
+        assert!(matches!(
+            parse_contract_rewrites(Some(&reserved)),
+            Err(ContractRewriteError::ReservedName(_))
+        ));
+
+        let unknown_field = json!([{ "name": "A", "block": 1, "foo": 1 }]);
+        assert!(matches!(
+            parse_contract_rewrites(Some(&unknown_field)),
+            Err(ContractRewriteError::Json { .. })
+        ));
+
+        let duplicate = json!([
+            { "name": "A", "block": 1 },
+            { "name": "A", "timestamp": 5 }
+        ]);
+        let forks = parse_contract_rewrites(Some(&duplicate)).unwrap();
+        assert!(matches!(
+            check_unique_names(&forks),
+            Err(ContractRewriteError::DuplicateName(_))
+        ));
+    }
+
+    #[test]
+    fn forks_are_registered_as_hardforks() {
+        use crate::spec::gnosis_spec::GnosisChainSpec;
+        use reth_chainspec::{Hardforks, Head};
+
+        let mut genesis = alloy_genesis::Genesis::default();
+        genesis.config.shanghai_time = Some(50);
+        for key in ["eip1559collector", "blockRewardsContract"] {
+            genesis.config.extra_fields.insert(
+                key.to_string(),
+                json!("0x0000000000000000000000000000000000000000"),
+            );
+        }
+        genesis.config.extra_fields.insert(
+            CONTRACT_REWRITES_KEY.to_string(),
+            json!([
+                { "name": "Late", "timestamp": 200 },
+                { "name": "Early", "timestamp": 100 },
+                { "name": "AtBlock", "block": 7 }
+            ]),
+        );
+        let spec = GnosisChainSpec::from_genesis(genesis.clone()).unwrap();
+
+        let late = GnosisHardfork::contract_rewrite("Late").unwrap();
+        assert_eq!(spec.fork(late), ForkCondition::Timestamp(200));
+        let at_block = GnosisHardfork::contract_rewrite("AtBlock").unwrap();
+        assert_eq!(spec.fork(at_block), ForkCondition::Block(7));
+
+        // Timestamp forks are listed in activation order, so fork IDs step
+        // through 50 -> 100 -> 200.
+        let times: Vec<_> = spec
+            .forks_iter()
+            .filter_map(|(_, cond)| cond.as_timestamp())
+            .collect();
+        assert_eq!(times, vec![50, 100, 200]);
+        let head = |number, timestamp| Head {
+            number,
+            timestamp,
+            ..Default::default()
+        };
+        assert_eq!(spec.fork_id(&head(0, 0)).next, 7);
+        assert_eq!(spec.fork_id(&head(7, 60)).next, 100);
+        assert_eq!(spec.fork_id(&head(7, 150)).next, 200);
+
+        assert_eq!(
+            spec.contract_rewrites_at(8, 90, 120)
+                .map(|fork| fork.hardfork.name())
+                .collect::<Vec<_>>(),
+            vec!["Early"]
+        );
+
+        genesis.config.extra_fields.insert(
+            CONTRACT_REWRITES_KEY.to_string(),
+            json!([{ "name": "Broken" }]),
+        );
+        assert!(GnosisChainSpec::from_genesis(genesis).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! Fee collector schedule: the address base fees, and blob fees from Prague
+//! on, are minted to.
+//!
+//! `eip1559collector` is the collector from genesis on. Governance moves it
+//! with the block-keyed `burntContract` map, and with its timestamp-keyed
+//! twin `burntContractTimestamp` after the merge:
+//!
+//! ```json
+//! "eip1559collector": "0x6BBe78ee9e474842Dbd4AB4987b3CeFE88426A92",
+//! "burntContract": { "19040000": "0x6BBe78ee9e474842Dbd4AB4987b3CeFE88426A92" },
+//! "burntContractTimestamp": { "1800000000": "0x1559000000000000000000000000000000000000" }
+//! ```
+
+use std::collections::BTreeMap;
+
+use alloy_genesis::Genesis;
+use reth_chainspec::ForkCondition;
+use revm_primitives::Address;
+
+/// Genesis `config` key holding the initial fee collector.
+pub const FEE_COLLECTOR_KEY: &str = "eip1559collector";
+
+/// Genesis `config` key holding the fee collector per activation block.
+pub const BURNT_CONTRACT_KEY: &str = "burntContract";
+
+/// Genesis `config` key holding the fee collector per activation timestamp.
+pub const BURNT_CONTRACT_TIMESTAMP_KEY: &str = "burntContractTimestamp";
+
+/// Fee collector over the life of the chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeCollectorSchedule {
+    initial: Address,
+    /// Block transitions in block order, then timestamp transitions in
+    /// timestamp order.
+    transitions: Vec<(ForkCondition, Address)>,
+}
+
+impl FeeCollectorSchedule {
+    /// A collector that never changes.
+    pub const fn fixed(address: Address) -> Self {
+        Self {
+            initial: address,
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Fee collector of the block `number` at `timestamp`.
+    pub fn at(&self, number: u64, timestamp: u64) -> Address {
+        self.transitions
+            .iter()
+            .rev()
+            .find(|(activation, _)| {
+                activation.active_at_block(number) || activation.active_at_timestamp(timestamp)
+            })
+            .map_or(self.initial, |(_, address)| *address)
+    }
+}
+
+/// The `key` transition map of `genesis`, in activation order.
+fn transitions(genesis: &Genesis, key: &str) -> eyre::Result<BTreeMap<u64, Address>> {
+    genesis
+        .config
+        .extra_fields
+        .get(key)
+        .map(|value| serde_json::from_value(value.clone()))
+        .transpose()
+        .map_err(|e| eyre::eyre!("malformed `{key}` field: {e}"))
+        .map(Option::unwrap_or_default)
+}
+
+/// Fee collector schedule of `genesis`, or `None` if it has no
+/// `eip1559collector`.
+pub fn load_fee_collectors(genesis: &Genesis) -> eyre::Result<Option<FeeCollectorSchedule>> {
+    let fields = &genesis.config.extra_fields;
+    let blocks = transitions(genesis, BURNT_CONTRACT_KEY)?;
+    let timestamps = transitions(genesis, BURNT_CONTRACT_TIMESTAMP_KEY)?;
+    let Some(initial) = fields.get(FEE_COLLECTOR_KEY) else {
+        return Ok(None);
+    };
+    let initial = serde_json::from_value(initial.clone())
+        .map_err(|e| eyre::eyre!("malformed `{FEE_COLLECTOR_KEY}` field: {e}"))?;
+
+    let transitions = blocks
+        .into_iter()
+        .map(|(block, address)| (ForkCondition::Block(block), address))
+        .chain(
+            timestamps
+                .into_iter()
+                .map(|(timestamp, address)| (ForkCondition::Timestamp(timestamp), address)),
+        )
+        .collect();
+
+    Ok(Some(FeeCollectorSchedule {
+        initial,
+        transitions,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use serde_json::json;
+
+    fn genesis(config: serde_json::Value) -> Genesis {
+        serde_json::from_value(json!({ "config": config, "alloc": {} })).unwrap()
+    }
+
+    #[test]
+    fn resolves_collector_per_block_and_timestamp() {
+        let genesis = genesis(json!({
+            "chainId": 1234,
+            "eip1559collector": "0x1559000000000000000000000000000000000000",
+            "burntContract": {
+                "100": "0x0000000000000000000000000000000000000002",
+                "50": "0x0000000000000000000000000000000000000001"
+            },
+            "burntContractTimestamp": {
+                "2000": "0x0000000000000000000000000000000000000003"
+            }
+        }));
+        let schedule = load_fee_collectors(&genesis).unwrap().unwrap();
+
+        assert_eq!(
+            schedule.at(49, 1000),
+            address!("0x1559000000000000000000000000000000000000")
+        );
+        assert_eq!(
+            schedule.at(99, 1000),
+            address!("0x0000000000000000000000000000000000000001")
+        );
+        assert_eq!(
+            schedule.at(100, 1005),
+            address!("0x0000000000000000000000000000000000000002")
+        );
+        assert_eq!(
+            schedule.at(500, 2000),
+            address!("0x0000000000000000000000000000000000000003")
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_transitions() {
+        assert!(load_fee_collectors(&genesis(json!({
+            "chainId": 1234,
+            "eip1559collector": "0x1559000000000000000000000000000000000000",
+            "burntContract": { "soon": "0x0000000000000000000000000000000000000002" }
+        })))
+        .is_err());
+        assert!(load_fee_collectors(&genesis(json!({
+            "chainId": 1234,
+            "eip1559collector": "0x1559000000000000000000000000000000000000",
+            "burntContractTimestamp": { "2": "0x12" }
+        })))
+        .is_err());
+        assert!(load_fee_collectors(&genesis(json!({ "chainId": 1234 })))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn builtin_burnt_contracts_are_transitions() {
+        use crate::spec::chains::{CHIADO_GENESIS, GNOSIS_GENESIS};
+
+        let gnosis = load_fee_collectors(&GNOSIS_GENESIS).unwrap().unwrap();
+        assert_eq!(
+            gnosis.transitions,
+            vec![(
+                ForkCondition::Block(19_040_000),
+                address!("0x6BBe78ee9e474842Dbd4AB4987b3CeFE88426A92")
+            )]
+        );
+        let chiado = load_fee_collectors(&CHIADO_GENESIS).unwrap().unwrap();
+        assert_eq!(
+            chiado.at(0, 0),
+            address!("0x1559000000000000000000000000000000000000")
+        );
+    }
+}
@@ -4,24 +4,30 @@ use core::fmt::Display;
 use tracing::debug;
 
 use crate::{
-    blobs::gnosis_blob_schedule, consts::parse_balancer_hardfork_config,
+    aura::config::AuraConfig,
+    blobs::genesis_blob_schedule,
+    consts::parse_balancer_hardfork_config,
     primitives::block::GnosisHeader,
+    spec::contract_rewrites::{
+        check_unique_names, is_reserved_name, parse_contract_rewrites, ContractRewriteError,
+        ContractRewriteFork, CONTRACT_REWRITES_KEY,
+    },
+    spec::fee_collector::{load_fee_collectors, FeeCollectorSchedule, FEE_COLLECTOR_KEY},
+    spec::nethermind::parse_chain_spec_json,
 };
 use alloy_eips::eip7840::BlobParams;
 use alloy_genesis::Genesis;
-use derive_more::{Constructor, Deref, Into};
+use derive_more::{Deref, Into};
 use reth_chainspec::{
     make_genesis_header, BaseFeeParams, BaseFeeParamsKind, ChainHardforks, ChainSpec,
     DepositContract, EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition, ForkFilter,
     ForkFilterKey, ForkHash, ForkId, Hardfork, Hardforks, Head,
 };
-use reth_cli::chainspec::{parse_genesis, ChainSpecParser};
-use reth_ethereum_forks::hardfork;
+use reth_cli::chainspec::ChainSpecParser;
 use reth_evm::eth::spec::EthExecutorSpec;
 use reth_network_peers::{parse_nodes, NodeRecord};
 use reth_primitives_traits::SealedHeader;
-use revm_primitives::{b256, Address, FixedBytes, B256, U256};
-use revm_state::Bytecode;
+use revm_primitives::{b256, Address, FixedBytes, U256};
 
 #[derive(Debug, PartialEq, Eq)]
 enum Chain {
@@ -73,21 +79,55 @@ const CHIADO_NODES: &[&str] = &[
     "enode://f7e62226a64a2ccc0ada8b032b33c4389464562f87135a3e0d5bdb814fab717d58db5d142c453b071d08b4e0ffd9c5aff4a6d4441c2041401634f10d7962f885@35.210.126.23:30303",
 ];
 
-hardfork!(
-    /// The name of an gnosis hardfork.
-    ///
-    /// When building a list of hardforks for a chain, it's still expected to mix with
-    /// [`EthereumHardfork`].
-    GnosisHardfork {
-        POSDAOActivation,
-        BalancerFork,
-    }
-);
-
-#[derive(Debug, Clone, Default, Into, Constructor, PartialEq, Eq)]
-pub struct BalancerHardforkConfig {
-    pub activation_time: u64,
-    pub config: Vec<(Address, Option<Bytecode>, B256)>,
+/// The name of an gnosis hardfork.
+///
+/// When building a list of hardforks for a chain, it's still expected to mix with
+/// [`EthereumHardfork`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GnosisHardfork {
+    POSDAOActivation,
+    BalancerFork,
+    /// A `gnosisContractRewrites` fork, named in genesis.
+    ContractRewrite(&'static str),
+}
+
+impl GnosisHardfork {
+    /// Name of the hardfork.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::POSDAOActivation => "POSDAOActivation",
+            Self::BalancerFork => "BalancerFork",
+            Self::ContractRewrite(name) => *name,
+        }
+    }
+
+    /// Hardfork of the contract rewrite fork `name`. `BalancerFork` maps to
+    /// its dedicated variant; other built-in names are rejected.
+    pub fn contract_rewrite(name: &str) -> Result<Self, ContractRewriteError> {
+        if name == Self::BalancerFork.name() {
+            return Ok(Self::BalancerFork);
+        }
+        if is_reserved_name(name) {
+            return Err(ContractRewriteError::ReservedName(name.to_string()));
+        }
+        // Hardfork names are `&'static str`. Genesis is parsed a handful of
+        // times per process, so leaking the few names is bounded.
+        Ok(Self::ContractRewrite(Box::leak(
+            name.to_string().into_boxed_str(),
+        )))
+    }
+}
+
+impl Hardfork for GnosisHardfork {
+    fn name(&self) -> &'static str {
+        Self::name(self)
+    }
+}
+
+impl Display for GnosisHardfork {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
 /// Gnosis chain spec type.
@@ -97,9 +137,15 @@ pub struct GnosisChainSpec {
     #[deref]
     pub inner: ChainSpec,
     pub genesis_header: SealedHeader<GnosisHeader>,
-    pub balancer_hardfork_config: Option<BalancerHardforkConfig>,
+    /// Contract rewrite forks (`gnosisContractRewrites`), each also
+    /// registered in `hardforks`.
+    pub contract_rewrites: Vec<ContractRewriteFork>,
     /// AuRa consensus configuration (parsed from genesis JSON).
-    pub aura_config: Option<crate::aura::config::AuraConfig>,
+    pub aura_config: Option<AuraConfig>,
+    /// Fee collector schedule (`eip1559collector`).
+    pub fee_collectors: FeeCollectorSchedule,
+    /// Global block rewards contract (`blockRewardsContract`).
+    pub block_rewards_contract: Address,
 }
 
 impl EthChainSpec for GnosisChainSpec {
@@ -267,11 +313,25 @@ impl EthExecutorSpec for GnosisChainSpec {
 }
 
 impl From<Genesis> for GnosisChainSpec {
+    /// Panics on a malformed genesis; use [`GnosisChainSpec::from_genesis`]
+    /// for user-supplied files.
     fn from(genesis: Genesis) -> Self {
-        let chain_id = genesis.config.chain_id;
+        Self::from_genesis(genesis).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl GnosisChainSpec {
+    /// Build the chain spec from `genesis`, returning an error if one of the
+    /// Gnosis-specific `config` sections is malformed, or if the fee
+    /// collector or the block rewards contract is missing.
+    pub fn from_genesis(genesis: Genesis) -> eyre::Result<Self> {
+        let aura_config = load_aura_config(&genesis)?;
+        let fee_collectors = load_fee_collectors(&genesis)?
+            .ok_or_else(|| eyre::eyre!("missing `{FEE_COLLECTOR_KEY}` field"))?;
+        let block_rewards_contract = genesis_address(&genesis, "blockRewardsContract")?;
 
         // Block-based hardforks
-        let mainnet_hardfork_opts: [(Box<dyn Hardfork>, Option<u64>); 11] = [
+        let block_hardfork_opts: [(Box<dyn Hardfork>, Option<u64>); 11] = [
             (
                 EthereumHardfork::Homestead.boxed(),
                 genesis.config.homestead_block,
@@ -301,45 +361,9 @@ impl From<Genesis> for GnosisChainSpec {
                 EthereumHardfork::Istanbul.boxed(),
                 genesis.config.istanbul_block,
             ),
-            (GnosisHardfork::POSDAOActivation.boxed(), Some(9186425)),
-            (
-                EthereumHardfork::Berlin.boxed(),
-                genesis.config.berlin_block,
-            ),
-            (
-                EthereumHardfork::London.boxed(),
-                genesis.config.london_block,
-            ),
-        ];
-
-        let chiado_hardfork_opts: [(Box<dyn Hardfork>, Option<u64>); 9] = [
-            (
-                EthereumHardfork::Homestead.boxed(),
-                genesis.config.homestead_block,
-            ),
-            (
-                EthereumHardfork::Tangerine.boxed(),
-                genesis.config.eip150_block,
-            ),
-            (
-                EthereumHardfork::SpuriousDragon.boxed(),
-                genesis.config.eip155_block,
-            ),
-            (
-                EthereumHardfork::Byzantium.boxed(),
-                genesis.config.byzantium_block,
-            ),
             (
-                EthereumHardfork::Constantinople.boxed(),
-                genesis.config.constantinople_block,
-            ),
-            (
-                EthereumHardfork::Petersburg.boxed(),
-                genesis.config.petersburg_block,
-            ),
-            (
-                EthereumHardfork::Istanbul.boxed(),
-                genesis.config.istanbul_block,
+                GnosisHardfork::POSDAOActivation.boxed(),
+                aura_config.as_ref().map(|aura| aura.posdao_transition),
             ),
             (
                 EthereumHardfork::Berlin.boxed(),
@@ -351,34 +375,42 @@ impl From<Genesis> for GnosisChainSpec {
             ),
         ];
 
-        let mut hardforks = match Chain::from_chain_id(chain_id) {
-            Some(Chain::Gnosis) => mainnet_hardfork_opts
-                .into_iter()
-                .filter_map(|(hardfork, opt)| {
-                    opt.map(|block| (hardfork, ForkCondition::Block(block)))
+        let contract_rewrites = load_contract_rewrites(&genesis)?;
+        let rewrite_hardforks = |timestamp_based: bool| {
+            contract_rewrites
+                .iter()
+                .filter(move |fork| {
+                    matches!(fork.activation, ForkCondition::Timestamp(_)) == timestamp_based
                 })
-                .collect::<Vec<_>>(),
-            _ => chiado_hardfork_opts
-                .into_iter()
-                .filter_map(|(hardfork, opt)| {
-                    opt.map(|block| (hardfork, ForkCondition::Block(block)))
+                .map(|fork| {
+                    (
+                        Box::new(fork.hardfork) as Box<dyn Hardfork>,
+                        fork.activation,
+                    )
                 })
-                .collect::<Vec<_>>(),
         };
 
+        let mut hardforks = block_hardfork_opts
+            .into_iter()
+            .filter_map(|(hardfork, opt)| opt.map(|block| (hardfork, ForkCondition::Block(block))))
+            .collect::<Vec<_>>();
+        // Fork IDs assume ascending activations, so block-based contract
+        // rewrites are merged in block order.
+        hardforks.extend(rewrite_hardforks(false));
+        hardforks.sort_by_key(|(_, cond)| match cond {
+            ForkCondition::Block(block) => *block,
+            _ => 0,
+        });
+
         // Paris
-        // For Gnosis chains we know the actual merge block heights from chain history.
-        // We set `activation_block_number` so `is_paris_active_at_block` returns the
-        // correct result, but keep `fork_block: None` so the fork ID computation
-        // EXCLUDES this entry — matching other Gnosis clients (Nethermind, Erigon)
-        // which use TTD-based merge without `mergeNetsplitBlock`.
+        // The merge block comes from genesis (`mergeNetsplitBlock`, or the block after
+        // `terminalBlockNumber`). We set `activation_block_number` so
+        // `is_paris_active_at_block` returns the correct result, but keep `fork_block: None`
+        // so the fork ID computation EXCLUDES this entry — matching other Gnosis clients
+        // (Nethermind, Erigon) which use TTD-based merge without `mergeNetsplitBlock`.
         // Setting `fork_block: Some(...)` would change the fork hash for ALL pre-merge
         // blocks and break P2P compatibility with the canonical chain.
-        let known_merge_block = match Chain::from_chain_id(chain_id) {
-            Some(Chain::Gnosis) => Some(25_349_537u64), // Gnosis mainnet merge
-            Some(Chain::Chiado) => Some(680_930u64),    // Chiado merge
-            _ => genesis.config.merge_netsplit_block,
-        };
+        let known_merge_block = load_merge_block(&genesis)?;
         let paris_block_and_final_difficulty =
             if let Some(ttd) = genesis.config.terminal_total_difficulty {
                 hardforks.push((
@@ -396,33 +428,14 @@ impl From<Genesis> for GnosisChainSpec {
                 None
             };
 
-        let balancer_hardfork_config = parse_balancer_hardfork_config(
-            genesis.config.extra_fields.get("balancerHardforkTime"),
-            genesis.config.extra_fields.get("balancerHardforkBytecodes"),
-        );
-
-        // `aura` is optional (devnets / post-merge-since-genesis), but if
-        // present it must parse — silently disabling pre-merge consensus is
-        // a misconfig that must surface at load, not mid-sync.
-        let aura_config = genesis.config.extra_fields.get("aura").map(|v| {
-            crate::aura::config::AuraConfig::from_json_value(v)
-                .unwrap_or_else(|e| panic!("malformed `aura` section in genesis: {e}"))
-        });
-
         // Time-based hardforks
-        let time_hardfork_opts: [(Box<dyn Hardfork>, Option<u64>); 5] = [
+        let time_hardfork_opts: [(Box<dyn Hardfork>, Option<u64>); 4] = [
             (
                 EthereumHardfork::Shanghai.boxed(),
                 genesis.config.shanghai_time,
             ),
             (EthereumHardfork::Cancun.boxed(), genesis.config.cancun_time),
             (EthereumHardfork::Prague.boxed(), genesis.config.prague_time),
-            (
-                GnosisHardfork::BalancerFork.boxed(),
-                balancer_hardfork_config
-                    .as_ref()
-                    .map(|cfg| cfg.activation_time),
-            ),
             (EthereumHardfork::Osaka.boxed(), genesis.config.osaka_time),
         ];
 
@@ -432,6 +445,20 @@ impl From<Genesis> for GnosisChainSpec {
                 opt.map(|time| (hardfork.clone(), ForkCondition::Timestamp(time)))
             })
             .collect::<Vec<_>>();
+        time_hardforks.extend(rewrite_hardforks(true));
+        // Blob-parameter-only forks change consensus rules, so they take part in fork IDs.
+        let blob_params = genesis_blob_schedule(&genesis)?;
+        let bpo_hardforks = [
+            EthereumHardfork::Bpo1,
+            EthereumHardfork::Bpo2,
+            EthereumHardfork::Bpo3,
+            EthereumHardfork::Bpo4,
+            EthereumHardfork::Bpo5,
+        ];
+        time_hardforks.extend(bpo_hardforks.into_iter().zip(&blob_params.scheduled).map(
+            |(hardfork, (timestamp, _))| (hardfork.boxed(), ForkCondition::Timestamp(*timestamp)),
+        ));
+        time_hardforks.sort_by_key(|(_, cond)| cond.as_timestamp());
 
         hardforks.append(&mut time_hardforks);
 
@@ -472,7 +499,7 @@ impl From<Genesis> for GnosisChainSpec {
         }
         let genesis_header = SealedHeader::new_unhashed(genesis_header);
 
-        Self {
+        Ok(Self {
             inner: ChainSpec {
                 chain: genesis.config.chain_id.into(),
                 genesis_header: SealedHeader::new_unhashed(make_genesis_header(
@@ -482,15 +509,105 @@ impl From<Genesis> for GnosisChainSpec {
                 hardforks,
                 paris_block_and_final_difficulty,
                 deposit_contract,
-                blob_params: gnosis_blob_schedule(),
+                blob_params,
                 base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
                 ..Default::default()
             },
             genesis_header,
-            balancer_hardfork_config,
+            contract_rewrites,
             aura_config,
-        }
+            fee_collectors,
+            block_rewards_contract,
+        })
+    }
+}
+
+/// Address stored in the genesis `config` field `key`.
+pub fn genesis_address(genesis: &Genesis, key: &str) -> eyre::Result<Address> {
+    let value = genesis
+        .config
+        .extra_fields
+        .get(key)
+        .ok_or_else(|| eyre::eyre!("missing `{key}` field"))?;
+    serde_json::from_value(value.clone()).map_err(|e| eyre::eyre!("malformed `{key}` field: {e}"))
+}
+
+/// Contract rewrite forks of `genesis`: the `gnosisContractRewrites` section
+/// plus the legacy Balancer keys, if present.
+pub fn load_contract_rewrites(
+    genesis: &Genesis,
+) -> Result<Vec<ContractRewriteFork>, ContractRewriteError> {
+    let mut contract_rewrites =
+        parse_contract_rewrites(genesis.config.extra_fields.get(CONTRACT_REWRITES_KEY))?;
+    contract_rewrites.extend(parse_balancer_hardfork_config(
+        genesis.config.extra_fields.get("balancerHardforkTime"),
+        genesis.config.extra_fields.get("balancerHardforkBytecodes"),
+    )?);
+    check_unique_names(&contract_rewrites)?;
+    Ok(contract_rewrites)
+}
+
+/// First post-merge block of `genesis`: `mergeNetsplitBlock`, or the block
+/// after `terminalBlockNumber` (the last pre-merge block).
+pub fn load_merge_block(genesis: &Genesis) -> eyre::Result<Option<u64>> {
+    if let Some(block) = genesis.config.merge_netsplit_block {
+        return Ok(Some(block));
     }
+    genesis
+        .config
+        .extra_fields
+        .get("terminalBlockNumber")
+        .map(|value| {
+            serde_json::from_value::<u64>(value.clone())
+                .map(|block| block + 1)
+                .map_err(|e| eyre::eyre!("malformed `terminalBlockNumber` field: {e}"))
+        })
+        .transpose()
+}
+
+/// The `aura` section of `genesis`.
+///
+/// `aura` is optional (devnets / post-merge-since-genesis), but if present it
+/// must parse — silently disabling pre-merge consensus is a misconfig that
+/// must surface at load, not mid-sync.
+pub fn load_aura_config(genesis: &Genesis) -> eyre::Result<Option<AuraConfig>> {
+    let fields = &genesis.config.extra_fields;
+    fields
+        .get("aura")
+        .map(|v| {
+            let mut aura = v.clone();
+            if let Some(aura) = aura.as_object_mut() {
+                for key in TOP_LEVEL_AURA_KEYS {
+                    if let Some(value) = fields.get(key) {
+                        aura.entry(key).or_insert_with(|| value.clone());
+                    }
+                }
+            }
+            AuraConfig::from_json_value(&aura)
+                .map_err(|e| eyre::eyre!("malformed `aura` section in genesis: {e}"))
+        })
+        .transpose()
+}
+
+/// AuRa settings Nethermind chain specs keep in the top-level `params`,
+/// accepted at the top of the genesis `config` as well as in `aura`.
+const TOP_LEVEL_AURA_KEYS: [&str; 2] = [
+    "transactionPermissionContract",
+    "transactionPermissionContractTransition",
+];
+
+/// Genesis of the `dev` chain. It deploys no Gnosis system contracts, so
+/// fees are collected at the zero address and the reward call to it, which
+/// has no code, pays nothing.
+fn dev_genesis() -> Genesis {
+    let mut genesis = Genesis::default();
+    for key in [FEE_COLLECTOR_KEY, "blockRewardsContract"] {
+        genesis.config.extra_fields.insert(
+            key.to_string(),
+            serde_json::to_value(Address::ZERO).unwrap(),
+        );
+    }
+    genesis
 }
 
 /// Gnosis chain specification parser.
@@ -511,13 +628,14 @@ impl ChainSpecParser for GnosisChainSpecParser {
 /// Clap value parser for [`GnosisChainSpec`]s.
 ///
 /// The value parser matches either a known chain, the path
-/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct or a
+/// Nethermind / OpenEthereum chain spec.
 pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<GnosisChainSpec>, eyre::Error> {
     Ok(match s {
-        "dev" => Arc::new(GnosisChainSpec::from(Genesis::default())),
+        "dev" => Arc::new(GnosisChainSpec::from_genesis(dev_genesis())?),
         "chiado" => Arc::new(GnosisChainSpec::from(CHIADO_GENESIS.clone())),
         "gnosis" => Arc::new(GnosisChainSpec::from(GNOSIS_GENESIS.clone())),
-        _ => Arc::new(parse_genesis(s)?.into()),
+        _ => Arc::new(GnosisChainSpec::from_genesis(parse_chain_spec_json(s)?)?),
     })
 }
 
@@ -533,6 +651,19 @@ impl GnosisHardForks for GnosisChainSpec {
 }
 
 impl GnosisChainSpec {
+    /// Contract rewrite forks activated by block `number` at `timestamp`,
+    /// whose parent has `parent_timestamp`.
+    pub fn contract_rewrites_at(
+        &self,
+        number: u64,
+        parent_timestamp: u64,
+        timestamp: u64,
+    ) -> impl Iterator<Item = &ContractRewriteFork> {
+        self.contract_rewrites
+            .iter()
+            .filter(move |fork| fork.activates_at(number, parent_timestamp, timestamp))
+    }
+
     /// Log fork IDs for all hardforks, including future ones
     pub fn log_all_fork_ids(&self) {
         debug!(target: "reth::gnosis", "=== Fork IDs for all hardforks ===");
@@ -599,3 +730,130 @@ impl GnosisChainSpec {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fork_id_at(spec: &GnosisChainSpec, number: u64, timestamp: u64) -> ForkId {
+        spec.fork_id(&Head {
+            number,
+            timestamp,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn builtin_fork_ids() {
+        let gnosis = GnosisChainSpec::from(GNOSIS_GENESIS.clone());
+        assert_eq!(
+            fork_id_at(&gnosis, 0, 0),
+            ForkId {
+                hash: ForkHash([0xf6, 0x49, 0x09, 0xb1]),
+                next: 1_604_400
+            }
+        );
+        assert_eq!(
+            fork_id_at(&gnosis, 9_186_425, 0),
+            ForkId {
+                hash: ForkHash([0xb6, 0xe6, 0xcd, 0x81]),
+                next: 16_101_500
+            }
+        );
+        assert_eq!(
+            fork_id_at(&gnosis, 40_000_000, 1_746_021_820),
+            ForkId {
+                hash: ForkHash([0x2f, 0x09, 0x5d, 0x4a]),
+                next: 1_776_168_380
+            }
+        );
+        assert!(!gnosis.is_paris_active_at_block(25_349_536));
+        assert!(gnosis.is_paris_active_at_block(25_349_537));
+
+        let chiado = GnosisChainSpec::from(CHIADO_GENESIS.clone());
+        assert_eq!(
+            fork_id_at(&chiado, 0, 0),
+            ForkId {
+                hash: ForkHash([0x50, 0xd3, 0x9d, 0x7b]),
+                next: 1_684_934_220
+            }
+        );
+        assert!(!chiado.is_paris_active_at_block(680_929));
+        assert!(chiado.is_paris_active_at_block(680_930));
+    }
+
+    #[test]
+    fn executor_fields_are_required() {
+        for key in ["eip1559collector", "blockRewardsContract"] {
+            let mut genesis = CHIADO_GENESIS.clone();
+            genesis.config.extra_fields.remove(key);
+            let err = GnosisChainSpec::from_genesis(genesis.clone()).unwrap_err();
+            assert!(err.to_string().contains(key), "{err}");
+
+            genesis
+                .config
+                .extra_fields
+                .insert(key.to_string(), json!("not an address"));
+            let err = chain_value_parser(&serde_json::to_string(&genesis).unwrap()).unwrap_err();
+            assert!(err.to_string().contains(key), "{err}");
+        }
+
+        let dev = chain_value_parser("dev").unwrap();
+        assert_eq!(dev.block_rewards_contract, Address::ZERO);
+    }
+
+    #[test]
+    fn custom_network_schedule_from_genesis() {
+        let mut genesis = CHIADO_GENESIS.clone();
+        genesis.config.chain_id = 1234;
+        genesis.config.dao_fork_block = Some(5);
+        genesis.config.berlin_block = Some(20);
+        genesis.config.london_block = Some(30);
+        genesis.config.merge_netsplit_block = None;
+        let fields = &mut genesis.config.extra_fields;
+        fields.insert("terminalBlockNumber".to_string(), json!(99));
+        fields.get_mut("aura").unwrap()["posdaoTransition"] = json!(10);
+
+        let spec = GnosisChainSpec::from_genesis(genesis).unwrap();
+        assert_eq!(spec.fork(EthereumHardfork::Dao), ForkCondition::Block(5));
+        assert_eq!(
+            spec.fork(GnosisHardfork::POSDAOActivation),
+            ForkCondition::Block(10)
+        );
+        assert!(!spec.is_paris_active_at_block(99));
+        assert!(spec.is_paris_active_at_block(100));
+
+        let activations: Vec<_> = spec
+            .forks_iter()
+            .filter_map(|(_, cond)| match cond {
+                ForkCondition::Block(block) if block > 0 => Some(block),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(activations, vec![5, 10, 20, 30]);
+    }
+
+    #[test]
+    fn reads_top_level_permission_contract() {
+        let mut genesis = CHIADO_GENESIS.clone();
+        let fields = &mut genesis.config.extra_fields;
+        fields.insert(
+            "transactionPermissionContract".to_string(),
+            json!("0x4000000000000000000000000000000000000001"),
+        );
+        fields.insert(
+            "transactionPermissionContractTransition".to_string(),
+            json!("0xa"),
+        );
+
+        let aura = load_aura_config(&genesis).unwrap().unwrap();
+        assert_eq!(aura.transaction_permission_contract_at(9), None);
+        assert_eq!(
+            aura.transaction_permission_contract_at(10),
+            Some(alloy_primitives::address!(
+                "0x4000000000000000000000000000000000000001"
+            ))
+        );
+    }
+}
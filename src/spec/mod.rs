@@ -1,2 +1,6 @@
 pub mod chains;
+pub mod check;
+pub mod contract_rewrites;
+pub mod fee_collector;
 pub mod gnosis_spec;
+pub mod nethermind;
@@ -0,0 +1,512 @@
+//! Conversion of Nethermind / OpenEthereum chain spec JSON into the genesis
+//! format [`GnosisChainSpec`](super::gnosis_spec::GnosisChainSpec) is built
+//! from.
+//!
+//! Gnosis and Chiado are specified upstream in the Nethermind format
+//! (`engine.authorityRound.params`, `params.eip*Transition`, `accounts`).
+//! Mapping it here lets `--chain` point straight at those files instead of a
+//! hand-converted genesis.
+
+use alloy_genesis::Genesis;
+use alloy_primitives::U256;
+use eyre::{eyre, WrapErr};
+use serde_json::{json, Map, Value};
+
+/// Genesis block forks, the Nethermind transitions that activate them and
+/// the block they activate at when the spec sets none of them. Nethermind
+/// enables Homestead, EIP-150 and EIP-155 from genesis by default; any other
+/// fork without a transition never activates and is left out.
+const BLOCK_FORKS: &[(&str, &[&str], Option<u64>)] = &[
+    ("homesteadBlock", &["homesteadTransition"], Some(0)),
+    ("eip150Block", &["eip150Transition"], Some(0)),
+    ("eip155Block", &["eip155Transition"], Some(0)),
+    ("byzantiumBlock", &["eip140Transition"], None),
+    ("constantinopleBlock", &["eip145Transition"], None),
+    (
+        "petersburgBlock",
+        &["eip1283DisableTransition", "eip145Transition"],
+        None,
+    ),
+    ("istanbulBlock", &["eip1344Transition"], None),
+    ("berlinBlock", &["eip2929Transition"], None),
+    ("londonBlock", &["eip1559Transition"], None),
+];
+
+/// Genesis timestamp forks and the Nethermind transitions that activate them.
+/// All transitions of a fork share one timestamp; the first one present wins.
+const TIMESTAMP_FORKS: &[(&str, &[&str])] = &[
+    (
+        "shanghaiTime",
+        &["eip3855TransitionTimestamp", "eip4895TransitionTimestamp"],
+    ),
+    (
+        "cancunTime",
+        &["eip4844TransitionTimestamp", "eip4788TransitionTimestamp"],
+    ),
+    (
+        "pragueTime",
+        &["eip7702TransitionTimestamp", "eip7685TransitionTimestamp"],
+    ),
+    (
+        "osakaTime",
+        &["eip7594TransitionTimestamp", "eip7939TransitionTimestamp"],
+    ),
+];
+
+/// Pre-`blobSchedule` blob parameters, renamed to the genesis keys.
+const BLOB_PARAMS: &[(&str, &str)] = &[
+    ("minBlobGasPrice", "eip4844MinBlobGasPrice"),
+    ("maxBlobGasPerBlock", "eip4844MaxBlobGasPerBlock"),
+    ("targetBlobGasPerBlock", "eip4844TargetBlobGasPerBlock"),
+    (
+        "blobGasPriceUpdateFraction",
+        "eip4844BlobGasPriceUpdateFraction",
+    ),
+];
+
+/// Genesis header fields, which keep their name apart from `author`.
+const HEADER_FIELDS: &[(&str, &str)] = &[
+    ("baseFeePerGas", "baseFeePerGas"),
+    ("difficulty", "difficulty"),
+    ("gasLimit", "gasLimit"),
+    ("timestamp", "timestamp"),
+    ("extraData", "extraData"),
+    ("parentHash", "parentHash"),
+    ("mixHash", "mixHash"),
+    ("nonce", "nonce"),
+    ("coinbase", "author"),
+    ("seal", "seal"),
+];
+
+/// Whether `value` is a Nethermind / OpenEthereum chain spec rather than a
+/// genesis file.
+pub fn is_nethermind_chain_spec(value: &Value) -> bool {
+    value.get("config").is_none() && value.get("engine").is_some() && value.get("params").is_some()
+}
+
+/// Read a genesis or Nethermind chain spec from the file at `s`, or from `s`
+/// itself if it is inline JSON.
+pub fn parse_chain_spec_json(s: &str) -> eyre::Result<Genesis> {
+    let raw = match std::fs::read_to_string(s) {
+        Ok(raw) => raw,
+        // valid json may start with "\n", but must contain "{"
+        Err(_) if s.contains('{') => s.to_string(),
+        Err(err) => return Err(eyre!("failed to read {s}: {err}")),
+    };
+    let value: Value = serde_json::from_str(&raw)?;
+    if is_nethermind_chain_spec(&value) {
+        return nethermind_to_genesis(&value);
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Map a Nethermind / OpenEthereum chain spec to the equivalent genesis.
+///
+/// Covers the fork transitions, the merge block, the AuRa engine parameters
+/// (with the registrar and transaction permission contract from `params`),
+/// the fee collector, block rewards and deposit contracts, blob parameters,
+/// the genesis header and the account allocations. Builtin-only accounts
+/// (precompiles) are skipped.
+pub fn nethermind_to_genesis(spec: &Value) -> eyre::Result<Genesis> {
+    let params = spec
+        .get("params")
+        .and_then(Value::as_object)
+        .ok_or_else(|| eyre!("missing `params` section"))?;
+    let aura = spec.pointer("/engine/authorityRound/params");
+
+    let mut config = Map::new();
+    let chain_id = params
+        .get("chainID")
+        .or_else(|| params.get("networkID"))
+        .ok_or_else(|| eyre!("missing `params.networkID`"))?;
+    config.insert("chainId".into(), json!(quantity("chainID", chain_id)?));
+    if aura.is_some() {
+        config.insert("consensus".into(), json!("aura"));
+    }
+
+    for (genesis_key, transitions, default) in BLOCK_FORKS {
+        if let Some(block) = first_quantity(params, transitions)?.or(*default) {
+            config.insert((*genesis_key).into(), json!(block));
+        }
+    }
+    for (genesis_key, transitions) in TIMESTAMP_FORKS {
+        if let Some(timestamp) = first_quantity(params, transitions)? {
+            config.insert((*genesis_key).into(), json!(timestamp));
+        }
+    }
+
+    if let Some(ttd) = params.get("terminalTotalDifficulty") {
+        config.insert("terminalTotalDifficulty".into(), ttd.clone());
+        // Only a zero TTD is known to be passed; otherwise the merge block
+        // comes from `terminalBlockNumber` below.
+        if quantity("terminalTotalDifficulty", ttd).is_ok_and(|ttd| ttd == 0) {
+            config.insert("terminalTotalDifficultyPassed".into(), json!(true));
+        }
+    }
+    if let Some(block) = params.get("terminalBlockNumber") {
+        config.insert(
+            "terminalBlockNumber".into(),
+            json!(quantity("terminalBlockNumber", block)?),
+        );
+    }
+    if let Some(collector) = params.get("feeCollector") {
+        let transition = first_quantity(
+            params,
+            &["eip1559FeeCollectorTransition", "eip1559Transition"],
+        )?
+        .unwrap_or(0);
+        config.insert(
+            "burntContract".into(),
+            json!({ transition.to_string(): collector }),
+        );
+        config.insert("eip1559collector".into(), collector.clone());
+    }
+    if let Some(deposit_contract) = params.get("depositContractAddress") {
+        config.insert("depositContractAddress".into(), deposit_contract.clone());
+    }
+    for (genesis_key, param) in BLOB_PARAMS {
+        if let Some(value) = params.get(*param) {
+            config.insert((*genesis_key).into(), json!(quantity(param, value)?));
+        }
+    }
+    if let Some(blob_schedule) = params.get("blobSchedule") {
+        config.insert("blobSchedule".into(), blob_schedule.clone());
+    }
+
+    if let Some(aura) = aura {
+        if let Some(contract) = block_rewards_contract(aura) {
+            config.insert("blockRewardsContract".into(), contract.clone());
+        }
+        let mut aura = aura.clone();
+        if let Some(aura) = aura.as_object_mut() {
+            if let Some(registrar) = params.get("registrar") {
+                aura.entry("registrar").or_insert_with(|| registrar.clone());
+            }
+            if let Some(contract) = params.get("transactionPermissionContract") {
+                aura.entry("transactionPermissionContract")
+                    .or_insert_with(|| contract.clone());
+            }
+            if let Some(transition) = params.get("transactionPermissionContractTransition") {
+                let transition = quantity("transactionPermissionContractTransition", transition)?;
+                aura.entry("transactionPermissionContractTransition")
+                    .or_insert_with(|| json!(transition));
+            }
+        }
+        config.insert("aura".into(), aura);
+    }
+
+    let mut genesis = Map::new();
+    genesis.insert("config".into(), Value::Object(config));
+    if let Some(header) = spec.get("genesis").and_then(Value::as_object) {
+        for (genesis_key, spec_key) in HEADER_FIELDS {
+            if let Some(value) = header.get(*spec_key) {
+                genesis.insert((*genesis_key).into(), value.clone());
+            }
+        }
+    }
+    genesis.insert("alloc".into(), Value::Object(accounts_to_alloc(spec)?));
+
+    serde_json::from_value(Value::Object(genesis))
+        .wrap_err("converted Nethermind chain spec is not a valid genesis")
+}
+
+/// The post-merge block rewards contract: the latest of
+/// `blockRewardContractTransitions`, or `blockRewardContractAddress`.
+fn block_rewards_contract(aura: &Value) -> Option<&Value> {
+    let latest = aura
+        .get("blockRewardContractTransitions")
+        .and_then(Value::as_object)
+        .and_then(|transitions| {
+            transitions
+                .iter()
+                .filter_map(|(block, address)| Some((block.parse::<u64>().ok()?, address)))
+                .max_by_key(|(block, _)| *block)
+                .map(|(_, address)| address)
+        });
+    latest.or_else(|| aura.get("blockRewardContractAddress"))
+}
+
+/// Genesis `alloc` from the spec `accounts`, skipping builtin-only entries.
+fn accounts_to_alloc(spec: &Value) -> eyre::Result<Map<String, Value>> {
+    let Some(accounts) = spec.get("accounts").and_then(Value::as_object) else {
+        return Ok(Map::new());
+    };
+
+    let mut alloc = Map::new();
+    for (address, account) in accounts {
+        let account = account
+            .as_object()
+            .ok_or_else(|| eyre!("malformed account {address}"))?;
+        if account.contains_key("constructor") {
+            eyre::bail!("account {address}: `constructor` accounts are not supported");
+        }
+
+        let mut entry = Map::new();
+        for key in ["code", "storage"] {
+            if let Some(value) = account.get(key) {
+                entry.insert(key.into(), value.clone());
+            }
+        }
+        if let Some(balance) = account.get("balance") {
+            entry.insert("balance".into(), json!(balance_quantity(address, balance)?));
+        }
+        if let Some(nonce) = account.get("nonce") {
+            entry.insert("nonce".into(), json!(quantity("nonce", nonce)?));
+        }
+        if entry.is_empty() {
+            continue;
+        }
+        entry.entry("balance").or_insert_with(|| json!("0x0"));
+        alloc.insert(address.clone(), Value::Object(entry));
+    }
+    Ok(alloc)
+}
+
+/// An account balance, which Nethermind also accepts as a decimal string.
+fn balance_quantity(address: &str, value: &Value) -> eyre::Result<U256> {
+    let parsed = match value {
+        Value::Number(number) => number.as_u64().map(U256::from),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    };
+    parsed.ok_or_else(|| eyre!("account {address}: malformed `balance`: {value}"))
+}
+
+/// The first of `keys` present in `params`.
+fn first_quantity(params: &Map<String, Value>, keys: &[&str]) -> eyre::Result<Option<u64>> {
+    keys.iter()
+        .find_map(|key| params.get(*key).map(|value| quantity(key, value)))
+        .transpose()
+}
+
+/// A number given as JSON number, hex string or decimal string.
+fn quantity(key: &str, value: &Value) -> eyre::Result<u64> {
+    let parsed = match value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(s) => match s.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => s.parse().ok(),
+        },
+        _ => None,
+    };
+    parsed.ok_or_else(|| eyre!("malformed `{key}`: {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use std::collections::BTreeMap;
+
+    fn chain_spec() -> Value {
+        json!({
+            "name": "devnet",
+            "engine": {
+                "authorityRound": {
+                    "params": {
+                        "stepDuration": 5,
+                        "blockReward": "0x0",
+                        "validators": {
+                            "multi": { "0": { "list": ["0x14747a698Ec1227e6753026C08B29b4d5D3bC484"] } }
+                        },
+                        "blockRewardContractAddress": "0x2000000000000000000000000000000000000001",
+                        "blockRewardContractTransition": 0,
+                        "blockRewardContractTransitions": {
+                            "100": "0x2000000000000000000000000000000000000002"
+                        },
+                        "posdaoTransition": 0
+                    }
+                }
+            },
+            "params": {
+                "networkID": "0x27d8",
+                "eip140Transition": "0x0",
+                "eip145Transition": "0xa",
+                "eip1344Transition": "0x14",
+                "eip2929Transition": "0x1e",
+                "eip1559Transition": "0x28",
+                "feeCollector": "0x1559000000000000000000000000000000000000",
+                "eip1559FeeCollectorTransition": 40,
+                "registrar": "0x6000000000000000000000000000000000000000",
+                "terminalTotalDifficulty": "0x0",
+                "terminalBlockNumber": "0x31",
+                "eip4844MinBlobGasPrice": "0x3b9aca00",
+                "eip3855TransitionTimestamp": "0x65971a48",
+                "eip4895TransitionTimestamp": "0x65971a48",
+                "eip4844TransitionTimestamp": "0x6b49d200",
+                "eip7685TransitionTimestamp": "0x713fb300",
+                "depositContractAddress": "0xbabe2bed00000000000000000000000000000003"
+            },
+            "genesis": {
+                "baseFeePerGas": "0x3b9aca00",
+                "difficulty": "0x01",
+                "gasLimit": "0x989680"
+            },
+            "accounts": {
+                "0xB03a86b3126157C039b55E21D378587CcFc04d45": { "balance": "0xc9f2c9cd04674edea40000000" },
+                "0x0000000000000000000000000000000000000005": { "builtin": { "name": "modexp" } },
+                "0x0000000000000000000000000000000000001234": { "code": "0x00", "nonce": "0x1" }
+            }
+        })
+    }
+
+    #[test]
+    fn detects_nethermind_chain_spec() {
+        assert!(is_nethermind_chain_spec(&chain_spec()));
+        assert!(!is_nethermind_chain_spec(
+            &json!({ "config": {}, "alloc": {} })
+        ));
+    }
+
+    #[test]
+    fn maps_nethermind_chain_spec_to_genesis() {
+        let genesis = nethermind_to_genesis(&chain_spec()).unwrap();
+        let config = &genesis.config;
+
+        assert_eq!(config.chain_id, 10200);
+        assert_eq!(config.byzantium_block, Some(0));
+        assert_eq!(config.constantinople_block, Some(10));
+        assert_eq!(config.petersburg_block, Some(10));
+        assert_eq!(config.istanbul_block, Some(20));
+        assert_eq!(config.berlin_block, Some(30));
+        assert_eq!(config.london_block, Some(40));
+        assert_eq!(config.shanghai_time, Some(0x65971a48));
+        assert_eq!(config.cancun_time, Some(0x6b49d200));
+        assert_eq!(config.prague_time, Some(0x713fb300));
+        assert_eq!(config.osaka_time, None);
+        assert_eq!(config.terminal_total_difficulty, Some(U256::ZERO));
+        assert_eq!(
+            config.extra_fields.get("terminalBlockNumber"),
+            Some(&json!(49))
+        );
+        assert_eq!(
+            config.deposit_contract_address,
+            Some(address!("0xbabe2bed00000000000000000000000000000003"))
+        );
+        assert_eq!(
+            config.extra_fields.get("eip1559collector"),
+            Some(&json!("0x1559000000000000000000000000000000000000"))
+        );
+        assert_eq!(
+            config.extra_fields.get("blockRewardsContract"),
+            Some(&json!("0x2000000000000000000000000000000000000002"))
+        );
+        assert_eq!(
+            config.extra_fields.get("aura").unwrap()["registrar"],
+            json!("0x6000000000000000000000000000000000000000")
+        );
+        assert_eq!(genesis.base_fee_per_gas, Some(1_000_000_000));
+        assert_eq!(genesis.gas_limit, 10_000_000);
+
+        assert_eq!(genesis.alloc.len(), 2);
+        let contract = &genesis.alloc[&address!("0x0000000000000000000000000000000000001234")];
+        assert_eq!(contract.nonce, Some(1));
+        assert_eq!(contract.balance, U256::ZERO);
+    }
+
+    #[test]
+    fn leaves_out_forks_without_transition() {
+        let mut spec = chain_spec();
+        spec["params"]
+            .as_object_mut()
+            .unwrap()
+            .remove("eip2929Transition");
+        let genesis = nethermind_to_genesis(&spec).unwrap();
+        assert_eq!(genesis.config.berlin_block, None);
+        assert_eq!(genesis.config.london_block, Some(40));
+        // Active from genesis in Nethermind unless configured.
+        assert_eq!(genesis.config.homestead_block, Some(0));
+        assert_eq!(genesis.config.eip155_block, Some(0));
+
+        spec["params"]["terminalTotalDifficulty"] = json!("0x100");
+        let genesis = nethermind_to_genesis(&spec).unwrap();
+        assert!(!genesis.config.terminal_total_difficulty_passed);
+    }
+
+    /// `scripts/networkdata/chainspec.json` (the Nethermind side of the
+    /// local devnet) against `reth_genesis.json`, its hand-made genesis.
+    #[test]
+    fn converts_networkdata_chain_spec_like_reference_genesis() {
+        use crate::aura::config::AuraConfig;
+
+        let spec: Value =
+            serde_json::from_str(include_str!("../../scripts/networkdata/chainspec.json")).unwrap();
+        let reference: Genesis =
+            serde_json::from_str(include_str!("../../scripts/networkdata/reth_genesis.json"))
+                .unwrap();
+        let converted = nethermind_to_genesis(&spec).unwrap();
+
+        let (config, expected) = (&converted.config, &reference.config);
+        assert_eq!(config.chain_id, expected.chain_id);
+        assert_eq!(config.homestead_block, expected.homestead_block);
+        assert_eq!(config.eip150_block, expected.eip150_block);
+        assert_eq!(config.eip155_block, expected.eip155_block);
+        assert_eq!(config.byzantium_block, expected.byzantium_block);
+        assert_eq!(config.constantinople_block, expected.constantinople_block);
+        assert_eq!(config.petersburg_block, expected.petersburg_block);
+        assert_eq!(config.istanbul_block, expected.istanbul_block);
+        assert_eq!(config.berlin_block, expected.berlin_block);
+        assert_eq!(config.london_block, expected.london_block);
+        assert_eq!(config.shanghai_time, expected.shanghai_time);
+        assert_eq!(config.cancun_time, expected.cancun_time);
+        assert_eq!(
+            config.terminal_total_difficulty,
+            expected.terminal_total_difficulty
+        );
+        assert_eq!(
+            config.terminal_total_difficulty_passed,
+            expected.terminal_total_difficulty_passed
+        );
+
+        // Gnosis fields of the reference, the AuRa section compared as parsed
+        // (`"0x0"` and `0` are the same `blockReward`).
+        for (key, value) in &expected.extra_fields {
+            let actual = config.extra_fields.get(key);
+            if key == "aura" {
+                let parse = |value: &Value| AuraConfig::from_json_value(value).unwrap();
+                let aura = parse(actual.unwrap());
+                assert_eq!(
+                    AuraConfig {
+                        transaction_permission_contract: None,
+                        transaction_permission_contract_transition: 0,
+                        ..aura.clone()
+                    },
+                    parse(value)
+                );
+                // Carried over from `params`, which the reference predates.
+                assert_eq!(
+                    aura.transaction_permission_contract,
+                    Some(address!("0x4000000000000000000000000000000000000001"))
+                );
+            } else {
+                assert_eq!(actual, Some(value), "config.{key}");
+            }
+        }
+
+        assert_eq!(converted.base_fee_per_gas, reference.base_fee_per_gas);
+        assert_eq!(converted.difficulty, reference.difficulty);
+        assert_eq!(converted.gas_limit, reference.gas_limit);
+        for (address, account) in &reference.alloc {
+            assert_eq!(
+                converted.alloc.get(address).map(|a| a.balance),
+                Some(account.balance),
+                "alloc {address}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_constructor_accounts() {
+        let mut spec = chain_spec();
+        spec["accounts"]["0x0000000000000000000000000000000000000001"] =
+            json!({ "constructor": "0x00" });
+        assert!(nethermind_to_genesis(&spec).is_err());
+    }
+
+    #[test]
+    fn picks_latest_block_rewards_contract() {
+        let transitions = BTreeMap::from([("9", "0x01"), ("10", "0x02")]);
+        let aura = json!({ "blockRewardContractTransitions": transitions });
+        assert_eq!(block_rewards_contract(&aura), Some(&json!("0x02")));
+    }
+}
@@ -0,0 +1,316 @@
+//! `debug_traceBlockByNumber` / `debug_traceBlockByHash` with an
+//! `includeSystemCalls` option.
+//!
+//! Replaces reth's methods of the same name in `main.rs`. Without the option
+//! (or with it `false`) requests are answered by reth's debug API unchanged.
+//! With it, the block's system calls are traced as well (see
+//! [`super::system_calls`]) and returned as extra entries, interleaved with
+//! the transaction traces in execution order (pre-block calls first,
+//! post-block calls last):
+//!
+//! ```json
+//! { "systemCall": { "phase": "postBlock", "to": "0x…", "input": "0x…" }, "result": { … } }
+//! ```
+
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, Bytes, B256};
+use alloy_rpc_types_eth::TransactionRequest;
+use alloy_rpc_types_trace::geth::{GethDebugTracingOptions, GethTrace, TraceResult};
+use gnosis_primitives::header::GnosisHeader;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::{
+        error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+        ErrorObjectOwned,
+    },
+};
+use reth_rpc_api::DebugApiServer;
+use reth_storage_api::{BlockReaderIdExt, StateProviderFactory, TransactionVariant};
+use serde::{Deserialize, Serialize};
+
+use super::system_calls::{
+    trace_block_system_calls, SystemCallPhase, SystemCallTraceError, SystemCallTraceKind,
+    TracedSystemCall,
+};
+use crate::{evm_config::GnosisEvmConfig, primitives::block::GnosisBlock};
+
+/// Block tracing that can include the executor's system calls.
+#[rpc(server, namespace = "debug")]
+pub trait GnosisDebugApi {
+    /// Traces of the transactions of `block`, and of its system calls if
+    /// `includeSystemCalls` is set.
+    #[method(name = "traceBlockByNumber")]
+    async fn trace_block_by_number(
+        &self,
+        block: BlockNumberOrTag,
+        opts: Option<GnosisTracingOptions>,
+    ) -> RpcResult<Vec<GnosisTraceResult>>;
+
+    /// Traces of the transactions of `block`, and of its system calls if
+    /// `includeSystemCalls` is set.
+    #[method(name = "traceBlockByHash")]
+    async fn trace_block_by_hash(
+        &self,
+        block: B256,
+        opts: Option<GnosisTracingOptions>,
+    ) -> RpcResult<Vec<GnosisTraceResult>>;
+}
+
+/// Geth tracing options plus `includeSystemCalls`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GnosisTracingOptions {
+    #[serde(flatten)]
+    pub tracing: GethDebugTracingOptions,
+    /// Also trace the system calls around the block's transactions. Only the
+    /// default tracer and `callTracer` are supported.
+    #[serde(default)]
+    pub include_system_calls: bool,
+}
+
+/// System call made by the executor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemCall {
+    pub phase: SystemCallPhase,
+    pub to: Address,
+    pub input: Bytes,
+}
+
+/// Trace of a system call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemCallTrace {
+    pub system_call: SystemCall,
+    pub result: GethTrace,
+}
+
+/// Entry of a block trace: a transaction trace as reth returns it, or a
+/// system call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GnosisTraceResult {
+    SystemCall(SystemCallTrace),
+    Transaction(TraceResult),
+}
+
+/// [`GnosisDebugApiServer`] implementation; `debug` is reth's debug API,
+/// which traces the transactions.
+#[derive(Debug, Clone)]
+pub struct GnosisDebugRpc<Provider, Debug> {
+    provider: Provider,
+    evm_config: GnosisEvmConfig,
+    debug: Debug,
+}
+
+impl<Provider, Debug> GnosisDebugRpc<Provider, Debug> {
+    pub fn new(provider: Provider, evm_config: GnosisEvmConfig, debug: Debug) -> Self {
+        Self {
+            provider,
+            evm_config,
+            debug,
+        }
+    }
+}
+
+impl<Provider, Debug> GnosisDebugRpc<Provider, Debug>
+where
+    Provider: BlockReaderIdExt<Header = GnosisHeader, Block = GnosisBlock>
+        + StateProviderFactory
+        + Clone
+        + 'static,
+    Debug: DebugApiServer<TransactionRequest> + Send + Sync + 'static,
+{
+    async fn trace_block(
+        &self,
+        hash: B256,
+        opts: GnosisTracingOptions,
+    ) -> RpcResult<Vec<GnosisTraceResult>> {
+        let GnosisTracingOptions {
+            tracing,
+            include_system_calls,
+        } = opts;
+        if !include_system_calls {
+            let traces = self
+                .debug
+                .debug_trace_block_by_hash(hash, Some(tracing))
+                .await?;
+            return Ok(traces
+                .into_iter()
+                .map(GnosisTraceResult::Transaction)
+                .collect());
+        }
+
+        let kind = SystemCallTraceKind::from_options(&tracing).map_err(invalid_params)?;
+        let tx_traces = self
+            .debug
+            .debug_trace_block_by_hash(hash, Some(tracing))
+            .await?;
+
+        let provider = self.provider.clone();
+        let evm_config = self.evm_config.clone();
+        let config = kind.inspector_config();
+        let calls = tokio::task::spawn_blocking(move || {
+            let block = provider
+                .recovered_block(hash.into(), TransactionVariant::WithHash)
+                .map_err(|err| SystemCallTraceError::Setup(err.to_string()))?
+                .ok_or_else(|| SystemCallTraceError::Setup(format!("block {hash} not found")))?;
+            let state = provider
+                .state_by_block_hash(block.header().parent_hash)
+                .map_err(|err| SystemCallTraceError::Setup(err.to_string()))?;
+            trace_block_system_calls(&evm_config, state, &block, config)
+        })
+        .await
+        .map_err(internal_error)?
+        .map_err(internal_error)?;
+
+        let calls = calls
+            .into_iter()
+            .map(|call| system_call_trace(call, &kind))
+            .collect();
+        Ok(merge_traces(tx_traces, calls))
+    }
+}
+
+#[async_trait]
+impl<Provider, Debug> GnosisDebugApiServer for GnosisDebugRpc<Provider, Debug>
+where
+    Provider: BlockReaderIdExt<Header = GnosisHeader, Block = GnosisBlock>
+        + StateProviderFactory
+        + Clone
+        + 'static,
+    Debug: DebugApiServer<TransactionRequest> + Send + Sync + 'static,
+{
+    async fn trace_block_by_number(
+        &self,
+        block: BlockNumberOrTag,
+        opts: Option<GnosisTracingOptions>,
+    ) -> RpcResult<Vec<GnosisTraceResult>> {
+        let header = self
+            .provider
+            .sealed_header_by_number_or_tag(block)
+            .map_err(internal_error)?
+            .ok_or_else(|| invalid_params(format!("block {block} not found")))?;
+        self.trace_block(header.hash(), opts.unwrap_or_default())
+            .await
+    }
+
+    async fn trace_block_by_hash(
+        &self,
+        block: B256,
+        opts: Option<GnosisTracingOptions>,
+    ) -> RpcResult<Vec<GnosisTraceResult>> {
+        self.trace_block(block, opts.unwrap_or_default()).await
+    }
+}
+
+fn system_call_trace(call: TracedSystemCall, kind: &SystemCallTraceKind) -> SystemCallTrace {
+    let system_call = SystemCall {
+        phase: call.phase,
+        to: call.to(),
+        input: call.input().clone(),
+    };
+    SystemCallTrace {
+        system_call,
+        result: call.into_geth_trace(kind),
+    }
+}
+
+/// Interleave system calls with the transaction traces: pre-block calls
+/// first, post-block calls last.
+fn merge_traces(
+    tx_traces: Vec<TraceResult>,
+    calls: Vec<SystemCallTrace>,
+) -> Vec<GnosisTraceResult> {
+    let (pre_block, post_block): (Vec<_>, Vec<_>) = calls
+        .into_iter()
+        .partition(|call| call.system_call.phase == SystemCallPhase::PreBlock);
+    pre_block
+        .into_iter()
+        .map(GnosisTraceResult::SystemCall)
+        .chain(tx_traces.into_iter().map(GnosisTraceResult::Transaction))
+        .chain(post_block.into_iter().map(GnosisTraceResult::SystemCall))
+        .collect()
+}
+
+fn internal_error(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+fn invalid_params(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INVALID_PARAMS_CODE, err.to_string(), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_trace::geth::DefaultFrame;
+
+    fn call(phase: SystemCallPhase) -> SystemCallTrace {
+        SystemCallTrace {
+            system_call: SystemCall {
+                phase,
+                to: Address::ZERO,
+                input: Bytes::new(),
+            },
+            result: DefaultFrame::default().into(),
+        }
+    }
+
+    fn tx(byte: u8) -> TraceResult {
+        TraceResult::Success {
+            result: DefaultFrame::default().into(),
+            tx_hash: Some(B256::repeat_byte(byte)),
+        }
+    }
+
+    #[test]
+    fn parses_include_system_calls() {
+        let opts: GnosisTracingOptions = serde_json::from_value(serde_json::json!({
+            "tracer": "callTracer",
+            "includeSystemCalls": true,
+        }))
+        .unwrap();
+        assert!(opts.include_system_calls);
+        assert!(opts.tracing.tracer.is_some());
+
+        let opts: GnosisTracingOptions =
+            serde_json::from_value(serde_json::json!({ "disableStorage": true })).unwrap();
+        assert!(!opts.include_system_calls);
+        assert_eq!(opts.tracing.config.disable_storage, Some(true));
+    }
+
+    #[test]
+    fn merges_in_execution_order() {
+        let merged = merge_traces(
+            vec![tx(1), tx(2)],
+            vec![
+                call(SystemCallPhase::PreBlock),
+                call(SystemCallPhase::PreBlock),
+                call(SystemCallPhase::PostBlock),
+            ],
+        );
+        let order: Vec<_> = merged
+            .iter()
+            .map(|entry| match entry {
+                GnosisTraceResult::SystemCall(call) => format!("{:?}", call.system_call.phase),
+                GnosisTraceResult::Transaction(TraceResult::Success { tx_hash, .. }) => {
+                    format!("tx{}", tx_hash.unwrap()[0])
+                }
+                GnosisTraceResult::Transaction(TraceResult::Error { .. }) => unreachable!(),
+            })
+            .collect();
+        assert_eq!(order, ["PreBlock", "PreBlock", "tx1", "tx2", "PostBlock"]);
+    }
+
+    #[test]
+    fn system_call_entry_shape() {
+        let value = serde_json::to_value(GnosisTraceResult::SystemCall(call(
+            SystemCallPhase::PostBlock,
+        )))
+        .unwrap();
+        assert_eq!(value["systemCall"]["phase"], "postBlock");
+        assert!(value.get("result").is_some());
+    }
+}
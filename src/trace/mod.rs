@@ -0,0 +1,6 @@
+//! Gnosis-specific tracing: the executor's system calls, which reth's
+//! per-transaction tracers never see, and the block rewards they pay.
+
+pub mod debug;
+pub mod parity;
+pub mod system_calls;
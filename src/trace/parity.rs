@@ -0,0 +1,694 @@
+//! `trace_block` / `trace_filter` with the rewards Gnosis blocks actually pay.
+//!
+//! reth derives reward traces from Ethereum's proof-of-work schedule, which
+//! does not apply here. These methods replace reth's in `main.rs`: transaction
+//! traces still come from reth's trace API, its reward entries are dropped,
+//! and each block gets its rewards as Nethermind reports them:
+//!
+//! - one `external` entry per `(receiversNative[i], rewardsNative[i])` pair
+//!   returned by the block rewards contract's `reward` call, in contract order
+//!   and not aggregated per receiver;
+//! - a `block` entry for the author of a pre-merge block paid a fixed AuRa
+//!   `blockReward`.
+//!
+//! Contract rewards are not classified further: the contract returns bare
+//! `(receiver, amount)` pairs, the same receiver can be paid as a validator
+//! and as a bridge mint, and Nethermind labels them all `external` too.
+//! Reconcile them by receiver.
+//!
+//! The contract's return data is read by replaying the block (see
+//! [`super::system_calls`]) with read-only finality. Blocks without a
+//! contract with code are not replayed, and `trace_filter` replays no block
+//! past its page or when its addresses select no reward, and covers at most
+//! [`MAX_REWARD_BLOCKS`] blocks. With `includeSystemCalls`, `trace_block` also
+//! returns the traces of the block's system calls, without a transaction
+//! hash or position and tagged with their `systemCallPhase`: pre-block calls
+//! before the transaction traces, post-block calls after them.
+
+use std::collections::HashSet;
+
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types_eth::TransactionRequest;
+use alloy_rpc_types_trace::{
+    filter::TraceFilter,
+    parity::{Action, LocalizedTransactionTrace, RewardAction, RewardType, TransactionTrace},
+};
+use gnosis_primitives::header::GnosisHeader;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::{
+        error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+        ErrorObjectOwned,
+    },
+};
+use reth_chainspec::EthereumHardforks;
+use reth_primitives_traits::SealedHeader;
+use reth_rpc_api::TraceApiServer;
+use reth_storage_api::{AccountReader, BlockReaderIdExt, StateProviderFactory, TransactionVariant};
+use revm_inspectors::tracing::TracingInspectorConfig;
+use serde::{Deserialize, Serialize};
+
+use super::system_calls::{trace_block_system_calls, SystemCallPhase, SystemCallTraceError};
+use crate::{
+    evm_config::GnosisEvmConfig,
+    gnosis::{decode_block_rewards, BLOCK_REWARDS_SELECTOR},
+    primitives::block::GnosisBlock,
+};
+
+/// Blocks `trace_filter` reports rewards for at most, as each may be
+/// replayed.
+pub const MAX_REWARD_BLOCKS: u64 = 100;
+
+/// Parity-style traces with Gnosis block rewards.
+#[rpc(server, namespace = "trace")]
+pub trait GnosisTraceApi {
+    /// Traces of the transactions of `block_id`, followed by its rewards, and
+    /// of its system calls if `includeSystemCalls` is set.
+    #[method(name = "block")]
+    async fn trace_block(
+        &self,
+        block_id: BlockId,
+        opts: Option<GnosisTraceOptions>,
+    ) -> RpcResult<Option<Vec<GnosisLocalizedTrace>>>;
+
+    /// Traces matching `filter`, with each block's rewards after its
+    /// transaction traces.
+    #[method(name = "filter")]
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<GnosisLocalizedTrace>>;
+}
+
+/// Options of `trace_block`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GnosisTraceOptions {
+    /// Also trace the system calls around the block's transactions.
+    #[serde(default)]
+    pub include_system_calls: bool,
+}
+
+/// `rewardType` of a reward trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GnosisRewardType {
+    /// Fixed AuRa `blockReward` paid to the author.
+    Block,
+    /// Paid by the block rewards contract.
+    External,
+}
+
+/// Action of a reward trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GnosisRewardAction {
+    /// Receiver of the reward.
+    pub author: Address,
+    pub reward_type: GnosisRewardType,
+    pub value: U256,
+}
+
+/// `type` of a reward trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RewardTraceType {
+    Reward,
+}
+
+/// Reward trace, shaped like Nethermind's.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardTrace {
+    pub action: GnosisRewardAction,
+    pub block_hash: B256,
+    pub block_number: u64,
+    /// Always `null`.
+    pub result: Option<()>,
+    pub subtraces: usize,
+    pub trace_address: Vec<usize>,
+    pub transaction_hash: Option<B256>,
+    pub transaction_position: Option<u64>,
+    #[serde(rename = "type")]
+    pub trace_type: RewardTraceType,
+}
+
+impl RewardTrace {
+    pub fn new(block_hash: B256, block_number: u64, action: GnosisRewardAction) -> Self {
+        Self {
+            action,
+            block_hash,
+            block_number,
+            result: None,
+            subtraces: 0,
+            trace_address: Vec::new(),
+            transaction_hash: None,
+            transaction_position: None,
+            trace_type: RewardTraceType::Reward,
+        }
+    }
+
+    /// Whether `filter` selects this reward, by the rules reth applies to its
+    /// own reward traces.
+    fn matches(&self, filter: &TraceFilter) -> bool {
+        filter.matcher().matches(&TransactionTrace {
+            action: Action::Reward(RewardAction {
+                author: self.action.author,
+                reward_type: RewardType::Block,
+                value: self.action.value,
+            }),
+            error: None,
+            result: None,
+            subtraces: 0,
+            trace_address: Vec::new(),
+        })
+    }
+}
+
+/// Trace of a call made by a system call, or by one of its subcalls.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemCallTrace {
+    pub system_call_phase: SystemCallPhase,
+    #[serde(flatten)]
+    pub trace: LocalizedTransactionTrace,
+}
+
+/// Entry of a trace response: a transaction trace as reth returns it, a
+/// system call or a Gnosis reward.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GnosisLocalizedTrace {
+    SystemCall(SystemCallTrace),
+    Transaction(LocalizedTransactionTrace),
+    Reward(RewardTrace),
+}
+
+/// [`GnosisTraceApiServer`] implementation; `trace` is reth's trace API,
+/// which traces the transactions.
+#[derive(Debug, Clone)]
+pub struct GnosisTraceRpc<Provider, Trace> {
+    provider: Provider,
+    evm_config: GnosisEvmConfig,
+    trace: Trace,
+}
+
+impl<Provider, Trace> GnosisTraceRpc<Provider, Trace> {
+    pub fn new(provider: Provider, evm_config: GnosisEvmConfig, trace: Trace) -> Self {
+        Self {
+            provider,
+            evm_config,
+            trace,
+        }
+    }
+}
+
+impl<Provider, Trace> GnosisTraceRpc<Provider, Trace>
+where
+    Provider: BlockReaderIdExt<Header = GnosisHeader, Block = GnosisBlock>
+        + StateProviderFactory
+        + Clone
+        + 'static,
+{
+    /// Run `replay` off the async runtime.
+    async fn spawn_replay<T: Send + 'static>(
+        &self,
+        replay: impl FnOnce(&Provider, &GnosisEvmConfig) -> Result<T, SystemCallTraceError>
+            + Send
+            + 'static,
+    ) -> RpcResult<T> {
+        let provider = self.provider.clone();
+        let evm_config = self.evm_config.clone();
+        tokio::task::spawn_blocking(move || replay(&provider, &evm_config))
+            .await
+            .map_err(internal_error)?
+            .map_err(internal_error)
+    }
+}
+
+#[async_trait]
+impl<Provider, Trace> GnosisTraceApiServer for GnosisTraceRpc<Provider, Trace>
+where
+    Provider: BlockReaderIdExt<Header = GnosisHeader, Block = GnosisBlock>
+        + StateProviderFactory
+        + Clone
+        + 'static,
+    Trace: TraceApiServer<TransactionRequest> + Send + Sync + 'static,
+{
+    async fn trace_block(
+        &self,
+        block_id: BlockId,
+        opts: Option<GnosisTraceOptions>,
+    ) -> RpcResult<Option<Vec<GnosisLocalizedTrace>>> {
+        let Some(header) = self
+            .provider
+            .sealed_header_by_id(block_id)
+            .map_err(internal_error)?
+        else {
+            return Ok(None);
+        };
+        // Trace by hash so a moving tag cannot resolve to another block.
+        let Some(traces) = self.trace.trace_block(header.hash().into()).await? else {
+            return Ok(None);
+        };
+        let include_system_calls = opts.unwrap_or_default().include_system_calls;
+        let (calls, rewards) = self
+            .spawn_replay(move |provider, evm_config| {
+                replay_block(provider, evm_config, &header, include_system_calls)
+            })
+            .await?;
+
+        let (pre_block, post_block): (Vec<_>, Vec<_>) = calls
+            .into_iter()
+            .partition(|call| call.system_call_phase == SystemCallPhase::PreBlock);
+        Ok(Some(
+            pre_block
+                .into_iter()
+                .map(GnosisLocalizedTrace::SystemCall)
+                .chain(
+                    traces
+                        .into_iter()
+                        .filter(|trace| !matches!(trace.trace.action, Action::Reward(_)))
+                        .map(GnosisLocalizedTrace::Transaction),
+                )
+                .chain(post_block.into_iter().map(GnosisLocalizedTrace::SystemCall))
+                .chain(rewards.into_iter().map(GnosisLocalizedTrace::Reward))
+                .collect(),
+        ))
+    }
+
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<GnosisLocalizedTrace>> {
+        // Resolve the range once and hand it to reth explicitly, so both
+        // cover the same blocks.
+        let start = filter.from_block.unwrap_or_default();
+        let end = match filter.to_block {
+            Some(end) => end,
+            None => self.provider.best_block_number().map_err(internal_error)?,
+        };
+        if end.saturating_sub(start) >= MAX_REWARD_BLOCKS {
+            return Err(invalid_params(format!(
+                "trace_filter reports rewards of at most {MAX_REWARD_BLOCKS} blocks"
+            )));
+        }
+
+        // Pagination applies to the merged list, so reth returns everything.
+        let (after, count) = (filter.after, filter.count);
+        let filter = TraceFilter {
+            from_block: Some(start),
+            to_block: Some(end),
+            after: None,
+            count: None,
+            ..filter
+        };
+        let traces = self.trace.trace_filter(filter.clone()).await?;
+
+        let after = usize::try_from(after.unwrap_or_default()).unwrap_or(usize::MAX);
+        let limit = count.map_or(usize::MAX, |count| {
+            after.saturating_add(usize::try_from(count).unwrap_or(usize::MAX))
+        });
+        let selects_rewards = selects_rewards(&filter);
+        let merged = self
+            .spawn_replay(move |provider, evm_config| {
+                merge_rewards(traces, start..=end, limit, |number| {
+                    if !selects_rewards {
+                        return Ok(Vec::new());
+                    }
+                    let header = provider
+                        .sealed_header(number)
+                        .map_err(|err| SystemCallTraceError::Setup(err.to_string()))?
+                        .ok_or_else(|| {
+                            SystemCallTraceError::Setup(format!("block {number} not found"))
+                        })?;
+                    let (_, rewards) = replay_block(provider, evm_config, &header, false)?;
+                    Ok(rewards
+                        .into_iter()
+                        .filter(|reward| reward.matches(&filter))
+                        .collect())
+                })
+            })
+            .await?;
+        Ok(merged.into_iter().skip(after).collect())
+    }
+}
+
+/// Rewards paid by the block of `header` and, with `include_system_calls`,
+/// the traces of its system calls.
+///
+/// The block is replayed only if its system calls are asked for or a block
+/// rewards contract with code pays its rewards. Like the executor, a block
+/// whose contract has no code pays no contract rewards, and a return that
+/// does not decode is an error.
+fn replay_block<Provider>(
+    provider: &Provider,
+    evm_config: &GnosisEvmConfig,
+    header: &SealedHeader<GnosisHeader>,
+    include_system_calls: bool,
+) -> Result<(Vec<SystemCallTrace>, Vec<RewardTrace>), SystemCallTraceError>
+where
+    Provider: BlockReaderIdExt<Header = GnosisHeader, Block = GnosisBlock> + StateProviderFactory,
+{
+    // Genesis is not executed.
+    if header.number == 0 {
+        return Ok(Default::default());
+    }
+
+    // The `reward` call is the block's last, so the code it ran into is the
+    // code after the block.
+    let reward_contract = match evm_config.block_reward_contract_at(header.number) {
+        Some(contract) => {
+            let account = provider
+                .state_by_block_hash(header.hash())
+                .and_then(|state| state.basic_account(&contract))
+                .map_err(|err| SystemCallTraceError::Setup(err.to_string()))?;
+            account
+                .is_some_and(|account| account.get_bytecode_hash() != KECCAK_EMPTY)
+                .then_some(contract)
+        }
+        None => None,
+    };
+
+    let calls = if include_system_calls || reward_contract.is_some() {
+        let block = provider
+            .recovered_block(header.hash().into(), TransactionVariant::WithHash)
+            .map_err(|err| SystemCallTraceError::Setup(err.to_string()))?
+            .ok_or_else(|| {
+                SystemCallTraceError::Setup(format!("block {} not found", header.hash()))
+            })?;
+        let state = provider
+            .state_by_block_hash(header.parent_hash)
+            .map_err(|err| SystemCallTraceError::Setup(err.to_string()))?;
+        trace_block_system_calls(
+            evm_config,
+            state,
+            &block,
+            TracingInspectorConfig::default_parity(),
+        )?
+    } else {
+        Vec::new()
+    };
+
+    let contract_rewards = match reward_contract {
+        Some(contract) => {
+            let call = calls
+                .iter()
+                .rev()
+                .find(|call| {
+                    call.phase == SystemCallPhase::PostBlock
+                        && call.to() == contract
+                        && call.input().starts_with(&BLOCK_REWARDS_SELECTOR)
+                })
+                .ok_or_else(|| {
+                    SystemCallTraceError::Rewards(format!("no call to {contract} traced"))
+                })?;
+            decode_block_rewards(call.output())
+                .map_err(|err| SystemCallTraceError::Rewards(format!("{}: {err}", call.output())))?
+        }
+        None => Vec::new(),
+    };
+
+    // Fixed rewards are only paid in AuRa execution mode, see
+    // `GnosisEvmConfig::aura_execution_ctx`.
+    let chain_spec = evm_config.chain_spec();
+    let fixed_reward = chain_spec
+        .aura_config
+        .as_ref()
+        .filter(|_| !chain_spec.is_paris_active_at_block(header.number))
+        .and_then(|aura| aura.fixed_block_reward_at(header.number))
+        .map(|value| {
+            (
+                header.beneficiary,
+                GnosisRewardType::Block,
+                U256::from(value),
+            )
+        });
+
+    let rewards = contract_rewards
+        .into_iter()
+        .map(|(author, value)| (author, GnosisRewardType::External, value))
+        .chain(fixed_reward)
+        .map(|(author, reward_type, value)| {
+            RewardTrace::new(
+                header.hash(),
+                header.number,
+                GnosisRewardAction {
+                    author,
+                    reward_type,
+                    value,
+                },
+            )
+        })
+        .collect();
+
+    let system_calls = if include_system_calls {
+        calls
+            .into_iter()
+            .flat_map(|call| {
+                let phase = call.phase;
+                call.into_parity_traces()
+                    .into_iter()
+                    .map(move |trace| SystemCallTrace {
+                        system_call_phase: phase,
+                        trace: LocalizedTransactionTrace {
+                            trace,
+                            block_hash: Some(header.hash()),
+                            block_number: Some(header.number),
+                            transaction_hash: None,
+                            transaction_position: None,
+                        },
+                    })
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    Ok((system_calls, rewards))
+}
+
+/// Whether `filter` can select any reward. Its address lists select by
+/// membership, so the listed addresses and one unlisted address stand for
+/// every receiver.
+fn selects_rewards(filter: &TraceFilter) -> bool {
+    let listed: HashSet<Address> = filter
+        .from_address
+        .iter()
+        .chain(&filter.to_address)
+        .copied()
+        .collect();
+    let unlisted = (0u64..)
+        .map(|n| Address::from_word(U256::from(n).into()))
+        .find(|address| !listed.contains(address));
+    listed.iter().copied().chain(unlisted).any(|author| {
+        let action = GnosisRewardAction {
+            author,
+            reward_type: GnosisRewardType::External,
+            value: U256::ZERO,
+        };
+        RewardTrace::new(B256::ZERO, 0, action).matches(filter)
+    })
+}
+
+/// Drop reth's reward traces from `traces` and append the rewards of each of
+/// `blocks`, from `rewards`, after its transaction traces. Stops at `limit`
+/// entries, without asking for the rewards of later blocks.
+fn merge_rewards<E>(
+    traces: Vec<LocalizedTransactionTrace>,
+    blocks: impl IntoIterator<Item = u64>,
+    limit: usize,
+    mut rewards: impl FnMut(u64) -> Result<Vec<RewardTrace>, E>,
+) -> Result<Vec<GnosisLocalizedTrace>, E> {
+    let mut merged = Vec::with_capacity(traces.len());
+    let mut traces = traces
+        .into_iter()
+        .filter(|trace| !matches!(trace.trace.action, Action::Reward(_)))
+        .peekable();
+    for block_number in blocks {
+        while let Some(trace) =
+            traces.next_if(|trace| trace.block_number.is_some_and(|n| n <= block_number))
+        {
+            merged.push(GnosisLocalizedTrace::Transaction(trace));
+        }
+        if merged.len() >= limit {
+            break;
+        }
+        merged.extend(
+            rewards(block_number)?
+                .into_iter()
+                .map(GnosisLocalizedTrace::Reward),
+        );
+    }
+    merged.extend(traces.map(GnosisLocalizedTrace::Transaction));
+    merged.truncate(limit);
+    Ok(merged)
+}
+
+fn internal_error(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+fn invalid_params(msg: impl Into<String>) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INVALID_PARAMS_CODE, msg.into(), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_trace::parity::{CallAction, CallType};
+
+    fn reward(block_number: u64, author: u8, reward_type: GnosisRewardType) -> RewardTrace {
+        RewardTrace::new(
+            B256::repeat_byte(block_number as u8),
+            block_number,
+            GnosisRewardAction {
+                author: Address::repeat_byte(author),
+                reward_type,
+                value: U256::from(1_000),
+            },
+        )
+    }
+
+    fn trace(block_number: u64, action: Action) -> LocalizedTransactionTrace {
+        LocalizedTransactionTrace {
+            trace: TransactionTrace {
+                action,
+                error: None,
+                result: None,
+                subtraces: 0,
+                trace_address: Vec::new(),
+            },
+            block_hash: Some(B256::repeat_byte(block_number as u8)),
+            block_number: Some(block_number),
+            transaction_hash: None,
+            transaction_position: None,
+        }
+    }
+
+    fn call(block_number: u64) -> LocalizedTransactionTrace {
+        trace(
+            block_number,
+            Action::Call(CallAction {
+                from: Address::ZERO,
+                call_type: CallType::Call,
+                gas: 0,
+                input: Default::default(),
+                to: Address::ZERO,
+                value: U256::ZERO,
+            }),
+        )
+    }
+
+    #[test]
+    fn reward_trace_shape() {
+        let value = serde_json::to_value(reward(0x10, 1, GnosisRewardType::External)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "action": {
+                    "author": Address::repeat_byte(1),
+                    "rewardType": "external",
+                    "value": "0x3e8",
+                },
+                "blockHash": B256::repeat_byte(0x10),
+                "blockNumber": 16,
+                "result": null,
+                "subtraces": 0,
+                "traceAddress": [],
+                "transactionHash": null,
+                "transactionPosition": null,
+                "type": "reward",
+            })
+        );
+    }
+
+    #[test]
+    fn system_call_trace_shape() {
+        let value = serde_json::to_value(GnosisLocalizedTrace::SystemCall(SystemCallTrace {
+            system_call_phase: SystemCallPhase::PostBlock,
+            trace: call(0x10),
+        }))
+        .unwrap();
+        assert_eq!(value["systemCallPhase"], "postBlock");
+        assert_eq!(value["type"], "call");
+        assert_eq!(value["blockNumber"], 16);
+        assert!(value["transactionHash"].is_null());
+
+        let options: GnosisTraceOptions =
+            serde_json::from_value(serde_json::json!({ "includeSystemCalls": true })).unwrap();
+        assert!(options.include_system_calls);
+    }
+
+    fn order(merged: &[GnosisLocalizedTrace]) -> Vec<String> {
+        merged
+            .iter()
+            .map(|entry| match entry {
+                GnosisLocalizedTrace::Transaction(trace) => {
+                    format!("call{}", trace.block_number.unwrap())
+                }
+                GnosisLocalizedTrace::Reward(reward) => {
+                    format!("reward{}", reward.action.author[0])
+                }
+                GnosisLocalizedTrace::SystemCall(_) => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn replaces_reth_rewards_per_block() {
+        let reth_reward = trace(
+            1,
+            Action::Reward(RewardAction {
+                author: Address::ZERO,
+                reward_type: RewardType::Block,
+                value: U256::from(5),
+            }),
+        );
+        let merged = merge_rewards(
+            vec![call(1), reth_reward, call(2)],
+            1..=2,
+            usize::MAX,
+            |number| {
+                Ok::<_, SystemCallTraceError>(match number {
+                    1 => vec![
+                        reward(1, 1, GnosisRewardType::External),
+                        reward(1, 2, GnosisRewardType::External),
+                    ],
+                    _ => vec![reward(2, 3, GnosisRewardType::Block)],
+                })
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            order(&merged),
+            ["call1", "reward1", "reward2", "call2", "reward3"]
+        );
+    }
+
+    #[test]
+    fn stops_replaying_past_the_limit() {
+        let mut replayed = Vec::new();
+        let merged = merge_rewards(vec![call(1), call(2), call(3)], 1..=3, 3, |number| {
+            replayed.push(number);
+            Ok::<_, SystemCallTraceError>(vec![reward(
+                number,
+                number as u8,
+                GnosisRewardType::External,
+            )])
+        })
+        .unwrap();
+        assert_eq!(order(&merged), ["call1", "reward1", "call2"]);
+        assert_eq!(replayed, [1]);
+    }
+
+    #[test]
+    fn address_filters_select_rewards_by_receiver() {
+        let receiver = Address::repeat_byte(1);
+        assert!(selects_rewards(&TraceFilter::default()));
+        let receiver_only = TraceFilter {
+            from_address: vec![receiver],
+            to_address: vec![receiver],
+            ..Default::default()
+        };
+        assert!(selects_rewards(&receiver_only));
+        assert!(reward(1, 1, GnosisRewardType::External).matches(&receiver_only));
+        assert!(!reward(1, 2, GnosisRewardType::External).matches(&receiver_only));
+    }
+}
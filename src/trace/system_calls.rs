@@ -0,0 +1,349 @@
+//! Tracing of the system calls the block executor makes around a block's
+//! transactions: AuRa `getValidators` / `finalizeChange`, the beacon-root and
+//! block-hash calls, `executeSystemWithdrawals` and `reward`.
+//!
+//! All of them go through `GnosisEvm::transact_system_call`, which inspects
+//! the call whenever the EVM was created with an inspector. [`SystemCallTracer`]
+//! gives each top-level call from `SYSTEM_ADDRESS` its own
+//! [`TracingInspector`] and ignores everything else, so replaying a block with
+//! it through the regular executor yields one trace per system call. Bytecode
+//! rewrites edit state directly and have no call to trace; transaction
+//! permission checks run on an EVM of their own and change no state.
+
+use alloy_eips::eip4788::SYSTEM_ADDRESS;
+use alloy_primitives::{Address, Bytes, Log, U256};
+use alloy_rpc_types_trace::{
+    geth::{
+        CallConfig, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions,
+        GethDefaultTracingOptions, GethTrace,
+    },
+    parity::TransactionTrace,
+};
+use reth_evm::{
+    block::{BlockExecutionError, BlockExecutor, BlockExecutorFactory},
+    ConfigureEvm, Evm,
+};
+use reth_primitives_traits::RecoveredBlock;
+use reth_revm::{database::StateProviderDatabase, db::State};
+use reth_storage_api::StateProviderBox;
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    Inspector,
+};
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::{evm_config::GnosisEvmConfig, primitives::block::GnosisBlock};
+
+/// Part of block execution a system call was made in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SystemCallPhase {
+    /// `apply_pre_execution_changes`.
+    PreBlock,
+    /// `finish`: withdrawals and block rewards.
+    PostBlock,
+}
+
+/// Tracers system calls can be traced with.
+#[derive(Debug, Clone)]
+pub enum SystemCallTraceKind {
+    /// Geth's default struct logger.
+    Default(GethDefaultTracingOptions),
+    /// `callTracer`.
+    Call(CallConfig),
+}
+
+impl SystemCallTraceKind {
+    /// Tracer requested by `opts`.
+    pub fn from_options(opts: &GethDebugTracingOptions) -> Result<Self, SystemCallTraceError> {
+        match &opts.tracer {
+            None => Ok(Self::Default(opts.config.clone())),
+            Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer)) => {
+                let config = opts
+                    .tracer_config
+                    .clone()
+                    .into_call_config()
+                    .map_err(|err| SystemCallTraceError::TracerConfig(err.to_string()))?;
+                Ok(Self::Call(config))
+            }
+            Some(other) => Err(SystemCallTraceError::UnsupportedTracer(format!(
+                "{other:?}"
+            ))),
+        }
+    }
+
+    /// Configuration of the [`TracingInspector`] of each system call.
+    pub fn inspector_config(&self) -> TracingInspectorConfig {
+        match self {
+            Self::Default(config) => TracingInspectorConfig::from_geth_config(config),
+            Self::Call(config) => TracingInspectorConfig::from_geth_call_config(config),
+        }
+    }
+}
+
+/// Failure to trace a block's system calls.
+#[derive(Debug, thiserror::Error)]
+pub enum SystemCallTraceError {
+    #[error("system calls can only be traced with the default tracer or callTracer, not {0}")]
+    UnsupportedTracer(String),
+    #[error("invalid tracer config: {0}")]
+    TracerConfig(String),
+    #[error("failed to set up block execution: {0}")]
+    Setup(String),
+    #[error("invalid block rewards contract return: {0}")]
+    Rewards(String),
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+}
+
+/// One traced system call.
+#[derive(Debug)]
+pub struct TracedSystemCall {
+    pub phase: SystemCallPhase,
+    inspector: TracingInspector,
+}
+
+impl TracedSystemCall {
+    /// Called contract.
+    pub fn to(&self) -> Address {
+        self.inspector.traces().nodes()[0].trace.address
+    }
+
+    /// Calldata.
+    pub fn input(&self) -> &Bytes {
+        &self.inspector.traces().nodes()[0].trace.data
+    }
+
+    /// Return data.
+    pub fn output(&self) -> &Bytes {
+        &self.inspector.traces().nodes()[0].trace.output
+    }
+
+    /// Trace in the format of `kind`.
+    pub fn into_geth_trace(self, kind: &SystemCallTraceKind) -> GethTrace {
+        let root = &self.inspector.traces().nodes()[0].trace;
+        let (gas_used, output) = (root.gas_used, root.output.clone());
+        let builder = self.inspector.into_geth_builder();
+        match kind {
+            SystemCallTraceKind::Default(config) => {
+                builder.geth_traces(gas_used, output, config.clone()).into()
+            }
+            SystemCallTraceKind::Call(config) => builder.geth_call_traces(*config, gas_used).into(),
+        }
+    }
+
+    /// Parity-style traces of the call and its subcalls.
+    pub fn into_parity_traces(self) -> Vec<TransactionTrace> {
+        self.inspector
+            .into_parity_builder()
+            .into_transaction_traces()
+    }
+}
+
+/// Inspector that traces top-level calls from `SYSTEM_ADDRESS`, each into
+/// its own [`TracingInspector`].
+#[derive(Debug)]
+pub struct SystemCallTracer {
+    config: TracingInspectorConfig,
+    phase: SystemCallPhase,
+    /// Call depth of the frame being executed, 0 between calls.
+    depth: usize,
+    /// Tracer of the system call in progress.
+    active: Option<TracingInspector>,
+    calls: Vec<TracedSystemCall>,
+}
+
+impl SystemCallTracer {
+    pub fn new(config: TracingInspectorConfig) -> Self {
+        Self {
+            config,
+            phase: SystemCallPhase::PreBlock,
+            depth: 0,
+            active: None,
+            calls: Vec::new(),
+        }
+    }
+
+    /// Attribute the following system calls to `phase`.
+    pub fn set_phase(&mut self, phase: SystemCallPhase) {
+        self.phase = phase;
+    }
+
+    /// Traced calls, in execution order.
+    pub fn into_calls(self) -> Vec<TracedSystemCall> {
+        self.calls
+    }
+
+    /// Count a frame being entered; starts a trace for a top-level system call.
+    fn enter(&mut self, caller: Address) {
+        if self.depth == 0 && caller == SYSTEM_ADDRESS {
+            self.active = Some(TracingInspector::new(self.config));
+        }
+        self.depth += 1;
+    }
+
+    /// Count a frame being left; completes the trace of a top-level system call.
+    fn exit(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth == 0 {
+            if let Some(inspector) = self.active.take() {
+                self.calls.push(TracedSystemCall {
+                    phase: self.phase,
+                    inspector,
+                });
+            }
+        }
+    }
+}
+
+impl<CTX> Inspector<CTX> for SystemCallTracer
+where
+    TracingInspector: Inspector<CTX>,
+{
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        if let Some(active) = &mut self.active {
+            active.initialize_interp(interp, context);
+        }
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        if let Some(active) = &mut self.active {
+            active.step(interp, context);
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        if let Some(active) = &mut self.active {
+            active.step_end(interp, context);
+        }
+    }
+
+    fn log(&mut self, context: &mut CTX, log: Log) {
+        if let Some(active) = &mut self.active {
+            active.log(context, log);
+        }
+    }
+
+    fn log_full(&mut self, interp: &mut Interpreter, context: &mut CTX, log: Log) {
+        if let Some(active) = &mut self.active {
+            active.log_full(interp, context, log);
+        }
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.enter(inputs.caller);
+        self.active
+            .as_mut()
+            .and_then(|active| active.call(context, inputs))
+    }
+
+    fn call_end(&mut self, context: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
+        if let Some(active) = &mut self.active {
+            active.call_end(context, inputs, outcome);
+        }
+        self.exit();
+    }
+
+    fn create(&mut self, context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        self.enter(inputs.caller());
+        self.active
+            .as_mut()
+            .and_then(|active| active.create(context, inputs))
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut CTX,
+        inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        if let Some(active) = &mut self.active {
+            active.create_end(context, inputs, outcome);
+        }
+        self.exit();
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if let Some(active) = &mut self.active {
+            Inspector::<CTX>::selfdestruct(active, contract, target, value);
+        }
+    }
+}
+
+/// Replay `block` on `state` (the state after its parent) and trace every
+/// system call the executor makes, each with a [`TracingInspector`] of
+/// `config`.
+///
+/// This runs the same executor as import, with read-only finality: the
+/// block's AuRa finality state is recomputed from its parent's, but neither
+/// cached nor persisted.
+pub fn trace_block_system_calls(
+    evm_config: &GnosisEvmConfig,
+    state: StateProviderBox,
+    block: &RecoveredBlock<GnosisBlock>,
+    config: TracingInspectorConfig,
+) -> Result<Vec<TracedSystemCall>, SystemCallTraceError> {
+    let evm_config = evm_config.clone().with_read_only_finality();
+    let mut db = State::builder()
+        .with_database(StateProviderDatabase::new(state))
+        .with_bundle_update()
+        .build();
+    let evm_env = evm_config
+        .evm_env(block.header())
+        .map_err(|err| SystemCallTraceError::Setup(err.to_string()))?;
+    let ctx = evm_config
+        .context_for_block(block.sealed_block())
+        .map_err(|err| SystemCallTraceError::Setup(err.to_string()))?;
+
+    let mut tracer = SystemCallTracer::new(config);
+    let evm = evm_config.evm_with_env_and_inspector(&mut db, evm_env, &mut tracer);
+    let mut executor = evm_config
+        .block_executor_factory()
+        .create_executor(evm, ctx);
+    executor.apply_pre_execution_changes()?;
+    for tx in block.transactions_recovered() {
+        executor.execute_transaction(tx)?;
+    }
+    executor
+        .evm_mut()
+        .inspector_mut()
+        .set_phase(SystemCallPhase::PostBlock);
+    executor.finish()?;
+
+    Ok(tracer.into_calls())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_trace::geth::GethDebugTracerConfig;
+
+    #[test]
+    fn supported_tracers() {
+        let default = GethDebugTracingOptions::default();
+        assert!(matches!(
+            SystemCallTraceKind::from_options(&default),
+            Ok(SystemCallTraceKind::Default(_))
+        ));
+
+        let call = GethDebugTracingOptions {
+            tracer: Some(GethDebugBuiltInTracerType::CallTracer.into()),
+            tracer_config: GethDebugTracerConfig(serde_json::json!({ "onlyTopCall": true })),
+            ..Default::default()
+        };
+        match SystemCallTraceKind::from_options(&call) {
+            Ok(SystemCallTraceKind::Call(config)) => assert_eq!(config.only_top_call, Some(true)),
+            other => panic!("expected callTracer, got {other:?}"),
+        }
+
+        let prestate = GethDebugTracingOptions {
+            tracer: Some(GethDebugBuiltInTracerType::PreStateTracer.into()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            SystemCallTraceKind::from_options(&prestate),
+            Err(SystemCallTraceError::UnsupportedTracer(_))
+        ));
+    }
+}
@@ -0,0 +1,146 @@
+//! End-to-end test of AuRa block authoring (`--gnosis.aura-signer`).
+//!
+//! Launches a node on a single-validator AuRa devnet and drives its engine
+//! with [`EngineAuthoringBackend`] the way `main` does, then checks that the
+//! authored blocks became canonical and are sealed by the signer.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::Address;
+use reth::{
+    args::DatadirArgs,
+    builder::{NodeBuilder, NodeConfig},
+    dirs::MaybePlatformPath,
+    CliRunner,
+};
+use reth_gnosis::{
+    aura::{
+        author::{run_aura_authoring, AuraAuthor, AuraSigner, EngineAuthoringBackend},
+        seal::recover_seal_author,
+    },
+    spec::gnosis_spec::GnosisChainSpec,
+    GnosisArgs, GnosisNode,
+};
+use reth_provider::{BlockNumReader, HeaderProvider};
+use serde_json::json;
+
+/// Blocks the test waits for.
+const BLOCKS: u64 = 3;
+
+/// Devnet whose only validator is `signer`, stepping every second and
+/// merging far beyond the blocks the test authors.
+fn devnet(signer: Address) -> GnosisChainSpec {
+    let genesis = json!({
+        "config": {
+            "chainId": 10_200_042,
+            "homesteadBlock": 0,
+            "eip150Block": 0,
+            "eip155Block": 0,
+            "eip158Block": 0,
+            "byzantiumBlock": 0,
+            "constantinopleBlock": 0,
+            "petersburgBlock": 0,
+            "istanbulBlock": 0,
+            "berlinBlock": 0,
+            "londonBlock": 0,
+            "terminalTotalDifficulty": "0xffffffffffffffffffffffffffffffff",
+            "terminalBlockNumber": 1000,
+            "eip1559collector": Address::ZERO,
+            "blockRewardsContract": Address::ZERO,
+            "aura": {
+                "stepDuration": 1,
+                "validators": { "multi": { "0": { "list": [signer] } } }
+            }
+        },
+        "difficulty": "0x20000",
+        "gasLimit": "0x1c9c380",
+        "timestamp": "0x0",
+        "alloc": {}
+    });
+    GnosisChainSpec::from_genesis(serde_json::from_value(genesis).unwrap()).unwrap()
+}
+
+#[test]
+fn authors_blocks_through_the_engine() -> eyre::Result<()> {
+    let dir =
+        std::env::temp_dir().join(format!("reth-gnosis-aura-authoring-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let keyfile = dir.join("signer.key");
+    std::fs::write(
+        &keyfile,
+        format!("{:x}", alloy_primitives::B256::repeat_byte(0x42)),
+    )?;
+    let signer = AuraSigner::from_keyfile(&keyfile)?.address();
+    let spec = Arc::new(devnet(signer));
+
+    let runner = CliRunner::try_default_runtime()?;
+    let runtime = runner.runtime();
+    runner.run_until_ctrl_c(async move {
+        let db = reth_db::init_db(dir.join("db"), Default::default())?;
+        let config = NodeConfig::new(spec.clone())
+            .with_unused_ports()
+            .with_datadir_args(DatadirArgs {
+                datadir: MaybePlatformPath::from(dir.clone()),
+                ..Default::default()
+            });
+        let handle = NodeBuilder::new(config)
+            .with_database(db)
+            .with_launch_context(runtime)
+            .node(GnosisNode::with_args(GnosisArgs {
+                aura_signer: Some(keyfile),
+                ..GnosisArgs::DEFAULT
+            }))
+            .launch_with_debug_capabilities()
+            .await?;
+
+        let node = &handle.node;
+        let author: Arc<AuraAuthor> = node
+            .evm_config
+            .aura_author()
+            .cloned()
+            .expect("--gnosis.aura-signer sets the author");
+        let backend = EngineAuthoringBackend::new(
+            node.provider.clone(),
+            spec.clone(),
+            node.add_ons_handle.beacon_engine_handle.clone(),
+            node.add_ons_handle.engine_events.clone(),
+            node.payload_builder_handle.clone(),
+        );
+        let authoring = tokio::spawn(run_aura_authoring(
+            author,
+            spec,
+            node.evm_config.finality_cache.clone(),
+            backend,
+        ));
+
+        // One block per one-second step, plus slack for a slow machine.
+        let deadline = Instant::now() + Duration::from_secs(5 * BLOCKS + 10);
+        while node.provider.best_block_number()? < BLOCKS {
+            eyre::ensure!(!authoring.is_finished(), "authoring stopped");
+            eyre::ensure!(
+                Instant::now() < deadline,
+                "no block {BLOCKS} authored in time"
+            );
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        authoring.abort();
+
+        for number in 1..=BLOCKS {
+            let header = node
+                .provider
+                .sealed_header(number)?
+                .ok_or_else(|| eyre::eyre!("missing canonical block {number}"))?;
+            assert_eq!(header.beneficiary, signer, "block {number}");
+            assert_eq!(
+                recover_seal_author(header.header())?,
+                signer,
+                "block {number}"
+            );
+        }
+        Ok(())
+    })
+}
@@ -1,7 +1,7 @@
 //! Integration tests for bytecode rewrite via full block execution.
 //!
-//! These tests verify that the Balancer hardfork bytecode rewrites are correctly
-//! applied during block execution, specifically in `apply_pre_execution_changes`.
+//! These tests verify that contract rewrite forks (here configured via the legacy
+//! Balancer hardfork keys) are correctly applied during block execution, specifically in `apply_pre_execution_changes`.
 //!
 //! This tests the actual production code path rather than calling `rewrite_bytecodes` directly.
 
@@ -12,7 +12,9 @@ use reth_evm::{Evm, EvmFactory};
 use reth_evm_ethereum::RethReceiptBuilder;
 use reth_gnosis::block::{GnosisBlockExecutionCtx, GnosisBlockExecutor};
 use reth_gnosis::evm::factory::GnosisEvmFactory;
-use reth_gnosis::spec::gnosis_spec::{BalancerHardforkConfig, GnosisChainSpec, GnosisHardForks};
+use reth_gnosis::spec::contract_rewrites::ContractRewriteFork;
+use reth_gnosis::spec::fee_collector::FeeCollectorSchedule;
+use reth_gnosis::spec::gnosis_spec::{GnosisChainSpec, GnosisHardForks};
 use revm::context::{BlockEnv, CfgEnv};
 use revm::database::{CacheDB, EmptyDB};
 use revm::Database;
@@ -28,6 +30,12 @@ fn create_test_chain_spec_with_balancer_hardfork() -> GnosisChainSpec {
     // Create genesis with extra_fields for BalancerFork
     // The GnosisChainSpec::from(Genesis) reads these to configure the hardfork
     let mut genesis = alloy_genesis::Genesis::default();
+    for key in ["eip1559collector", "blockRewardsContract"] {
+        genesis.config.extra_fields.insert(
+            key.to_string(),
+            json!("0x0000000000000000000000000000000000000000"),
+        );
+    }
     genesis.config.extra_fields.insert(
         "balancerHardforkTime".to_string(),
         json!(HARDFORK_ACTIVATION_TIME),
@@ -67,15 +75,27 @@ fn create_execution_ctx(parent_timestamp: u64) -> GnosisBlockExecutionCtx<'stati
         parent_timestamp,
         aura: None,
         block_rewards_override: None,
+        tx_permission_contract: None,
+        transactions: None,
     }
 }
 
+/// `(address, new code, new code hash)` of every code rewrite in `config`.
+fn code_rewrites(config: &ContractRewriteFork) -> Vec<(Address, Option<Bytecode>, B256)> {
+    config
+        .accounts
+        .iter()
+        .filter_map(|(addr, rewrite)| Some((*addr, rewrite.code.clone()?, rewrite.code_hash()?)))
+        .collect()
+}
+
 /// Sets up a database with "wrong" bytecode at the target addresses.
-fn setup_db_with_wrong_bytecode(config: &BalancerHardforkConfig) -> CacheDB<EmptyDB> {
+fn setup_db_with_wrong_bytecode(config: &ContractRewriteFork) -> CacheDB<EmptyDB> {
     let mut db = CacheDB::new(EmptyDB::default());
+    let rewrites = code_rewrites(config);
     let wrong_code = Bytecode::new_legacy(Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]));
 
-    for (addr, _, _) in &config.config {
+    for (addr, _, _) in &rewrites {
         db.insert_account_info(
             *addr,
             AccountInfo {
@@ -91,10 +111,11 @@ fn setup_db_with_wrong_bytecode(config: &BalancerHardforkConfig) -> CacheDB<Empt
 }
 
 /// Sets up a database with correct bytecode (already rewritten).
-fn setup_db_with_correct_bytecode(config: &BalancerHardforkConfig) -> CacheDB<EmptyDB> {
+fn setup_db_with_correct_bytecode(config: &ContractRewriteFork) -> CacheDB<EmptyDB> {
     let mut db = CacheDB::new(EmptyDB::default());
+    let rewrites = code_rewrites(config);
 
-    for (addr, expected_code, expected_hash) in &config.config {
+    for (addr, expected_code, expected_hash) in &rewrites {
         db.insert_account_info(
             *addr,
             AccountInfo {
@@ -113,7 +134,8 @@ fn setup_db_with_correct_bytecode(config: &BalancerHardforkConfig) -> CacheDB<Em
 fn test_bytecode_rewrite_at_hardfork_activation_block() {
     // Setup: Chain spec with Balancer hardfork at timestamp 1000
     let spec = create_test_chain_spec_with_balancer_hardfork();
-    let config = spec.balancer_hardfork_config.as_ref().unwrap();
+    let config = &spec.contract_rewrites[0];
+    let rewrites = code_rewrites(config);
 
     // Parent block at timestamp 999 (BEFORE hardfork)
     // Current block at timestamp 1000 (AT hardfork activation)
@@ -136,7 +158,7 @@ fn test_bytecode_rewrite_at_hardfork_activation_block() {
 
     // Create EVM and executor
     let factory = GnosisEvmFactory {
-        fee_collector_address: Address::ZERO,
+        fee_collectors: FeeCollectorSchedule::fixed(Address::ZERO),
     };
     let evm_env = create_block_env(current_timestamp);
     let evm = factory.create_evm(&mut state, evm_env);
@@ -158,7 +180,7 @@ fn test_bytecode_rewrite_at_hardfork_activation_block() {
     let _ = executor.apply_pre_execution_changes();
 
     // Verify bytecode was rewritten for ALL configured addresses
-    for (addr, _expected_code, expected_hash) in &config.config {
+    for (addr, _expected_code, expected_hash) in &rewrites {
         let account = executor.evm_mut().db_mut().basic(*addr).unwrap().unwrap();
         assert_eq!(
             account.code_hash, *expected_hash,
@@ -178,7 +200,8 @@ fn test_bytecode_rewrite_at_hardfork_activation_block() {
 fn test_bytecode_not_rewritten_after_hardfork_activation() {
     // Setup: Chain spec with Balancer hardfork at timestamp 1000
     let spec = create_test_chain_spec_with_balancer_hardfork();
-    let config = spec.balancer_hardfork_config.as_ref().unwrap();
+    let config = &spec.contract_rewrites[0];
+    let rewrites = code_rewrites(config);
 
     // Parent block at timestamp 1000 (AFTER hardfork - already active)
     // Current block at timestamp 1001 (still active)
@@ -201,7 +224,7 @@ fn test_bytecode_not_rewritten_after_hardfork_activation() {
     let wrong_hash = db
         .cache
         .accounts
-        .get(&config.config[0].0)
+        .get(&rewrites[0].0)
         .unwrap()
         .info
         .code_hash;
@@ -209,7 +232,7 @@ fn test_bytecode_not_rewritten_after_hardfork_activation() {
 
     // Create EVM and executor
     let factory = GnosisEvmFactory {
-        fee_collector_address: Address::ZERO,
+        fee_collectors: FeeCollectorSchedule::fixed(Address::ZERO),
     };
     let evm_env = create_block_env(current_timestamp);
     let evm = factory.create_evm(&mut state, evm_env);
@@ -223,7 +246,7 @@ fn test_bytecode_not_rewritten_after_hardfork_activation() {
     let _ = executor.apply_pre_execution_changes();
 
     // Verify bytecode was NOT rewritten (still has wrong code)
-    let (addr, _, expected_hash) = &config.config[0];
+    let (addr, _, expected_hash) = &rewrites[0];
     let account = executor.evm_mut().db_mut().basic(*addr).unwrap().unwrap();
     assert_ne!(
         account.code_hash, *expected_hash,
@@ -239,7 +262,8 @@ fn test_bytecode_not_rewritten_after_hardfork_activation() {
 fn test_bytecode_not_rewritten_before_hardfork() {
     // Setup: Chain spec with Balancer hardfork at timestamp 1000
     let spec = create_test_chain_spec_with_balancer_hardfork();
-    let config = spec.balancer_hardfork_config.as_ref().unwrap();
+    let config = &spec.contract_rewrites[0];
+    let rewrites = code_rewrites(config);
 
     // Parent block at timestamp 998 (before hardfork)
     // Current block at timestamp 999 (still before hardfork)
@@ -261,7 +285,7 @@ fn test_bytecode_not_rewritten_before_hardfork() {
     let wrong_hash = db
         .cache
         .accounts
-        .get(&config.config[0].0)
+        .get(&rewrites[0].0)
         .unwrap()
         .info
         .code_hash;
@@ -269,7 +293,7 @@ fn test_bytecode_not_rewritten_before_hardfork() {
 
     // Create EVM and executor
     let factory = GnosisEvmFactory {
-        fee_collector_address: Address::ZERO,
+        fee_collectors: FeeCollectorSchedule::fixed(Address::ZERO),
     };
     let evm_env = create_block_env(current_timestamp);
     let evm = factory.create_evm(&mut state, evm_env);
@@ -283,7 +307,7 @@ fn test_bytecode_not_rewritten_before_hardfork() {
     let _ = executor.apply_pre_execution_changes();
 
     // Verify bytecode was NOT rewritten
-    let (addr, _, expected_hash) = &config.config[0];
+    let (addr, _, expected_hash) = &rewrites[0];
     let account = executor.evm_mut().db_mut().basic(*addr).unwrap().unwrap();
     assert_ne!(
         account.code_hash, *expected_hash,
@@ -299,7 +323,8 @@ fn test_bytecode_not_rewritten_before_hardfork() {
 fn test_bytecode_rewrite_idempotent_via_block_execution() {
     // Setup: Chain spec with Balancer hardfork at timestamp 1000
     let spec = create_test_chain_spec_with_balancer_hardfork();
-    let config = spec.balancer_hardfork_config.as_ref().unwrap();
+    let config = &spec.contract_rewrites[0];
+    let rewrites = code_rewrites(config);
 
     // At activation boundary
     let parent_timestamp = HARDFORK_ACTIVATION_TIME - 1;
@@ -311,7 +336,7 @@ fn test_bytecode_rewrite_idempotent_via_block_execution() {
 
     // Create EVM and executor
     let factory = GnosisEvmFactory {
-        fee_collector_address: Address::ZERO,
+        fee_collectors: FeeCollectorSchedule::fixed(Address::ZERO),
     };
     let evm_env = create_block_env(current_timestamp);
     let evm = factory.create_evm(&mut state, evm_env);
@@ -325,7 +350,7 @@ fn test_bytecode_rewrite_idempotent_via_block_execution() {
     let _ = executor.apply_pre_execution_changes();
 
     // Verify bytecode still has correct values (idempotent)
-    for (addr, _, expected_hash) in &config.config {
+    for (addr, _, expected_hash) in &rewrites {
         let account = executor.evm_mut().db_mut().basic(*addr).unwrap().unwrap();
         assert_eq!(
             account.code_hash, *expected_hash,
@@ -1,10 +1,23 @@
 use alloy_consensus::constants::KECCAK_EMPTY;
+use reth_chainspec::ForkCondition;
 use reth_gnosis::consts::parse_balancer_hardfork_config;
+use reth_gnosis::spec::contract_rewrites::{
+    AccountRewrite, ContractRewriteError, ContractRewriteFork,
+};
+use reth_gnosis::spec::gnosis_spec::GnosisHardfork;
 use serde_json::json;
 
+/// The single account rewrite of a parsed config.
+fn only_account(config: &ContractRewriteFork) -> &AccountRewrite {
+    assert_eq!(config.accounts.len(), 1);
+    config.accounts.values().next().unwrap()
+}
+
 #[test]
 fn test_returns_none_when_both_inputs_missing() {
-    assert!(parse_balancer_hardfork_config(None, None).is_none());
+    assert!(parse_balancer_hardfork_config(None, None)
+        .unwrap()
+        .is_none());
 }
 
 #[test]
@@ -12,13 +25,17 @@ fn test_returns_none_when_time_missing() {
     let config = json!({
         "0x1111111111111111111111111111111111111111": ""
     });
-    assert!(parse_balancer_hardfork_config(None, Some(&config)).is_none());
+    assert!(parse_balancer_hardfork_config(None, Some(&config))
+        .unwrap()
+        .is_none());
 }
 
 #[test]
 fn test_returns_none_when_config_missing() {
     let time = json!(1000);
-    assert!(parse_balancer_hardfork_config(Some(&time), None).is_none());
+    assert!(parse_balancer_hardfork_config(Some(&time), None)
+        .unwrap()
+        .is_none());
 }
 
 #[test]
@@ -29,12 +46,13 @@ fn test_parses_valid_config() {
         "0x2222222222222222222222222222222222222222": "6080"
     });
 
-    let result = parse_balancer_hardfork_config(Some(&time), Some(&config));
+    let result = parse_balancer_hardfork_config(Some(&time), Some(&config)).unwrap();
     assert!(result.is_some());
 
     let hardfork_config = result.unwrap();
-    assert_eq!(hardfork_config.activation_time, 12345);
-    assert_eq!(hardfork_config.config.len(), 2);
+    assert_eq!(hardfork_config.hardfork, GnosisHardfork::BalancerFork);
+    assert_eq!(hardfork_config.activation, ForkCondition::Timestamp(12345));
+    assert_eq!(hardfork_config.accounts.len(), 2);
 }
 
 #[test]
@@ -45,15 +63,18 @@ fn test_empty_bytecode_string_results_in_none_with_keccak_empty() {
     });
 
     let result = parse_balancer_hardfork_config(Some(&time), Some(&config))
+        .unwrap()
         .expect("Should parse successfully");
 
-    let (_, bytecode, code_hash) = &result.config[0];
+    let account = only_account(&result);
+    let bytecode = account.code.as_ref().expect("Code should be rewritten");
+    let code_hash = account.code_hash().unwrap();
     assert!(
         bytecode.is_none(),
         "Empty string should result in None bytecode"
     );
     assert_eq!(
-        *code_hash, KECCAK_EMPTY,
+        code_hash, KECCAK_EMPTY,
         "Empty bytecode should have KECCAK_EMPTY hash"
     );
 }
@@ -66,9 +87,10 @@ fn test_handles_bytecode_with_0x_prefix() {
     });
 
     let result = parse_balancer_hardfork_config(Some(&time), Some(&config))
+        .unwrap()
         .expect("Should parse successfully");
 
-    let (_, bytecode, _) = &result.config[0];
+    let bytecode = only_account(&result).code.as_ref().unwrap();
     assert!(bytecode.is_some(), "Should parse bytecode with 0x prefix");
 }
 
@@ -80,9 +102,10 @@ fn test_handles_bytecode_without_0x_prefix() {
     });
 
     let result = parse_balancer_hardfork_config(Some(&time), Some(&config))
+        .unwrap()
         .expect("Should parse successfully");
 
-    let (_, bytecode, _) = &result.config[0];
+    let bytecode = only_account(&result).code.as_ref().unwrap();
     assert!(
         bytecode.is_some(),
         "Should parse bytecode without 0x prefix"
@@ -99,9 +122,10 @@ fn test_handles_multiple_addresses() {
     });
 
     let result = parse_balancer_hardfork_config(Some(&time), Some(&config))
+        .unwrap()
         .expect("Should parse successfully");
 
-    assert_eq!(result.config.len(), 3, "Should have 3 addresses");
+    assert_eq!(result.accounts.len(), 3, "Should have 3 addresses");
 }
 
 #[test]
@@ -113,14 +137,42 @@ fn test_code_hash_matches_bytecode() {
     });
 
     let result = parse_balancer_hardfork_config(Some(&time), Some(&config))
+        .unwrap()
         .expect("Should parse successfully");
 
-    let (_, bytecode, code_hash) = &result.config[0];
-    let bytecode = bytecode.as_ref().expect("Should have bytecode");
+    let account = only_account(&result);
+    let bytecode = account
+        .code
+        .as_ref()
+        .unwrap()
+        .as_ref()
+        .expect("Should have bytecode");
 
     assert_eq!(
         bytecode.hash_slow(),
-        *code_hash,
+        account.code_hash().unwrap(),
         "Code hash should match the bytecode's hash"
     );
 }
+
+#[test]
+fn test_returns_errors_on_malformed_input() {
+    let config = json!({
+        "0x1111111111111111111111111111111111111111": "6080"
+    });
+    assert!(matches!(
+        parse_balancer_hardfork_config(Some(&json!("soon")), Some(&config)),
+        Err(ContractRewriteError::Json {
+            key: "balancerHardforkTime",
+            ..
+        })
+    ));
+
+    let bad_code = json!({
+        "0x1111111111111111111111111111111111111111": "not hex"
+    });
+    assert!(matches!(
+        parse_balancer_hardfork_config(Some(&json!(1000)), Some(&bad_code)),
+        Err(ContractRewriteError::Code { .. })
+    ));
+}
@@ -1,10 +1,11 @@
 use alloy_consensus::constants::KECCAK_EMPTY;
-use alloy_primitives::{keccak256, Address, Bytes, U256};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
 use reth_evm::{env::EvmEnv, Evm, EvmFactory};
 use reth_gnosis::consts::parse_balancer_hardfork_config;
 use reth_gnosis::evm::factory::GnosisEvmFactory;
 use reth_gnosis::gnosis::rewrite_bytecodes;
-use reth_gnosis::spec::gnosis_spec::BalancerHardforkConfig;
+use reth_gnosis::spec::contract_rewrites::{parse_contract_rewrites, ContractRewriteFork};
+use reth_gnosis::spec::fee_collector::FeeCollectorSchedule;
 use revm::context::{BlockEnv, CfgEnv};
 use revm::database::{CacheDB, EmptyDB};
 use revm::Database;
@@ -13,7 +14,7 @@ use serde_json::json;
 
 const TEST_BYTECODE: &str = "6080604052348015600e575f5ffd5b50603e80601a5f395ff3fe60806040525f5ffdfea2646970667358221220f7f53e1645a9cd5b79da6920c67891306d178dcff5e5683946cc1dae3c65aed664736f6c634300081e0033";
 
-fn get_test_hardfork_config() -> BalancerHardforkConfig {
+fn get_test_hardfork_config() -> ContractRewriteFork {
     let time_value = json!(1000);
     let config_value = json!({
         "0x1111111111111111111111111111111111111111": "",
@@ -21,6 +22,16 @@ fn get_test_hardfork_config() -> BalancerHardforkConfig {
     });
     parse_balancer_hardfork_config(Some(&time_value), Some(&config_value))
         .expect("Test config should parse successfully")
+        .expect("Both keys are set")
+}
+
+/// `(address, new code, new code hash)` of every code rewrite in `config`.
+fn code_rewrites(config: &ContractRewriteFork) -> Vec<(Address, Option<Bytecode>, B256)> {
+    config
+        .accounts
+        .iter()
+        .filter_map(|(addr, rewrite)| Some((*addr, rewrite.code.clone()?, rewrite.code_hash()?)))
+        .collect()
 }
 
 /// Creates a minimal EvmEnv for testing purposes
@@ -34,7 +45,7 @@ fn create_test_evm_env() -> EvmEnv {
 /// Creates a GnosisEvm instance with a CacheDB for testing
 fn create_test_evm(db: CacheDB<EmptyDB>) -> impl Evm<DB = CacheDB<EmptyDB>> {
     let factory = GnosisEvmFactory {
-        fee_collector_address: Address::ZERO,
+        fee_collectors: FeeCollectorSchedule::fixed(Address::ZERO),
     };
     factory.create_evm(db, create_test_evm_env())
 }
@@ -44,11 +55,11 @@ fn test_rewrite_bytecodes_rewrites_when_code_differs() {
     // Get the rewrite config to know what addresses and expected codes we're testing
     let config = get_test_hardfork_config();
     assert!(
-        !config.config.is_empty(),
+        !config.accounts.is_empty(),
         "Should have at least one rewrite configured"
     );
 
-    let (addr, _expected_bytecode, expected_code_hash) = config.config[0].clone();
+    let (addr, _expected_bytecode, expected_code_hash) = code_rewrites(&config)[0].clone();
 
     // Setup: Create account with DIFFERENT code than what rewrite expects
     let mut db = CacheDB::new(EmptyDB::default());
@@ -92,7 +103,7 @@ fn test_rewrite_bytecodes_rewrites_when_code_differs() {
 #[test]
 fn test_rewrite_bytecodes_skips_when_code_matches_expected() {
     let config = get_test_hardfork_config();
-    let (addr, expected_bytecode, expected_code_hash) = config.config[0].clone();
+    let (addr, expected_bytecode, expected_code_hash) = code_rewrites(&config)[0].clone();
 
     // Setup: Account already has the expected code hash
     let mut db = CacheDB::new(EmptyDB::default());
@@ -125,13 +136,12 @@ fn test_rewrite_bytecodes_handles_nonexistent_account() {
     // Find an address that should get actual bytecode (not None)
     // When bytecode is None and account doesn't exist, skip is expected
     // (KECCAK_EMPTY == KECCAK_EMPTY)
-    let code_entry = config
-        .config
-        .iter()
+    let code_entry = code_rewrites(&config)
+        .into_iter()
         .find(|(_, bytecode, _)| bytecode.is_some())
         .expect("Should have at least one address with bytecode");
 
-    let (addr, _expected_bytecode, expected_code_hash) = code_entry.clone();
+    let (addr, _expected_bytecode, expected_code_hash) = code_entry;
 
     // Setup: Empty database - account doesn't exist
     let db = CacheDB::new(EmptyDB::default());
@@ -160,7 +170,7 @@ fn test_rewrite_bytecodes_all_addresses_processed() {
     let mut db = CacheDB::new(EmptyDB::default());
     let wrong_code = Bytecode::new_legacy(Bytes::from_static(&[0xff, 0xff]));
 
-    for (addr, _, _) in &config.config {
+    for (addr, _, _) in &code_rewrites(&config) {
         db.insert_account_info(
             *addr,
             AccountInfo {
@@ -178,7 +188,7 @@ fn test_rewrite_bytecodes_all_addresses_processed() {
     rewrite_bytecodes(&mut evm, &config);
 
     // Assert: ALL addresses should have been rewritten
-    for (addr, _expected_bytecode, expected_code_hash) in &config.config {
+    for (addr, _expected_bytecode, expected_code_hash) in &code_rewrites(&config) {
         let account = evm.db_mut().basic(*addr).unwrap().unwrap();
         assert_eq!(
             account.code_hash, *expected_code_hash,
@@ -191,10 +201,8 @@ fn test_rewrite_bytecodes_all_addresses_processed() {
 fn test_rewrite_bytecodes_clears_code_when_none() {
     // Find the address that should have its code cleared (bytecode = None)
     let config = get_test_hardfork_config();
-    let clear_entry = config
-        .config
-        .iter()
-        .find(|(_, bytecode, _)| bytecode.is_none());
+    let rewrites = code_rewrites(&config);
+    let clear_entry = rewrites.iter().find(|(_, bytecode, _)| bytecode.is_none());
 
     if let Some((addr, _, expected_code_hash)) = clear_entry {
         assert_eq!(
@@ -237,10 +245,8 @@ fn test_rewrite_bytecodes_clears_code_when_none() {
 fn test_rewrite_bytecodes_sets_specific_code() {
     // Find an address that should get specific bytecode (not None)
     let config = get_test_hardfork_config();
-    let code_entry = config
-        .config
-        .iter()
-        .find(|(_, bytecode, _)| bytecode.is_some());
+    let rewrites = code_rewrites(&config);
+    let code_entry = rewrites.iter().find(|(_, bytecode, _)| bytecode.is_some());
 
     if let Some((addr, expected_bytecode, expected_code_hash)) = code_entry {
         // Setup: Account with no code
@@ -278,7 +284,7 @@ fn test_rewrite_bytecodes_idempotent() {
 
     // Setup: Account with wrong code
     let mut db = CacheDB::new(EmptyDB::default());
-    for (addr, _, _) in &config.config {
+    for (addr, _, _) in &code_rewrites(&config) {
         db.insert_account_info(
             *addr,
             AccountInfo {
@@ -297,8 +303,55 @@ fn test_rewrite_bytecodes_idempotent() {
     rewrite_bytecodes(&mut evm, &config); // Second call should be a no-op
 
     // Assert: All accounts still have correct code
-    for (addr, _, expected_code_hash) in &config.config {
+    for (addr, _, expected_code_hash) in &code_rewrites(&config) {
         let account = evm.db_mut().basic(*addr).unwrap().unwrap();
         assert_eq!(account.code_hash, *expected_code_hash);
     }
 }
+
+#[test]
+fn test_rewrite_bytecodes_sets_storage_balance_and_nonce() {
+    let value = json!([{
+        "name": "TestRewrite",
+        "block": 10,
+        "accounts": {
+            "0x3333333333333333333333333333333333333333": {
+                "storage": { "0x1": "0x2a" },
+                "balance": "0x64",
+                "nonce": 3
+            }
+        }
+    }]);
+    let forks = parse_contract_rewrites(Some(&value)).expect("Should parse successfully");
+    let addr = Address::from([0x33; 20]);
+
+    // Setup: Account with code, which the rewrite does not touch
+    let mut db = CacheDB::new(EmptyDB::default());
+    let code = Bytecode::new_legacy(Bytes::from_static(&[0x60, 0x00]));
+    db.insert_account_info(
+        addr,
+        AccountInfo {
+            balance: U256::from(1),
+            nonce: 1,
+            code_hash: code.hash_slow(),
+            code: Some(code.clone()),
+            account_id: None,
+        },
+    );
+
+    let mut evm = create_test_evm(db);
+    rewrite_bytecodes(&mut evm, &forks[0]);
+
+    let account = evm.db_mut().basic(addr).unwrap().unwrap();
+    assert_eq!(
+        account.code_hash,
+        code.hash_slow(),
+        "Code should be preserved"
+    );
+    assert_eq!(account.balance, U256::from(100));
+    assert_eq!(account.nonce, 3);
+    assert_eq!(
+        evm.db_mut().storage(addr, U256::from(1)).unwrap(),
+        U256::from(42)
+    );
+}